@@ -30,6 +30,26 @@ pub struct ViabilityKernel {
 }
 
 impl ViabilityKernel {
+    /// Polytope must be well-formed: one bound per row, 7 coefficients per row.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.a.len() != self.b.len() {
+            anyhow::bail!(
+                "viability kernel {} has {} constraint rows but {} bounds",
+                self.mode_id,
+                self.a.len(),
+                self.b.len()
+            );
+        }
+        if self.a.iter().any(|row| row.len() != 7) {
+            anyhow::bail!("viability kernel rows must have 7 coefficients (SwarmState7D)");
+        }
+        let finite = self.a.iter().flatten().chain(&self.b).all(|v| v.is_finite());
+        if !finite || !self.min_integrity.is_finite() || !self.min_chi.is_finite() {
+            anyhow::bail!("viability kernel coefficients must be finite");
+        }
+        Ok(())
+    }
+
     pub fn is_viable(
         &self,
         state: &SwarmState7D,
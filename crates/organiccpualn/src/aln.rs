@@ -0,0 +1,679 @@
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Parse error for ALN shards, located by file, line and column (1-based).
+///
+/// Errors that concern the whole document (I/O, missing sections) use line 0.
+#[derive(Clone, Debug, thiserror::Error)]
+pub struct AlnError {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub kind: AlnErrorKind,
+}
+
+impl fmt::Display for AlnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}: {}", self.file, self.kind)
+        } else {
+            write!(
+                f,
+                "{}:{}:{}: {}",
+                self.file, self.line, self.column, self.kind
+            )
+        }
+    }
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum AlnErrorKind {
+    #[error("I/O error: {0}")]
+    Io(String),
+    #[error("tab characters are not allowed in indentation")]
+    TabIndent,
+    #[error("expected a section name, got `{0}`")]
+    BadSectionName(String),
+    #[error("indented line outside of any section")]
+    OrphanLine,
+    #[error("indentation does not match any enclosing block")]
+    BadIndent,
+    #[error("nested block under `{0}`, which already has a value")]
+    NestedUnderValue(String),
+    #[error("duplicate section `{0}`")]
+    DuplicateSection(String),
+    #[error("duplicate key `{0}`")]
+    DuplicateKey(String),
+    #[error("unterminated quoted string")]
+    UnterminatedQuote,
+    #[error("unexpected `{0}` after quoted string")]
+    TrailingAfterQuote(char),
+    #[error("row has {found} columns, header declares {expected}")]
+    ColumnCount { expected: usize, found: usize },
+    #[error("missing section `{0}`")]
+    MissingSection(String),
+    #[error("section `{0}` has no `#` column header")]
+    MissingHeader(String),
+    #[error("missing key `{0}`")]
+    MissingKey(String),
+    #[error("missing column `{0}`")]
+    MissingColumn(String),
+    #[error("invalid value `{value}` for `{field}`: {reason}")]
+    InvalidValue {
+        field: String,
+        value: String,
+        reason: String,
+    },
+}
+
+/// A parsed ALN document: named top-level sections in file order.
+#[derive(Clone, Debug)]
+pub struct AlnDocument {
+    file: Arc<str>,
+    sections: Vec<AlnSection>,
+}
+
+/// One top-level section. Sections with a `#` column header hold rows,
+/// all other sections hold `key value` entries (possibly nested).
+#[derive(Clone, Debug)]
+pub struct AlnSection {
+    file: Arc<str>,
+    pub name: String,
+    pub line: usize,
+    pub header: Option<Vec<String>>,
+    pub rows: Vec<AlnRow>,
+    pub entries: Vec<AlnEntry>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AlnRow {
+    file: Arc<str>,
+    pub line: usize,
+    pub cells: Vec<AlnCell>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AlnCell {
+    pub value: String,
+    pub column: usize,
+    pub quoted: bool,
+}
+
+/// `key value` line inside a section; an entry with an empty value may own
+/// an indented block of child entries (e.g. `stakerequirements`).
+#[derive(Clone, Debug)]
+pub struct AlnEntry {
+    file: Arc<str>,
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+    pub column: usize,
+    pub children: Vec<AlnEntry>,
+}
+
+/// Row view bound to its section header, for lookups by column name.
+#[derive(Clone, Copy, Debug)]
+pub struct AlnRecord<'a> {
+    header: &'a [String],
+    row: &'a AlnRow,
+}
+
+impl AlnError {
+    fn at(file: &str, line: usize, column: usize, kind: AlnErrorKind) -> Self {
+        Self {
+            file: file.to_string(),
+            line,
+            column,
+            kind,
+        }
+    }
+}
+
+impl AlnDocument {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AlnError> {
+        let path = path.as_ref();
+        let file = path.display().to_string();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| AlnError::at(&file, 0, 0, AlnErrorKind::Io(e.to_string())))?;
+        Self::parse(&file, &text)
+    }
+
+    pub fn parse(file: &str, text: &str) -> Result<Self, AlnError> {
+        let file: Arc<str> = Arc::from(file);
+        let mut sections: Vec<AlnSection> = Vec::new();
+        let mut block = BlockBuilder::default();
+
+        for (idx, raw) in text.lines().enumerate() {
+            let line_no = idx + 1;
+            let line = raw.strip_suffix('\r').unwrap_or(raw);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let body = line.trim_start_matches(' ');
+            let indent = line.len() - body.len();
+            if body.starts_with('\t') {
+                return Err(AlnError::at(
+                    &file,
+                    line_no,
+                    indent + 1,
+                    AlnErrorKind::TabIndent,
+                ));
+            }
+
+            if indent == 0 {
+                if body.starts_with('#') {
+                    continue;
+                }
+                if let Some(section) = sections.last_mut() {
+                    section.entries = block.finish()?;
+                }
+                let name = body.trim_end();
+                if name.contains(char::is_whitespace) {
+                    return Err(AlnError::at(
+                        &file,
+                        line_no,
+                        1,
+                        AlnErrorKind::BadSectionName(name.to_string()),
+                    ));
+                }
+                if sections.iter().any(|s| s.name == name) {
+                    return Err(AlnError::at(
+                        &file,
+                        line_no,
+                        1,
+                        AlnErrorKind::DuplicateSection(name.to_string()),
+                    ));
+                }
+                sections.push(AlnSection {
+                    file: file.clone(),
+                    name: name.to_string(),
+                    line: line_no,
+                    header: None,
+                    rows: Vec::new(),
+                    entries: Vec::new(),
+                });
+                continue;
+            }
+
+            let Some(section) = sections.last_mut() else {
+                return Err(AlnError::at(
+                    &file,
+                    line_no,
+                    indent + 1,
+                    AlnErrorKind::OrphanLine,
+                ));
+            };
+
+            if let Some(comment) = body.strip_prefix('#') {
+                // The first comment of a section is its column header when it
+                // lists columns; any other comment is ignored.
+                let is_header = section.header.is_none()
+                    && section.rows.is_empty()
+                    && block.is_empty()
+                    && comment.contains(',');
+                if is_header {
+                    let header: Vec<String> =
+                        comment.split(',').map(|c| c.trim().to_string()).collect();
+                    for (i, name) in header.iter().enumerate() {
+                        if header[..i].contains(name) {
+                            return Err(AlnError::at(
+                                &file,
+                                line_no,
+                                indent + 1,
+                                AlnErrorKind::DuplicateKey(name.clone()),
+                            ));
+                        }
+                    }
+                    section.header = Some(header);
+                }
+                continue;
+            }
+
+            if let Some(header) = &section.header {
+                let cells = split_cells(&file, line_no, line, indent)?;
+                if cells.len() != header.len() {
+                    return Err(AlnError::at(
+                        &file,
+                        line_no,
+                        indent + 1,
+                        AlnErrorKind::ColumnCount {
+                            expected: header.len(),
+                            found: cells.len(),
+                        },
+                    ));
+                }
+                section.rows.push(AlnRow {
+                    file: file.clone(),
+                    line: line_no,
+                    cells,
+                });
+            } else {
+                let body = body.trim_end();
+                let (key, value, value_col) = match body.find(char::is_whitespace) {
+                    Some(pos) => {
+                        let rest = &body[pos..];
+                        let value = rest.trim_start();
+                        let skipped = &body[..body.len() - value.len()];
+                        let col = indent + skipped.chars().count() + 1;
+                        (&body[..pos], value, col)
+                    }
+                    None => (body, "", indent + body.chars().count() + 1),
+                };
+                block.push(
+                    indent,
+                    AlnEntry {
+                        file: file.clone(),
+                        key: key.to_string(),
+                        value: value.to_string(),
+                        line: line_no,
+                        column: value_col,
+                        children: Vec::new(),
+                    },
+                )?;
+            }
+        }
+        if let Some(section) = sections.last_mut() {
+            section.entries = block.finish()?;
+        }
+
+        Ok(Self { file, sections })
+    }
+
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn sections(&self) -> &[AlnSection] {
+        &self.sections
+    }
+
+    pub fn section(&self, name: &str) -> Option<&AlnSection> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    pub fn require_section(&self, name: &str) -> Result<&AlnSection, AlnError> {
+        self.section(name).ok_or_else(|| {
+            AlnError::at(
+                &self.file,
+                0,
+                0,
+                AlnErrorKind::MissingSection(name.to_string()),
+            )
+        })
+    }
+
+    /// Error located at the whole document, for checks that span sections.
+    pub fn error(&self, kind: AlnErrorKind) -> AlnError {
+        AlnError::at(&self.file, 0, 0, kind)
+    }
+}
+
+impl AlnSection {
+    pub fn entry(&self, key: &str) -> Option<&AlnEntry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    pub fn require_entry(&self, key: &str) -> Result<&AlnEntry, AlnError> {
+        self.entry(key).ok_or_else(|| {
+            AlnError::at(
+                &self.file,
+                self.line,
+                1,
+                AlnErrorKind::MissingKey(key.to_string()),
+            )
+        })
+    }
+
+    /// Rows of a table section; fails if the section has no `#` header.
+    pub fn records(&self) -> Result<impl Iterator<Item = AlnRecord<'_>>, AlnError> {
+        let header = self.require_header()?;
+        Ok(self.rows.iter().map(move |row| AlnRecord { header, row }))
+    }
+
+    /// Fails unless the header declares every one of `columns`.
+    pub fn require_columns(&self, columns: &[&str]) -> Result<(), AlnError> {
+        let header = self.require_header()?;
+        match columns.iter().find(|c| !header.iter().any(|h| h == *c)) {
+            Some(missing) => Err(AlnError::at(
+                &self.file,
+                self.line,
+                1,
+                AlnErrorKind::MissingColumn(missing.to_string()),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    pub fn has_column(&self, column: &str) -> bool {
+        self.header
+            .as_ref()
+            .is_some_and(|h| h.iter().any(|c| c == column))
+    }
+
+    pub fn error(&self, kind: AlnErrorKind) -> AlnError {
+        AlnError::at(&self.file, self.line, 1, kind)
+    }
+
+    fn require_header(&self) -> Result<&[String], AlnError> {
+        self.header
+            .as_deref()
+            .ok_or_else(|| self.error(AlnErrorKind::MissingHeader(self.name.clone())))
+    }
+}
+
+impl<'a> AlnRecord<'a> {
+    pub fn line(&self) -> usize {
+        self.row.line
+    }
+
+    pub fn cell(&self, column: &str) -> Option<&'a AlnCell> {
+        let idx = self.header.iter().position(|h| h == column)?;
+        self.row.cells.get(idx)
+    }
+
+    pub fn str(&self, column: &str) -> Result<&'a str, AlnError> {
+        self.require(column).map(|c| c.value.as_str())
+    }
+
+    /// Value of an optional column; absent columns and `-` read as `None`.
+    pub fn opt_str(&self, column: &str) -> Option<&'a str> {
+        self.cell(column)
+            .map(|c| c.value.as_str())
+            .filter(|v| *v != "-" && !v.is_empty())
+    }
+
+    pub fn parse<T>(&self, column: &str) -> Result<T, AlnError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let cell = self.require(column)?;
+        cell.value
+            .parse()
+            .map_err(|e: T::Err| self.invalid(column, e))
+    }
+
+    pub fn bool(&self, column: &str) -> Result<bool, AlnError> {
+        let cell = self.require(column)?;
+        parse_bool(&cell.value).ok_or_else(|| self.invalid(column, "expected `true` or `false`"))
+    }
+
+    /// Comma-separated list inside a single (quoted) cell.
+    pub fn list(&self, column: &str) -> Result<Vec<String>, AlnError> {
+        self.str(column).map(split_list)
+    }
+
+    /// Builds an `InvalidValue` error pointing at `column` of this row.
+    pub fn invalid(&self, column: &str, reason: impl fmt::Display) -> AlnError {
+        let (value, col) = match self.cell(column) {
+            Some(c) => (c.value.clone(), c.column),
+            None => (String::new(), 1),
+        };
+        AlnError::at(
+            &self.row.file,
+            self.row.line,
+            col,
+            AlnErrorKind::InvalidValue {
+                field: column.to_string(),
+                value,
+                reason: reason.to_string(),
+            },
+        )
+    }
+
+    pub fn error(&self, kind: AlnErrorKind) -> AlnError {
+        let col = self.row.cells.first().map_or(1, |c| c.column);
+        AlnError::at(&self.row.file, self.row.line, col, kind)
+    }
+
+    fn require(&self, column: &str) -> Result<&'a AlnCell, AlnError> {
+        self.cell(column)
+            .ok_or_else(|| self.error(AlnErrorKind::MissingColumn(column.to_string())))
+    }
+}
+
+impl AlnEntry {
+    pub fn child(&self, key: &str) -> Option<&AlnEntry> {
+        self.children.iter().find(|e| e.key == key)
+    }
+
+    pub fn require_child(&self, key: &str) -> Result<&AlnEntry, AlnError> {
+        self.child(key).ok_or_else(|| {
+            AlnError::at(
+                &self.file,
+                self.line,
+                1,
+                AlnErrorKind::MissingKey(key.to_string()),
+            )
+        })
+    }
+
+    pub fn parse<T>(&self) -> Result<T, AlnError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.value.parse().map_err(|e: T::Err| self.invalid(e))
+    }
+
+    pub fn bool(&self) -> Result<bool, AlnError> {
+        parse_bool(&self.value).ok_or_else(|| self.invalid("expected `true` or `false`"))
+    }
+
+    pub fn list(&self) -> Vec<String> {
+        split_list(&self.value)
+    }
+
+    pub fn invalid(&self, reason: impl fmt::Display) -> AlnError {
+        AlnError::at(
+            &self.file,
+            self.line,
+            self.column,
+            AlnErrorKind::InvalidValue {
+                field: self.key.clone(),
+                value: self.value.clone(),
+                reason: reason.to_string(),
+            },
+        )
+    }
+}
+
+/// Formats one cell for writing, quoting it when it would not round-trip bare.
+pub fn format_cell(value: &str) -> String {
+    let needs_quotes =
+        value.is_empty() || value.contains([',', '"', '\\', '#']) || value.trim() != value;
+    if !needs_quotes {
+        return value.to_string();
+    }
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for ch in value.chars() {
+        if ch == '"' || ch == '\\' {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out.push('"');
+    out
+}
+
+/// Formats a table row (without indentation or trailing newline).
+pub fn format_row<S: AsRef<str>>(cells: &[S]) -> String {
+    cells
+        .iter()
+        .map(|c| format_cell(c.as_ref()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn split_cells(
+    file: &str,
+    line_no: usize,
+    line: &str,
+    indent: usize,
+) -> Result<Vec<AlnCell>, AlnError> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut cells = Vec::new();
+    let mut i = indent;
+    loop {
+        while i < chars.len() && chars[i] == ' ' {
+            i += 1;
+        }
+        let column = i + 1;
+        if i < chars.len() && chars[i] == '"' {
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => {
+                        return Err(AlnError::at(
+                            file,
+                            line_no,
+                            column,
+                            AlnErrorKind::UnterminatedQuote,
+                        ))
+                    }
+                    Some('\\') if i + 1 < chars.len() => {
+                        value.push(chars[i + 1]);
+                        i += 2;
+                    }
+                    Some('"') => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&c) => {
+                        value.push(c);
+                        i += 1;
+                    }
+                }
+            }
+            while i < chars.len() && chars[i] == ' ' {
+                i += 1;
+            }
+            if i < chars.len() && chars[i] != ',' {
+                return Err(AlnError::at(
+                    file,
+                    line_no,
+                    i + 1,
+                    AlnErrorKind::TrailingAfterQuote(chars[i]),
+                ));
+            }
+            cells.push(AlnCell {
+                value,
+                column,
+                quoted: true,
+            });
+        } else {
+            let start = i;
+            while i < chars.len() && chars[i] != ',' {
+                i += 1;
+            }
+            let value: String = chars[start..i].iter().collect();
+            cells.push(AlnCell {
+                value: value.trim_end().to_string(),
+                column,
+                quoted: false,
+            });
+        }
+        if i >= chars.len() {
+            break;
+        }
+        // skip the comma
+        i += 1;
+    }
+    Ok(cells)
+}
+
+/// Accumulates indented `key value` lines of one section into a tree.
+#[derive(Default)]
+struct BlockBuilder {
+    roots: Vec<AlnEntry>,
+    root_indent: Option<usize>,
+    // (indent, entry, indent of its children once known)
+    stack: Vec<(usize, AlnEntry, Option<usize>)>,
+}
+
+impl BlockBuilder {
+    fn is_empty(&self) -> bool {
+        self.roots.is_empty() && self.stack.is_empty()
+    }
+
+    fn push(&mut self, indent: usize, entry: AlnEntry) -> Result<(), AlnError> {
+        while let Some((top_indent, _, _)) = self.stack.last() {
+            if *top_indent < indent {
+                break;
+            }
+            let sibling = *top_indent == indent;
+            self.pop()?;
+            if sibling {
+                break;
+            }
+        }
+
+        let err = |kind| AlnError::at(&entry.file, entry.line, indent + 1, kind);
+        match self.stack.last_mut() {
+            Some((_, parent, child_indent)) => {
+                if !parent.value.is_empty() {
+                    return Err(err(AlnErrorKind::NestedUnderValue(parent.key.clone())));
+                }
+                if *child_indent.get_or_insert(indent) != indent {
+                    return Err(err(AlnErrorKind::BadIndent));
+                }
+            }
+            None => {
+                if *self.root_indent.get_or_insert(indent) != indent {
+                    return Err(err(AlnErrorKind::BadIndent));
+                }
+            }
+        }
+        self.stack.push((indent, entry, None));
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<(), AlnError> {
+        let Some((_, entry, _)) = self.stack.pop() else {
+            return Ok(());
+        };
+        let siblings = match self.stack.last_mut() {
+            Some((_, parent, _)) => &mut parent.children,
+            None => &mut self.roots,
+        };
+        if siblings.iter().any(|s| s.key == entry.key) {
+            return Err(AlnError::at(
+                &entry.file,
+                entry.line,
+                1,
+                AlnErrorKind::DuplicateKey(entry.key.clone()),
+            ));
+        }
+        siblings.push(entry);
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<Vec<AlnEntry>, AlnError> {
+        while !self.stack.is_empty() {
+            self.pop()?;
+        }
+        self.root_indent = None;
+        Ok(std::mem::take(&mut self.roots))
+    }
+}
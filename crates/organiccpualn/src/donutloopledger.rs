@@ -0,0 +1,639 @@
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::aln::{self, AlnDocument, AlnError, AlnRecord};
+use crate::evolvestream::{
+    Decision, EvolutionProposalRecord, EVOLVE_SCHEMA_VERSION, ROLLBACK_KIND,
+};
+use crate::hexstamp::{Canonical, Hexstamped};
+use crate::rohmodel::ROH_CEILING;
+
+/// `prev_hexstamp` of the first entry in every ledger.
+pub const GENESIS_HEXSTAMP: &str =
+    "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+const COLUMNS: [&str; 10] = [
+    "entry_id",
+    "proposal_id",
+    "subject_id",
+    "decision",
+    "roh_before",
+    "roh_after",
+    "prev_hexstamp",
+    "hexstamp",
+    "policy_refs",
+    "timestamp_utc",
+];
+
+/// One row of `.donutloop.aln`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DonutloopEntry {
+    pub entry_id: String,
+    pub proposal_id: String,
+    pub subject_id: String,
+    pub decision: String,
+    pub roh_before: f32,
+    pub roh_after: f32,
+    pub prev_hexstamp: String,
+    pub hexstamp: String,
+    pub policy_refs: Vec<String>,
+    pub timestamp_utc: String,
+}
+
+/// Append-only, hash-linked ledger backed by a `.donutloop.aln` file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DonutloopLedger {
+    path: PathBuf,
+    subject_id: String,
+    entries: Vec<DonutloopEntry>,
+    // Entries before `entries[0]` left on disk by `resume`, and their ids.
+    #[serde(default)]
+    skipped: usize,
+    #[serde(default)]
+    skipped_ids: BTreeSet<String>,
+    // Bytes of a torn final write dropped when the ledger was opened.
+    #[serde(skip)]
+    repaired_bytes: u64,
+}
+
+/// A ledger's head and where its row starts on disk, saved in checkpoints.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LedgerMark {
+    pub subject_id: String,
+    // Entries up to and including `head`.
+    pub len: usize,
+    pub head: Option<DonutloopEntry>,
+    pub head_offset: u64,
+    // SHA-256 of the bytes before `head_offset`, and the ids of the entries
+    // they hold, so `resume` neither trusts nor re-parses them.
+    pub prefix_hash: String,
+    pub prefix_ids: BTreeSet<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+#[error("donutloop chain broken at entry {index} (`{entry_id}`): {kind}")]
+pub struct ChainError {
+    pub index: usize,
+    pub entry_id: String,
+    pub kind: ChainErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum ChainErrorKind {
+    #[error("prev_hexstamp {found} does not link to {expected}")]
+    BrokenLink { expected: String, found: String },
+    #[error("hexstamp {found} does not match entry contents ({expected})")]
+    HexstampMismatch { expected: String, found: String },
+    #[error("RoH increases within entry: {before} -> {after}")]
+    RohIncrease { before: f32, after: f32 },
+    #[error("RoH increases across entries: {previous} -> {after}")]
+    RohIncreaseAcross { previous: f32, after: f32 },
+    #[error("duplicate entry id")]
+    DuplicateEntryId,
+}
+
+impl DonutloopEntry {
+    /// Unstamped entry for an allowed proposal; `append` fills in the links.
+    pub fn from_proposal(proposal: &EvolutionProposalRecord) -> Self {
+        Self {
+            entry_id: format!("donut-{}", proposal.proposalid),
+            proposal_id: proposal.proposalid.clone(),
+            subject_id: proposal.subjectid.clone(),
+            decision: proposal.decision.to_string(),
+            roh_before: proposal.roh_before,
+            roh_after: proposal.roh_after,
+            prev_hexstamp: String::new(),
+            hexstamp: String::new(),
+            policy_refs: proposal
+                .rollback_target()
+                .map(|target| vec![format!("rollback:{target}")])
+                .unwrap_or_default(),
+            timestamp_utc: proposal.timestamp_utc.clone(),
+        }
+    }
+
+    /// The entry this one rolls back, from its `rollback:<entry_id>` policy ref.
+    pub fn rollback_of(&self) -> Option<&str> {
+        self.policy_refs
+            .iter()
+            .find_map(|r| r.strip_prefix("rollback:"))
+    }
+
+    /// Unstamped Proposed record that undoes this entry: same scope, module
+    /// and token as `original`, RoH going from `current_roh` back to this
+    /// entry's `roh_before`. Signers and timestamp are left for the caller.
+    pub fn rollback_proposal(
+        &self,
+        original: &EvolutionProposalRecord,
+        current_roh: f32,
+    ) -> EvolutionProposalRecord {
+        EvolutionProposalRecord {
+            schema_version: EVOLVE_SCHEMA_VERSION,
+            proposalid: format!("rollback-{}", self.entry_id),
+            subjectid: self.subject_id.clone(),
+            scope: original.scope,
+            kind: ROLLBACK_KIND.to_string(),
+            module: original.module.clone(),
+            updatekind: self.entry_id.clone(),
+            effectbounds: original.effectbounds.clone(),
+            roh_before: current_roh,
+            roh_after: self.roh_before,
+            tsafe_mode: original.tsafe_mode.clone(),
+            signer_roles: Vec::new(),
+            tokenkind: original.tokenkind,
+            decision: Decision::Proposed,
+            hexstamp: String::new(),
+            timestamp_utc: String::new(),
+            // It serves the purposes of the change it undoes.
+            purposes: original.purposes.clone(),
+            data_access: Vec::new(),
+            payload_salt: String::new(),
+            payload_commitment: String::new(),
+            signatures: Vec::new(),
+            forgotten_utc: None,
+        }
+    }
+
+    fn cells(&self) -> Vec<(&'static str, String)> {
+        let values = [
+            self.entry_id.clone(),
+            self.proposal_id.clone(),
+            self.subject_id.clone(),
+            self.decision.clone(),
+            self.roh_before.to_string(),
+            self.roh_after.to_string(),
+            self.prev_hexstamp.clone(),
+            self.hexstamp.clone(),
+            self.policy_refs.join(","),
+            self.timestamp_utc.clone(),
+        ];
+        COLUMNS.into_iter().zip(values).collect()
+    }
+
+    fn to_row(&self) -> String {
+        let values: Vec<String> = self.cells().into_iter().map(|(_, v)| v).collect();
+        format!("  {}\n", aln::format_row(&values))
+    }
+
+    fn from_record(rec: &AlnRecord<'_>) -> Result<Self, AlnError> {
+        Ok(Self {
+            entry_id: rec.str("entry_id")?.to_string(),
+            proposal_id: rec.opt_str("proposal_id").unwrap_or_default().to_string(),
+            subject_id: rec.opt_str("subject_id").unwrap_or_default().to_string(),
+            decision: rec.str("decision")?.to_string(),
+            roh_before: rec.parse("roh_before")?,
+            roh_after: rec.parse("roh_after")?,
+            prev_hexstamp: rec.str("prev_hexstamp")?.to_string(),
+            hexstamp: rec.str("hexstamp")?.to_string(),
+            policy_refs: match rec.opt_str("policy_refs") {
+                Some(_) => rec.list("policy_refs")?,
+                None => Vec::new(),
+            },
+            timestamp_utc: rec.str("timestamp_utc")?.to_string(),
+        })
+    }
+}
+
+impl Hexstamped for DonutloopEntry {
+    /// Every column except `hexstamp`, in column order.
+    fn canonical(&self) -> Canonical {
+        Canonical::new("donutloop-entry", 1)
+            .str("entry_id", &self.entry_id)
+            .str("proposal_id", &self.proposal_id)
+            .str("subject_id", &self.subject_id)
+            .str("decision", &self.decision)
+            .f32("roh_before", self.roh_before)
+            .f32("roh_after", self.roh_after)
+            .str("prev_hexstamp", &self.prev_hexstamp)
+            .list("policy_refs", &self.policy_refs)
+            .str("timestamp_utc", &self.timestamp_utc)
+    }
+
+    fn hexstamp(&self) -> &str {
+        &self.hexstamp
+    }
+
+    fn set_hexstamp(&mut self, hexstamp: String) {
+        self.hexstamp = hexstamp;
+    }
+}
+
+impl DonutloopLedger {
+    /// Create an empty ledger file. The header is written to a temporary file
+    /// and renamed into place so a crash never leaves a half-written header.
+    pub fn create(path: impl AsRef<Path>, subject_id: &str) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            anyhow::bail!("donutloop ledger {} already exists", path.display());
+        }
+        let header = format!(
+            "meta\n  version 1.0.0\n  subject_id {subject_id}\n  description Donutloop ledger; append-only, hash-linked.\n\nentries\n  # {}\n",
+            COLUMNS.join(", ")
+        );
+        let tmp = path.with_extension("aln.tmp");
+        {
+            let mut f =
+                File::create(&tmp).with_context(|| format!("creating {}", tmp.display()))?;
+            f.write_all(header.as_bytes())?;
+            f.sync_all()?;
+        }
+        fs::rename(&tmp, path)?;
+        sync_parent_dir(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            subject_id: subject_id.to_string(),
+            entries: Vec::new(),
+            skipped: 0,
+            skipped_ids: BTreeSet::new(),
+            repaired_bytes: 0,
+        })
+    }
+
+    /// Open an existing ledger, truncating a torn final write left by a crash.
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let repaired_bytes = repair_torn_tail(path)?;
+        let (subject_id, entries) = read_ledger(path)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            subject_id,
+            entries,
+            skipped: 0,
+            skipped_ids: BTreeSet::new(),
+            repaired_bytes,
+        })
+    }
+
+    /// Open a ledger at a checkpointed `mark`, parsing only the head row and
+    /// the rows after it. Fails if the bytes before `mark.head_offset` changed
+    /// or the row there is no longer `mark.head`, in which case the caller
+    /// falls back to `open`.
+    pub fn resume(path: impl AsRef<Path>, mark: &LedgerMark) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let Some(head) = &mark.head else {
+            return Self::open(path);
+        };
+        if mark.prefix_ids.len() + 1 != mark.len {
+            anyhow::bail!(
+                "{}: mark lists {} entry ids before head, expected {}",
+                path.display(),
+                mark.prefix_ids.len(),
+                mark.len.saturating_sub(1)
+            );
+        }
+        let repaired_bytes = repair_torn_tail(path)?;
+        if prefix_hash(path, mark.head_offset)? != mark.prefix_hash {
+            anyhow::bail!(
+                "{}: the {} bytes before head `{}` changed since the mark",
+                path.display(),
+                mark.head_offset,
+                head.entry_id
+            );
+        }
+        let entries = read_rows_from(path, mark.head_offset)?;
+        if entries.first() != Some(head) || head.verify_hexstamp().is_err() {
+            anyhow::bail!(
+                "{}: head `{}` is not at byte {}",
+                path.display(),
+                head.entry_id,
+                mark.head_offset
+            );
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            subject_id: mark.subject_id.clone(),
+            entries,
+            skipped: mark.len - 1,
+            skipped_ids: mark.prefix_ids.clone(),
+            repaired_bytes,
+        })
+    }
+
+    /// The current head and its row's byte offset, for a checkpoint.
+    pub fn mark(&self) -> anyhow::Result<LedgerMark> {
+        let end = fs::metadata(&self.path)
+            .with_context(|| format!("reading {}", self.path.display()))?
+            .len();
+        let head_offset = match self.head() {
+            None => end,
+            Some(head) => {
+                let mut file = File::open(&self.path)?;
+                let start = match end.checked_sub(1) {
+                    Some(last) => last_newline_before(&mut file, last)?.map_or(0, |p| p + 1),
+                    None => 0,
+                };
+                if read_rows_from(&self.path, start)?.as_slice() != std::slice::from_ref(head) {
+                    anyhow::bail!(
+                        "{}: last row is not head `{}`",
+                        self.path.display(),
+                        head.entry_id
+                    );
+                }
+                start
+            }
+        };
+        let before_head = self.entries.len().saturating_sub(1);
+        let mut prefix_ids = self.skipped_ids.clone();
+        prefix_ids.extend(self.entries[..before_head].iter().map(|e| e.entry_id.clone()));
+        Ok(LedgerMark {
+            subject_id: self.subject_id.clone(),
+            len: self.len(),
+            head: self.head().cloned(),
+            head_offset,
+            prefix_hash: prefix_hash(&self.path, head_offset)?,
+            prefix_ids,
+        })
+    }
+
+    pub fn open_or_create(path: impl AsRef<Path>, subject_id: &str) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            Self::open(path)
+        } else {
+            Self::create(path, subject_id)
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn subject_id(&self) -> &str {
+        &self.subject_id
+    }
+
+    /// Entries held in memory: all of them, or for a resumed ledger the
+    /// checkpointed head and everything after it.
+    pub fn entries(&self) -> &[DonutloopEntry] {
+        &self.entries
+    }
+
+    /// Entry `entry_id`; rows before a resumed ledger's head are read from disk.
+    pub fn entry(&self, entry_id: &str) -> anyhow::Result<Option<DonutloopEntry>> {
+        self.entry_before(self.entries.len(), entry_id)
+    }
+
+    /// Entry `entry_id` among those before `entries[i]`. Only ids known to
+    /// lie before a resumed ledger's head are read back from disk.
+    fn entry_before(&self, i: usize, entry_id: &str) -> anyhow::Result<Option<DonutloopEntry>> {
+        if let Some(e) = self.entries[..i].iter().find(|e| e.entry_id == entry_id) {
+            return Ok(Some(e.clone()));
+        }
+        if !self.skipped_ids.contains(entry_id) {
+            return Ok(None);
+        }
+        let (_, entries) = read_ledger(&self.path)?;
+        Ok(entries
+            .into_iter()
+            .take(self.skipped)
+            .find(|e| e.entry_id == entry_id))
+    }
+
+    pub fn len(&self) -> usize {
+        self.skipped + self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn head(&self) -> Option<&DonutloopEntry> {
+        self.entries.last()
+    }
+
+    pub fn head_hexstamp(&self) -> &str {
+        self.head()
+            .map_or(GENESIS_HEXSTAMP, |e| e.hexstamp.as_str())
+    }
+
+    /// Bytes dropped from a torn final write when the ledger was opened.
+    pub fn repaired_bytes(&self) -> u64 {
+        self.repaired_bytes
+    }
+
+    /// Verify hash links, hexstamps and RoH monotonicity over the whole chain.
+    /// A resumed ledger checks its head's stamp and the links after it; the
+    /// rows before were checked when the checkpoint was taken, and `resume`
+    /// verified they are byte-for-byte unchanged.
+    pub fn validate_chain(&self) -> Result<(), ChainError> {
+        for (i, entry) in self.entries.iter().enumerate() {
+            let index = self.skipped + i;
+            if self.skipped_ids.contains(&entry.entry_id)
+                || self.entries[..i]
+                    .iter()
+                    .any(|e| e.entry_id == entry.entry_id)
+            {
+                return Err(chain_error(index, entry, ChainErrorKind::DuplicateEntryId));
+            }
+            if i > 0 || self.skipped == 0 {
+                // A target that cannot be read is treated as missing, so
+                // the rollback fails the RoH check rather than passing it.
+                let target = match entry.rollback_of() {
+                    Some(id) => self.entry_before(i, id).ok().flatten(),
+                    None => None,
+                };
+                check_link(index, entry, self.entries[..i].last(), target.as_ref())?;
+            }
+            let expected = entry.compute_hexstamp();
+            if entry.hexstamp != expected {
+                return Err(chain_error(
+                    index,
+                    entry,
+                    ChainErrorKind::HexstampMismatch {
+                        expected,
+                        found: entry.hexstamp.clone(),
+                    },
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Link, stamp and durably append an entry. The row is written with a
+    /// single append-mode write and fsync'd before the in-memory chain moves.
+    pub fn append(&mut self, mut entry: DonutloopEntry) -> anyhow::Result<&DonutloopEntry> {
+        let index = self.len();
+        if self.entry(&entry.entry_id)?.is_some() {
+            return Err(chain_error(index, &entry, ChainErrorKind::DuplicateEntryId).into());
+        }
+        entry.prev_hexstamp = self.head_hexstamp().to_string();
+        let target = match entry.rollback_of() {
+            Some(id) => self.entry(id)?,
+            None => None,
+        };
+        check_link(index, &entry, self.head(), target.as_ref())?;
+        entry.stamp();
+
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("opening {}", self.path.display()))?;
+        let len_before = file.metadata()?.len();
+        let written = file
+            .write_all(entry.to_row().as_bytes())
+            .and_then(|_| file.sync_data());
+        if let Err(e) = written {
+            // Roll back a partial row so the file never holds a torn entry.
+            let _ = file.set_len(len_before).and_then(|_| file.sync_data());
+            return Err(e).with_context(|| format!("appending to {}", self.path.display()));
+        }
+
+        self.entries.push(entry);
+        Ok(self.entries.last().expect("just pushed"))
+    }
+}
+
+/// Subject and entries of a ledger file, without repairing it.
+fn read_ledger(path: &Path) -> anyhow::Result<(String, Vec<DonutloopEntry>)> {
+    let doc = AlnDocument::load(path)?;
+    let subject_id = doc
+        .require_section("meta")?
+        .require_entry("subject_id")?
+        .value
+        .clone();
+    let entries = doc
+        .require_section("entries")?
+        .records()?
+        .map(|rec| DonutloopEntry::from_record(&rec))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((subject_id, entries))
+}
+
+/// Entries in the rows from byte `offset` to the end of the file; the rows
+/// are parsed under the ledger's column header.
+fn read_rows_from(path: &Path, offset: u64) -> anyhow::Result<Vec<DonutloopEntry>> {
+    let mut file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut rows = String::new();
+    file.read_to_string(&mut rows)
+        .with_context(|| format!("reading {} from byte {offset}", path.display()))?;
+    let text = format!("entries\n  # {}\n{rows}", COLUMNS.join(", "));
+    let doc = AlnDocument::parse(&format!("{}@{offset}", path.display()), &text)?;
+    let entries = doc
+        .require_section("entries")?
+        .records()?
+        .map(|rec| DonutloopEntry::from_record(&rec))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(entries)
+}
+
+/// `0x` + hex SHA-256 of the first `len` bytes of `path`.
+fn prefix_hash(path: &Path, len: u64) -> anyhow::Result<String> {
+    let file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut prefix = file.take(len);
+    let mut hasher = Sha256::new();
+    let read = std::io::copy(&mut prefix, &mut hasher)
+        .with_context(|| format!("hashing {}", path.display()))?;
+    if read != len {
+        anyhow::bail!("{}: shorter than {len} bytes", path.display());
+    }
+    Ok(format!("0x{}", hex::encode(hasher.finalize())))
+}
+
+fn chain_error(index: usize, entry: &DonutloopEntry, kind: ChainErrorKind) -> ChainError {
+    ChainError {
+        index,
+        entry_id: entry.entry_id.clone(),
+        kind,
+    }
+}
+
+/// `prev` is the entry before `entry`, `target` the entry it rolls back.
+fn check_link(
+    index: usize,
+    entry: &DonutloopEntry,
+    prev: Option<&DonutloopEntry>,
+    target: Option<&DonutloopEntry>,
+) -> Result<(), ChainError> {
+    let expected = prev.map_or(GENESIS_HEXSTAMP, |p| p.hexstamp.as_str());
+    if entry.prev_hexstamp != expected {
+        return Err(chain_error(
+            index,
+            entry,
+            ChainErrorKind::BrokenLink {
+                expected: expected.to_string(),
+                found: entry.prev_hexstamp.clone(),
+            },
+        ));
+    }
+    // A rollback may raise RoH, but only back to the state recorded before
+    // the entry it undoes, and only if that state was within the RoH ceiling.
+    let restores_recorded_state = target
+        .filter(|t| entry.rollback_of() == Some(t.entry_id.as_str()))
+        .is_some_and(|target| {
+            (entry.roh_after - target.roh_before).abs() <= f32::EPSILON
+                && target.roh_before <= ROH_CEILING + f32::EPSILON
+        });
+    if restores_recorded_state {
+        return Ok(());
+    }
+    if entry.roh_after > entry.roh_before + f32::EPSILON {
+        return Err(chain_error(
+            index,
+            entry,
+            ChainErrorKind::RohIncrease {
+                before: entry.roh_before,
+                after: entry.roh_after,
+            },
+        ));
+    }
+    if let Some(p) = prev {
+        if entry.roh_after > p.roh_after + f32::EPSILON {
+            return Err(chain_error(
+                index,
+                entry,
+                ChainErrorKind::RohIncreaseAcross {
+                    previous: p.roh_after,
+                    after: entry.roh_after,
+                },
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Every complete row ends in `\n`; anything after the last newline is a
+/// write that did not finish and is cut off. Returns the bytes dropped.
+fn repair_torn_tail(path: &Path) -> anyhow::Result<u64> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    let len = file.metadata()?.len();
+    let keep = last_newline_before(&mut file, len)?.map_or(0, |p| p + 1);
+    if keep < len {
+        file.set_len(keep)?;
+        file.sync_all()?;
+    }
+    Ok(len - keep)
+}
+
+/// Offset of the last `\n` before byte `end`, scanning backwards in chunks.
+pub(crate) fn last_newline_before(file: &mut File, end: u64) -> std::io::Result<Option<u64>> {
+    let mut pos = end;
+    let mut buf = [0u8; 4096];
+    while pos > 0 {
+        let chunk = buf.len().min(pos as usize);
+        pos -= chunk as u64;
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(&mut buf[..chunk])?;
+        if let Some(i) = buf[..chunk].iter().rposition(|b| *b == b'\n') {
+            return Ok(Some(pos + i as u64));
+        }
+    }
+    Ok(None)
+}
+
+fn sync_parent_dir(path: &Path) -> std::io::Result<()> {
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => File::open(dir)?.sync_all(),
+        _ => Ok(()),
+    }
+}
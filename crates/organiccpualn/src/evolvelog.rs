@@ -0,0 +1,608 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Context;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::{Date, Month, OffsetDateTime, UtcOffset};
+
+use crate::donutloopledger::last_newline_before;
+use crate::evolvestream::{EvolutionProposalRecord, SchemaError};
+use crate::hexstamp::Hexstamped;
+use crate::nnetevolve::NnetUpdateRecord;
+
+/// A record that can live in a date-rotated evolve log.
+pub trait EvolveRecord: Serialize + DeserializeOwned + Hexstamped {
+    fn proposal_id(&self) -> &str;
+    fn timestamp_utc(&self) -> &str;
+
+    /// Decode one log line and verify its hexstamp; record kinds with a
+    /// versioned schema check the version here.
+    fn decode(line: &str) -> Result<Self, SchemaError> {
+        let rec: Self = serde_json::from_str(line)?;
+        rec.verify_hexstamp()?;
+        Ok(rec)
+    }
+
+    /// `timestamp_utc` as a UTC instant, if it is valid RFC 3339.
+    fn timestamp(&self) -> Option<OffsetDateTime> {
+        OffsetDateTime::parse(self.timestamp_utc(), &Rfc3339)
+            .ok()
+            .map(|t| t.to_offset(UtcOffset::UTC))
+    }
+}
+
+impl EvolveRecord for EvolutionProposalRecord {
+    fn proposal_id(&self) -> &str {
+        &self.proposalid
+    }
+
+    fn timestamp_utc(&self) -> &str {
+        &self.timestamp_utc
+    }
+
+    fn decode(line: &str) -> Result<Self, SchemaError> {
+        Self::from_json(line)
+    }
+}
+
+impl EvolveRecord for NnetUpdateRecord {
+    fn proposal_id(&self) -> &str {
+        &self.proposal_id
+    }
+
+    fn timestamp_utc(&self) -> &str {
+        &self.timestamp_utc
+    }
+}
+
+/// Writer for `<prefix>-YYYYMMDD.jsonl` files, one per UTC day: each record
+/// goes to the file of its own `timestamp_utc` day.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RotatingEvolveLog {
+    dir: PathBuf,
+    prefix: String,
+}
+
+impl RotatingEvolveLog {
+    pub fn new(dir: impl Into<PathBuf>, prefix: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            prefix: prefix.into(),
+        }
+    }
+
+    /// `nnet-evolve-YYYYMMDD.jsonl` files in `dir`.
+    pub fn nnet(dir: impl Into<PathBuf>) -> Self {
+        Self::new(dir, "nnet-evolve")
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path_for(&self, day: Date) -> PathBuf {
+        self.dir.join(format!(
+            "{}-{:04}{:02}{:02}.jsonl",
+            self.prefix,
+            day.year(),
+            u8::from(day.month()),
+            day.day()
+        ))
+    }
+
+    /// Append `rec` to its day's file (created on first use) and fsync it.
+    /// `rec` must carry a valid hexstamp, as readers reject anything else.
+    pub fn append<R: EvolveRecord>(&self, rec: &R) -> anyhow::Result<PathBuf> {
+        rec.verify_hexstamp()
+            .with_context(|| format!("refusing to log `{}`", rec.proposal_id()))?;
+        let day = rec
+            .timestamp()
+            .with_context(|| format!("invalid timestamp_utc `{}`", rec.timestamp_utc()))?
+            .date();
+        let path = self.path_for(day);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("opening evolve log {}", path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(rec)?)?;
+        file.sync_data()?;
+        Ok(path)
+    }
+
+    /// Every record of this log, merged across days.
+    pub fn read<R: EvolveRecord>(&self) -> anyhow::Result<MergedEvolveLog<R>> {
+        MergedEvolveLog::read(self.dir.join(format!("{}-*.jsonl", self.prefix)))
+    }
+
+    /// Mark the end of the latest day's file; `None` if nothing was logged.
+    pub fn mark(&self) -> anyhow::Result<Option<RotatingMark>> {
+        match self.day_files()?.pop() {
+            Some((day, path)) => Ok(Some(RotatingMark {
+                day,
+                last: LogMark::at_end(&path)?,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    /// Records logged after `mark`, or all of them, in file order. Fails if
+    /// the marked record is no longer where the mark says.
+    pub fn read_after<R: EvolveRecord>(
+        &self,
+        mark: Option<&RotatingMark>,
+    ) -> anyhow::Result<Vec<R>> {
+        if let Some(m) = mark {
+            let path = self.path_for(m.day);
+            if !path.exists() {
+                anyhow::bail!("marked log {} is gone", path.display());
+            }
+        }
+        let mut records = Vec::new();
+        for (day, path) in self.day_files()? {
+            let after = match mark {
+                Some(m) if day < m.day => continue,
+                Some(m) if day == m.day => m.last.clone(),
+                _ => LogMark::default(),
+            };
+            records.extend(after.records_after(&path)?);
+        }
+        Ok(records)
+    }
+
+    /// Dated files of this log, oldest first.
+    fn day_files(&self) -> anyhow::Result<Vec<(Date, PathBuf)>> {
+        let pattern = self.dir.join(format!("{}-*.jsonl", self.prefix));
+        let mut files = Vec::new();
+        for path in glob::glob(&pattern.to_string_lossy())
+            .with_context(|| format!("invalid log pattern {}", pattern.display()))?
+        {
+            let path = path?;
+            if let Some(day) = file_day(&path) {
+                files.push((day, path));
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// The last record of a JSONL log as of a checkpoint: the byte range of its
+/// line and its hexstamp. The default mark is the start of the log.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogMark {
+    pub start: u64,
+    pub end: u64,
+    pub hexstamp: String,
+}
+
+/// A rotating log's mark: its latest day and the mark in that day's file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RotatingMark {
+    pub day: Date,
+    pub last: LogMark,
+}
+
+impl LogMark {
+    /// Mark the last complete line of `path`; a missing or empty log is the
+    /// default mark.
+    pub fn at_end(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let mut file = fs::File::open(path).with_context(|| format!("opening {}", path.display()))?;
+        let len = file.metadata()?.len();
+        let Some(end) = last_newline_before(&mut file, len)?.map(|p| p + 1) else {
+            return Ok(Self::default());
+        };
+        let start = last_newline_before(&mut file, end - 1)?.map_or(0, |p| p + 1);
+        let hexstamp = read_hexstamp(&mut file, start, end)
+            .with_context(|| format!("{}: no record at byte {start}", path.display()))?;
+        Ok(Self {
+            start,
+            end,
+            hexstamp,
+        })
+    }
+
+    /// Records after the mark. Fails unless `path` still holds the marked
+    /// record between `start` and `end`.
+    pub fn records_after<R: EvolveRecord>(&self, path: &Path) -> anyhow::Result<Vec<R>> {
+        if self.end == 0 && !path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut file = fs::File::open(path).with_context(|| format!("opening {}", path.display()))?;
+        if self.end > 0 {
+            let found = read_hexstamp(&mut file, self.start, self.end);
+            if found.as_deref() != Some(self.hexstamp.as_str()) {
+                anyhow::bail!(
+                    "{}: record {} is not at bytes {}..{}",
+                    path.display(),
+                    self.hexstamp,
+                    self.start,
+                    self.end
+                );
+            }
+        }
+        file.seek(SeekFrom::Start(self.end))?;
+        EvolveRecords::new(BufReader::new(file))
+            .map(|r| {
+                r.map(|(_, rec)| rec).map_err(|e| {
+                    anyhow::anyhow!("{} after byte {}: {e}", path.display(), self.end)
+                })
+            })
+            .collect()
+    }
+}
+
+/// `hexstamp` of the record whose line is exactly `start..end`.
+fn read_hexstamp(file: &mut fs::File, start: u64, end: u64) -> Option<String> {
+    let mut line = vec![0; usize::try_from(end.checked_sub(start)?).ok()?];
+    file.seek(SeekFrom::Start(start)).ok()?;
+    file.read_exact(&mut line).ok()?;
+    let line = std::str::from_utf8(line.strip_suffix(b"\n")?).ok()?;
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    value.get("hexstamp")?.as_str().map(str::to_string)
+}
+
+/// A record and where it was read from.
+#[derive(Clone, Debug)]
+pub struct LoggedRecord<R> {
+    pub path: PathBuf,
+    pub line: usize,
+    pub record: R,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum LogIssue {
+    #[error("{} has no YYYYMMDD date in its name; skipped", path.display())]
+    UndatedFile { path: PathBuf },
+    #[error("no log file for {day}")]
+    MissingDay { day: Date },
+    #[error("{}:{line}: record is dated outside its file's day", path.display())]
+    Misfiled { path: PathBuf, line: usize },
+    #[error("{}:{line}: invalid timestamp_utc", path.display())]
+    BadTimestamp { path: PathBuf, line: usize },
+    #[error("{}:{line}: proposal `{proposal_id}` already logged at {}:{first_line}", path.display(), first.display())]
+    DuplicateProposal {
+        proposal_id: String,
+        first: PathBuf,
+        first_line: usize,
+        path: PathBuf,
+        line: usize,
+    },
+}
+
+/// Records of a set of date-rotated log files, in time order, with the
+/// gaps and duplicates found while merging.
+#[derive(Clone, Debug)]
+pub struct MergedEvolveLog<R> {
+    pub files: Vec<(Date, PathBuf)>,
+    pub records: Vec<LoggedRecord<R>>,
+    pub issues: Vec<LogIssue>,
+}
+
+impl<R: EvolveRecord> MergedEvolveLog<R> {
+    /// `source` is a directory (every `*-YYYYMMDD.jsonl` in it) or a glob
+    /// pattern. Unparseable lines are errors; ordering problems are issues.
+    pub fn read(source: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let source = source.as_ref();
+        let pattern = if source.is_dir() {
+            source.join("*.jsonl")
+        } else {
+            source.to_path_buf()
+        };
+        let mut issues = Vec::new();
+        let mut files = Vec::new();
+        for path in glob::glob(&pattern.to_string_lossy())
+            .with_context(|| format!("invalid log pattern {}", pattern.display()))?
+        {
+            let path = path?;
+            match file_day(&path) {
+                Some(day) => files.push((day, path)),
+                None => issues.push(LogIssue::UndatedFile { path }),
+            }
+        }
+        files.sort();
+
+        for pair in files.windows(2) {
+            let (mut day, next) = (pair[0].0, pair[1].0);
+            while let Some(d) = day.next_day().filter(|d| *d < next) {
+                issues.push(LogIssue::MissingDay { day: d });
+                day = d;
+            }
+        }
+
+        let mut records = Vec::new();
+        for (day, path) in &files {
+            let file =
+                fs::File::open(path).with_context(|| format!("opening {}", path.display()))?;
+            for (n, line) in BufReader::new(file).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record =
+                    R::decode(&line).with_context(|| format!("{}:{}", path.display(), n + 1))?;
+                let logged = LoggedRecord {
+                    path: path.clone(),
+                    line: n + 1,
+                    record,
+                };
+                match logged.record.timestamp() {
+                    None => issues.push(LogIssue::BadTimestamp {
+                        path: path.clone(),
+                        line: n + 1,
+                    }),
+                    Some(t) if t.date() != *day => issues.push(LogIssue::Misfiled {
+                        path: path.clone(),
+                        line: n + 1,
+                    }),
+                    Some(_) => {}
+                }
+                records.push(logged);
+            }
+        }
+        // Stable: equal timestamps keep file order; invalid ones sort first.
+        records.sort_by_key(|r| r.record.timestamp());
+
+        let mut seen: HashMap<String, (PathBuf, usize)> = HashMap::new();
+        for r in &records {
+            let id = r.record.proposal_id();
+            if let Some((first, first_line)) = seen.get(id) {
+                issues.push(LogIssue::DuplicateProposal {
+                    proposal_id: id.to_string(),
+                    first: first.clone(),
+                    first_line: *first_line,
+                    path: r.path.clone(),
+                    line: r.line,
+                });
+            } else {
+                seen.insert(id.to_string(), (r.path.clone(), r.line));
+            }
+        }
+
+        Ok(Self {
+            files,
+            records,
+            issues,
+        })
+    }
+
+    pub fn into_records(self) -> impl Iterator<Item = R> {
+        self.records.into_iter().map(|r| r.record)
+    }
+}
+
+/// Day encoded as the trailing `YYYYMMDD` of a `.jsonl` file stem.
+fn file_day(path: &Path) -> Option<Date> {
+    let stem = path.file_stem()?.to_str()?;
+    let digits = stem.get(stem.len().checked_sub(8)?..)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let year = digits[..4].parse().ok()?;
+    let month = Month::try_from(digits[4..6].parse::<u8>().ok()?).ok()?;
+    Date::from_calendar_date(year, month, digits[6..].parse().ok()?).ok()
+}
+
+/// A line of an evolve log that could not be read or parsed.
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+#[error("line {line}: {message}")]
+pub struct LineError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// Streaming reader over a JSONL evolve log. Yields `(line, record)` and
+/// keeps going after a bad line; in lenient mode bad lines are skipped and
+/// counted instead. Blank lines are ignored.
+pub struct EvolveRecords<B, R> {
+    reader: B,
+    line: usize,
+    lenient: bool,
+    skipped: usize,
+    done: bool,
+    _record: PhantomData<R>,
+}
+
+impl<B: BufRead, R: EvolveRecord> EvolveRecords<B, R> {
+    pub fn new(reader: B) -> Self {
+        Self {
+            reader,
+            line: 0,
+            lenient: false,
+            skipped: 0,
+            done: false,
+            _record: PhantomData,
+        }
+    }
+
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    /// Bad lines skipped so far (lenient mode only).
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Lines consumed so far.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl<B: BufRead, R: EvolveRecord> Iterator for EvolveRecords<B, R> {
+    type Item = Result<(usize, R), LineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = String::new();
+        while !self.done {
+            buf.clear();
+            match self.reader.read_line(&mut buf) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line += 1;
+                    match parse_line(&buf, self.line) {
+                        None => continue,
+                        Some(Err(_)) if self.lenient => self.skipped += 1,
+                        Some(item) => return Some(item),
+                    }
+                }
+                // An I/O error ends the stream.
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(LineError {
+                        line: self.line + 1,
+                        message: e.to_string(),
+                    }));
+                }
+            }
+        }
+        None
+    }
+}
+
+fn parse_line<R: EvolveRecord>(text: &str, line: usize) -> Option<Result<(usize, R), LineError>> {
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some(
+        R::decode(text)
+            .map(|rec| (line, rec))
+            .map_err(|e| LineError {
+                line,
+                message: e.to_string(),
+            }),
+    )
+}
+
+/// `tail -f` over a JSONL evolve log: yields records as complete lines are
+/// appended. A partially written last line is held back until its newline
+/// arrives; a truncated file is read again from the start.
+pub struct FollowEvolveLog<R> {
+    path: PathBuf,
+    reader: BufReader<fs::File>,
+    pos: u64,
+    line: usize,
+    partial: String,
+    lenient: bool,
+    skipped: usize,
+    poll_interval: Duration,
+    _record: PhantomData<R>,
+}
+
+impl<R: EvolveRecord> FollowEvolveLog<R> {
+    /// Follow from the first record.
+    pub fn from_start(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let file = fs::File::open(&path).with_context(|| format!("opening {}", path.display()))?;
+        Ok(Self {
+            path,
+            reader: BufReader::new(file),
+            pos: 0,
+            line: 0,
+            partial: String::new(),
+            lenient: false,
+            skipped: 0,
+            poll_interval: Duration::from_millis(250),
+            _record: PhantomData,
+        })
+    }
+
+    /// Follow only records appended from now on (line numbers stay absolute).
+    pub fn from_end(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let mut follow = Self::from_start(path)?;
+        let mut buf = String::new();
+        loop {
+            buf.clear();
+            let n = follow.reader.read_line(&mut buf)?;
+            if n == 0 || !buf.ends_with('\n') {
+                // Leave a partial tail line to be completed by the writer.
+                follow.reader.seek(SeekFrom::Start(follow.pos))?;
+                break;
+            }
+            follow.pos += n as u64;
+            follow.line += 1;
+        }
+        Ok(follow)
+    }
+
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Next complete record if one is available now, without waiting.
+    pub fn poll(&mut self) -> Option<Result<(usize, R), LineError>> {
+        loop {
+            match self.read_complete_line() {
+                Ok(None) => return None,
+                Ok(Some(text)) => {
+                    self.line += 1;
+                    match parse_line(&text, self.line) {
+                        None => continue,
+                        Some(Err(_)) if self.lenient => self.skipped += 1,
+                        Some(item) => return Some(item),
+                    }
+                }
+                Err(e) => {
+                    return Some(Err(LineError {
+                        line: self.line + 1,
+                        message: e.to_string(),
+                    }))
+                }
+            }
+        }
+    }
+
+    fn read_complete_line(&mut self) -> std::io::Result<Option<String>> {
+        let len = fs::metadata(&self.path)?.len();
+        if len < self.pos {
+            // Truncated or replaced: start over.
+            self.reader = BufReader::new(fs::File::open(&self.path)?);
+            self.pos = 0;
+            self.line = 0;
+            self.partial.clear();
+        }
+        let n = self.reader.read_line(&mut self.partial)?;
+        self.pos += n as u64;
+        if self.partial.ends_with('\n') {
+            Ok(Some(std::mem::take(&mut self.partial)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<R: EvolveRecord> Iterator for FollowEvolveLog<R> {
+    type Item = Result<(usize, R), LineError>;
+
+    /// Blocks, polling every `poll_interval`, until the next record arrives.
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.poll() {
+                return Some(item);
+            }
+            thread::sleep(self.poll_interval);
+        }
+    }
+}
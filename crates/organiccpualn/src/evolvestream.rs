@@ -1,5 +1,25 @@
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::io::{BufRead, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::evolvelog::{EvolveRecords, FollowEvolveLog};
+use crate::hexstamp::{self, Canonical, HexstampError, Hexstamped};
+use crate::proposalsig::ProposalSignature;
+
+/// `schema_version` new `.evolve.jsonl` records are written with. Version 2
+/// stamps a salted commitment to the payload instead of the payload itself,
+/// so a forgotten record still verifies.
+pub const EVOLVE_SCHEMA_VERSION: u32 = 2;
+
+/// First version whose records carry a hexstamp. Older records are only
+/// read by `from_legacy_json`; stamped records keep the version they were
+/// written with, since their hexstamp covers that version's layout.
+const FIRST_STAMPED_VERSION: u32 = 1;
+
+/// `kind` of a rollback proposal; its `updatekind` is the donutloop entry it undoes.
+pub const ROLLBACK_KIND: &str = "Rollback";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EffectBounds {
@@ -8,10 +28,12 @@ pub struct EffectBounds {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct EvolutionProposalRecord {
+    pub schema_version: u32,
     pub proposalid: String,
     pub subjectid: String,
-    pub scope: String, // lifeforcealteration | archchange | daytodaytuning | …
+    pub scope: Scope,
     pub kind: String,
     pub module: String,
     pub updatekind: String,
@@ -20,10 +42,453 @@ pub struct EvolutionProposalRecord {
     pub roh_after: f32,
     pub tsafe_mode: String,
     pub signer_roles: Vec<String>,
-    pub tokenkind: String, // SMART | EVOLVE
-    pub decision: String,  // Allowed | Rejected | Deferred
+    pub tokenkind: TokenKind,
+    pub decision: Decision,
     pub hexstamp: String,
     pub timestamp_utc: String,
+    #[serde(default)]
+    pub purposes: Vec<String>, // checked against neurorights allowed_uses / forbid_decision_use
+    #[serde(default)]
+    pub data_access: Vec<String>, // neural_patterns | biostate_summaries | dream_metrics
+    // v2: hex salt of the payload commitment; cleared when forgotten.
+    #[serde(default)]
+    pub payload_salt: String,
+    // v2: hexstamp over salt + payload fields, covered by `hexstamp`.
+    #[serde(default)]
+    pub payload_commitment: String,
+    // Signers' ed25519 signatures over `signing_canonical`; part of the v2 payload.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<ProposalSignature>,
+    // Set when the payload was tombstoned under a right-to-forget request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forgotten_utc: Option<String>,
+}
+
+/// Evolution scope, spelled as in `.evolve.jsonl`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    DayToDayTuning,
+    ArchChange,
+    LifeforceAlteration,
+}
+
+/// Token a proposal is paid with, spelled as in `.evolve.jsonl`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum TokenKind {
+    Smart,
+    Evolve,
+}
+
+/// Lifecycle state of a proposal, spelled as in `.evolve.jsonl`. Each
+/// logged record carries the state its proposal entered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Decision {
+    Proposed,
+    // Waiting for required multisig signers.
+    Pending,
+    Allowed,
+    Rejected,
+    Deferred,
+    // An allowed change the executor has carried out.
+    Applied,
+    RolledBack,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("unknown {kind} `{value}`")]
+pub struct UnknownVariant {
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::DayToDayTuning => "daytodaytuning",
+            Scope::ArchChange => "archchange",
+            Scope::LifeforceAlteration => "lifeforcealteration",
+        }
+    }
+}
+
+impl FromStr for Scope {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daytodaytuning" => Ok(Scope::DayToDayTuning),
+            "archchange" => Ok(Scope::ArchChange),
+            "lifeforcealteration" => Ok(Scope::LifeforceAlteration),
+            _ => Err(UnknownVariant {
+                kind: "scope",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TokenKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenKind::Smart => "SMART",
+            TokenKind::Evolve => "EVOLVE",
+        }
+    }
+}
+
+impl FromStr for TokenKind {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "SMART" => Ok(TokenKind::Smart),
+            "EVOLVE" => Ok(TokenKind::Evolve),
+            _ => Err(UnknownVariant {
+                kind: "token kind",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Decision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Decision::Proposed => "Proposed",
+            Decision::Pending => "Pending",
+            Decision::Allowed => "Allowed",
+            Decision::Rejected => "Rejected",
+            Decision::Deferred => "Deferred",
+            Decision::Applied => "Applied",
+            Decision::RolledBack => "RolledBack",
+        }
+    }
+}
+
+impl FromStr for Decision {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Proposed" => Ok(Decision::Proposed),
+            "Pending" => Ok(Decision::Pending),
+            "Allowed" => Ok(Decision::Allowed),
+            "Rejected" => Ok(Decision::Rejected),
+            "Deferred" => Ok(Decision::Deferred),
+            "Applied" => Ok(Decision::Applied),
+            "RolledBack" => Ok(Decision::RolledBack),
+            _ => Err(UnknownVariant {
+                kind: "decision",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SchemaError {
+    #[error("invalid record: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("record is not a JSON object")]
+    NotAnObject,
+    #[error("schema_version must be a non-negative integer")]
+    BadVersion,
+    #[error("schema_version {found} records carry no hexstamp; import them with from_legacy_json")]
+    Unstamped { found: u64 },
+    #[error("record without schema_version carries `{field}`; stamped records must declare it")]
+    UnversionedStamp { field: &'static str },
+    #[error("schema_version {found} is newer than supported {supported}")]
+    UnsupportedVersion { found: u64, supported: u32 },
+    #[error(transparent)]
+    Hexstamp(#[from] HexstampError),
+}
+
+// MIGRATIONS[n] upgrades an unstamped version-n record to version n + 1.
+const MIGRATIONS: [fn(&mut serde_json::Map<String, serde_json::Value>); 1] = [migrate_v0];
+
+/// v0 records had no `schema_version` and free-form enum strings: normalize
+/// case and separators so `LifeforceAlteration`, `lifeforce_alteration`,
+/// `smart` or `allowed` decode. Anything still unknown fails strict decoding.
+fn migrate_v0(rec: &mut serde_json::Map<String, serde_json::Value>) {
+    use serde_json::Value;
+    let mut normalize = |key: &str, f: fn(&str) -> String| {
+        if let Some(Value::String(v)) = rec.get_mut(key) {
+            *v = f(v.trim());
+        }
+    };
+    normalize("scope", |v| {
+        v.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase()
+    });
+    normalize("tokenkind", |v| v.to_ascii_uppercase());
+    normalize("decision", |v| {
+        let lower = v.to_ascii_lowercase();
+        let mut chars = lower.chars();
+        match chars.next() {
+            Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+            None => lower,
+        }
+    });
+}
+
+/// `schema_version` of a raw record; 0 if absent.
+fn schema_version(value: &serde_json::Value) -> Result<u64, SchemaError> {
+    let rec = value.as_object().ok_or(SchemaError::NotAnObject)?;
+    let found = match rec.get("schema_version") {
+        None => 0,
+        Some(v) => v.as_u64().ok_or(SchemaError::BadVersion)?,
+    };
+    if found > u64::from(EVOLVE_SCHEMA_VERSION) {
+        return Err(SchemaError::UnsupportedVersion {
+            found,
+            supported: EVOLVE_SCHEMA_VERSION,
+        });
+    }
+    Ok(found)
+}
+
+/// First field of a raw record that only stamped versions carry.
+fn stamp_field(rec: &serde_json::Map<String, serde_json::Value>) -> Option<&'static str> {
+    use serde_json::Value;
+    ["hexstamp", "payload_commitment", "signatures"]
+        .into_iter()
+        .find(|field| match rec.get(*field) {
+            None | Some(Value::Null) => false,
+            Some(Value::String(s)) => !s.is_empty(),
+            Some(Value::Array(a)) => !a.is_empty(),
+            Some(_) => true,
+        })
+}
+
+impl EvolutionProposalRecord {
+    /// Decode one stamped record strictly (unknown fields or enum values are
+    /// errors) and verify its hexstamp for the version it was written with.
+    /// Records older than the first stamped version are rejected: every log
+    /// reader goes through here, so an edited line cannot pass as unstamped.
+    pub fn from_json(text: &str) -> Result<Self, SchemaError> {
+        let value: serde_json::Value = serde_json::from_str(text)?;
+        let found = schema_version(&value)?;
+        if found < u64::from(FIRST_STAMPED_VERSION) {
+            return Err(SchemaError::Unstamped { found });
+        }
+        let rec: Self = serde_json::from_value(value)?;
+        rec.verify_hexstamp()?;
+        Ok(rec)
+    }
+
+    /// One-off import of a pre-hexstamp log line. Unstamped v0 records are
+    /// migrated to v1 and returned with an empty hexstamp: the importer
+    /// vouches for them and must stamp them before logging. A record without
+    /// `schema_version` but with a hexstamp, commitment or signatures is
+    /// rejected; stamped records decode as in `from_json`.
+    pub fn from_legacy_json(text: &str) -> Result<Self, SchemaError> {
+        let mut value: serde_json::Value = serde_json::from_str(text)?;
+        let found = schema_version(&value)?;
+        if found >= u64::from(FIRST_STAMPED_VERSION) {
+            return Self::from_json(text);
+        }
+        let rec = value.as_object_mut().ok_or(SchemaError::NotAnObject)?;
+        if let Some(field) = stamp_field(rec) {
+            return Err(SchemaError::UnversionedStamp { field });
+        }
+        for migrate in &MIGRATIONS[found as usize..] {
+            migrate(rec);
+        }
+        rec.insert("schema_version".into(), FIRST_STAMPED_VERSION.into());
+        rec.entry("hexstamp").or_insert_with(|| "".into());
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Whether the payload fields hold tombstones rather than subject data.
+    pub fn is_forgotten(&self) -> bool {
+        self.forgotten_utc.is_some()
+    }
+
+    /// Commitment to the payload fields under `payload_salt`. Signatures
+    /// are only committed to when present, so unsigned records keep the
+    /// commitment they were written with.
+    pub fn compute_payload_commitment(&self) -> String {
+        let payload = Canonical::new("evolve-payload", self.schema_version)
+            .str("salt", &self.payload_salt)
+            .str("module", &self.module)
+            .str("updatekind", &self.updatekind)
+            .f32("effectbounds.l2_delta_norm", self.effectbounds.l2_delta_norm)
+            .bool("effectbounds.irreversible", self.effectbounds.irreversible)
+            .str("tsafe_mode", &self.tsafe_mode)
+            .list("signer_roles", &self.signer_roles)
+            .list("purposes", &self.purposes)
+            .list("data_access", &self.data_access);
+        if self.signatures.is_empty() {
+            return payload.hexstamp();
+        }
+        let signatures: Vec<String> = self
+            .signatures
+            .iter()
+            .map(|s| format!("{} {} {}", s.address, s.public_key, s.signature))
+            .collect();
+        payload.list("signatures", &signatures).hexstamp()
+    }
+
+    /// Draw a payload salt from the OS if a v2 record still needs one.
+    pub fn salt_payload(&mut self) -> Result<(), getrandom::Error> {
+        if self.schema_version >= 2 && !self.is_forgotten() && self.payload_salt.is_empty() {
+            self.payload_salt = hexstamp::random_salt()?;
+        }
+        Ok(())
+    }
+
+    /// Right to forget: replace the payload with tombstones and drop the
+    /// salt. The commitment, and so the hexstamp, stay valid. v1 records
+    /// stamp the payload itself and have no commitment; they are verified,
+    /// then re-stamped as v2 first, so their hexstamp changes. The v2 stamp
+    /// verifies from then on; the v1 stamp cannot be recomputed without the
+    /// payload and is kept only in the forget receipt.
+    pub fn forget(&mut self, forgotten_utc: String) -> Result<(), HexstampError> {
+        self.verify_hexstamp()?;
+        if self.is_forgotten() {
+            return Ok(());
+        }
+        if self.schema_version < 2 {
+            self.schema_version = 2;
+            self.stamp();
+        }
+        self.module.clear();
+        self.updatekind.clear();
+        self.effectbounds = EffectBounds {
+            l2_delta_norm: 0.0,
+            irreversible: false,
+        };
+        self.tsafe_mode.clear();
+        self.signer_roles.clear();
+        self.purposes.clear();
+        self.data_access.clear();
+        self.signatures.clear();
+        self.payload_salt.clear();
+        self.forgotten_utc = Some(forgotten_utc);
+        Ok(())
+    }
+
+    fn is_tombstoned(&self) -> bool {
+        self.module.is_empty()
+            && self.updatekind.is_empty()
+            && self.effectbounds.l2_delta_norm == 0.0
+            && !self.effectbounds.irreversible
+            && self.tsafe_mode.is_empty()
+            && self.signer_roles.is_empty()
+            && self.purposes.is_empty()
+            && self.data_access.is_empty()
+            && self.signatures.is_empty()
+            && self.payload_salt.is_empty()
+    }
+}
+
+impl EvolutionProposalRecord {
+    /// The donutloop entry this proposal undoes, if it is a rollback.
+    pub fn rollback_target(&self) -> Option<&str> {
+        (self.kind == ROLLBACK_KIND).then_some(self.updatekind.as_str())
+    }
+}
+
+impl Hexstamped for EvolutionProposalRecord {
+    /// Field order of the record's `schema_version`; `hexstamp` is excluded.
+    fn canonical(&self) -> Canonical {
+        let header = Canonical::new("evolve-proposal", self.schema_version);
+        if self.schema_version >= 2 {
+            return header
+                .str("proposalid", &self.proposalid)
+                .str("subjectid", &self.subjectid)
+                .str("scope", self.scope.as_str())
+                .str("kind", &self.kind)
+                .f32("roh_before", self.roh_before)
+                .f32("roh_after", self.roh_after)
+                .str("tokenkind", self.tokenkind.as_str())
+                .str("decision", self.decision.as_str())
+                .str("timestamp_utc", &self.timestamp_utc)
+                .str("payload_commitment", &self.payload_commitment);
+        }
+        header
+            .str("proposalid", &self.proposalid)
+            .str("subjectid", &self.subjectid)
+            .str("scope", self.scope.as_str())
+            .str("kind", &self.kind)
+            .str("module", &self.module)
+            .str("updatekind", &self.updatekind)
+            .f32("effectbounds.l2_delta_norm", self.effectbounds.l2_delta_norm)
+            .bool("effectbounds.irreversible", self.effectbounds.irreversible)
+            .f32("roh_before", self.roh_before)
+            .f32("roh_after", self.roh_after)
+            .str("tsafe_mode", &self.tsafe_mode)
+            .list("signer_roles", &self.signer_roles)
+            .str("tokenkind", self.tokenkind.as_str())
+            .str("decision", self.decision.as_str())
+            .str("timestamp_utc", &self.timestamp_utc)
+            .list("purposes", &self.purposes)
+            .list("data_access", &self.data_access)
+    }
+
+    fn hexstamp(&self) -> &str {
+        &self.hexstamp
+    }
+
+    fn set_hexstamp(&mut self, hexstamp: String) {
+        self.hexstamp = hexstamp;
+    }
+
+    /// v2 records commit to their payload first, under a fresh salt if none
+    /// is set yet. If no salt can be drawn the commitment is left empty, so
+    /// the record fails verification instead of carrying a guessable one.
+    fn stamp(&mut self) {
+        if self.schema_version >= 2 && !self.is_forgotten() {
+            self.payload_commitment = match self.salt_payload() {
+                Ok(()) => self.compute_payload_commitment(),
+                Err(_) => String::new(),
+            };
+        }
+        self.hexstamp = self.compute_hexstamp();
+    }
+
+    /// Also checks the payload against its commitment, or, once forgotten,
+    /// that only tombstones remain.
+    fn verify_hexstamp(&self) -> Result<(), HexstampError> {
+        if self.schema_version >= 2 {
+            if self.is_forgotten() {
+                if !self.is_tombstoned() {
+                    return Err(HexstampError::NotTombstoned);
+                }
+            } else {
+                let expected = self.compute_payload_commitment();
+                if self.payload_commitment != expected {
+                    return Err(HexstampError::PayloadMismatch {
+                        expected,
+                        found: self.payload_commitment.clone(),
+                    });
+                }
+            }
+        }
+        hexstamp::check(&self.hexstamp, self.compute_hexstamp())
+    }
 }
 
 pub trait EvolutionLogReader {
@@ -48,19 +513,34 @@ impl EvolutionLogReader for JsonlEvolutionLog {
             if line.trim().is_empty() {
                 continue;
             }
-            let rec: EvolutionProposalRecord = serde_json::from_str(&line)?;
-            out.push(rec);
+            out.push(EvolutionProposalRecord::from_json(&line)?);
         }
         Ok(out)
     }
 }
 
+impl JsonlEvolutionLog {
+    /// Stream records one line at a time instead of loading the whole log.
+    pub fn records<R: BufRead>(&self, reader: R) -> EvolveRecords<R, EvolutionProposalRecord> {
+        EvolveRecords::new(reader)
+    }
+
+    /// Follow `path` like `tail -f`, starting at its current end.
+    pub fn follow(
+        &self,
+        path: impl Into<PathBuf>,
+    ) -> anyhow::Result<FollowEvolveLog<EvolutionProposalRecord>> {
+        FollowEvolveLog::from_end(path)
+    }
+}
+
 impl EvolutionLogWriter for JsonlEvolutionLog {
     fn append<W: Write>(
         &self,
         writer: &mut W,
         rec: &EvolutionProposalRecord,
     ) -> anyhow::Result<()> {
+        rec.verify_hexstamp()?;
         let line = serde_json::to_string(rec)?;
         writeln!(writer, "{}", line)?;
         Ok(())
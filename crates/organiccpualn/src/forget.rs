@@ -0,0 +1,323 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
+
+use crate::evolvestream::EvolutionProposalRecord;
+use crate::hexstamp::Canonical;
+
+/// A subject's request to have its record payloads forgotten.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ForgetRequest {
+    pub request_id: String,
+    pub subject_id: String,
+    pub requested_utc: String,
+}
+
+/// A record whose payload was tombstoned; the commitment stays in its place.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ForgottenRecord {
+    pub proposal_id: String,
+    pub hexstamp: String,
+    pub payload_commitment: String,
+    // The v1 hexstamp of a record re-stamped as v2 to be forgotten. It can
+    // no longer be recomputed, so the signed receipt is what ties it to the
+    // new stamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restamped_from: Option<String>,
+}
+
+/// Signed proof that a forget request was carried out.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ForgetReceipt {
+    pub request_id: String,
+    pub subject_id: String,
+    pub requested_utc: String,
+    pub completed_utc: String,
+    pub records: Vec<ForgottenRecord>,
+    // Donutloop entries carry ids, decisions and RoH values but no payload,
+    // so they are left as is; the head shows the chain was not rewritten.
+    pub donutloop_head: String,
+    // Logs still holding records of the subject, e.g. the day-rotated
+    // nnet-evolve files, whose records have no payload commitment to
+    // redact under.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_covered: Vec<PathBuf>,
+    // Hex ed25519 public key and signature over the receipt's canonical form.
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// One line of the forget log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ForgetEvent {
+    Request(ForgetRequest),
+    Receipt(ForgetReceipt),
+}
+
+/// A request past its SLA: still open, or completed late.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OverdueForget {
+    pub request: ForgetRequest,
+    pub deadline_utc: String,
+    pub completed_utc: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum ForgetError {
+    #[error("forget request `{0}` is unknown")]
+    UnknownRequest(String),
+    #[error("forget request `{0}` is already completed")]
+    AlreadyCompleted(String),
+    #[error("invalid timestamp `{0}`")]
+    BadTimestamp(String),
+    #[error("forget receipt for `{0}` has an invalid signature")]
+    BadSignature(String),
+}
+
+impl ForgetReceipt {
+    fn canonical(&self) -> Canonical {
+        let records: Vec<String> = self
+            .records
+            .iter()
+            .map(|r| {
+                let line = format!("{} {} {}", r.proposal_id, r.hexstamp, r.payload_commitment);
+                match &r.restamped_from {
+                    Some(v1) => format!("{line} {v1}"),
+                    None => line,
+                }
+            })
+            .collect();
+        let canonical = Canonical::new("forget-receipt", 1)
+            .str("request_id", &self.request_id)
+            .str("subject_id", &self.subject_id)
+            .str("requested_utc", &self.requested_utc)
+            .str("completed_utc", &self.completed_utc)
+            .list("records", &records)
+            .str("donutloop_head", &self.donutloop_head)
+            .str("public_key", &self.public_key);
+        // Only signed over when present, so earlier receipts still verify.
+        if self.not_covered.is_empty() {
+            return canonical;
+        }
+        let not_covered: Vec<String> = self
+            .not_covered
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+        canonical.list("not_covered", &not_covered)
+    }
+
+    /// Set `public_key` and sign.
+    pub fn sign(&mut self, key: &SigningKey) {
+        self.public_key = hex::encode(key.verifying_key().as_bytes());
+        let signature = key.sign(self.canonical().as_str().as_bytes());
+        self.signature = hex::encode(signature.to_bytes());
+    }
+
+    /// Check the signature against the embedded `public_key`. Whether that
+    /// key belongs to a trusted signer is up to the caller.
+    pub fn verify_signature(&self) -> Result<(), ForgetError> {
+        let bad = || ForgetError::BadSignature(self.request_id.clone());
+        let key: [u8; 32] = hex::decode(&self.public_key)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(bad)?;
+        let signature: [u8; 64] = hex::decode(&self.signature)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(bad)?;
+        VerifyingKey::from_bytes(&key)
+            .map_err(|_| bad())?
+            .verify(
+                self.canonical().as_str().as_bytes(),
+                &Signature::from_bytes(&signature),
+            )
+            .map_err(|_| bad())
+    }
+}
+
+/// Append-only JSONL log of forget requests and their receipts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ForgetLog {
+    path: PathBuf,
+}
+
+impl ForgetLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every event so far; a missing file is an empty log.
+    pub fn events(&self) -> anyhow::Result<Vec<ForgetEvent>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let file = File::open(&self.path)
+            .with_context(|| format!("opening forget log {}", self.path.display()))?;
+        let mut events = Vec::new();
+        for (n, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            events.push(
+                serde_json::from_str(&line)
+                    .with_context(|| format!("{}:{}", self.path.display(), n + 1))?,
+            );
+        }
+        Ok(events)
+    }
+
+    /// Record a new request for `subject_id`.
+    pub fn request(
+        &self,
+        subject_id: &str,
+        requested_utc: String,
+    ) -> anyhow::Result<ForgetRequest> {
+        parse_utc(&requested_utc)?;
+        let count = self
+            .events()?
+            .iter()
+            .filter(|e| matches!(e, ForgetEvent::Request(_)))
+            .count();
+        let request = ForgetRequest {
+            request_id: format!("forget-{}", count + 1),
+            subject_id: subject_id.to_string(),
+            requested_utc,
+        };
+        self.append(&ForgetEvent::Request(request.clone()))?;
+        Ok(request)
+    }
+
+    /// The open request `request_id`.
+    pub fn pending(&self, request_id: &str) -> anyhow::Result<ForgetRequest> {
+        let events = self.events()?;
+        let request = events
+            .iter()
+            .find_map(|e| match e {
+                ForgetEvent::Request(r) if r.request_id == request_id => Some(r.clone()),
+                _ => None,
+            })
+            .ok_or_else(|| ForgetError::UnknownRequest(request_id.to_string()))?;
+        if receipt_for(&events, request_id).is_some() {
+            return Err(ForgetError::AlreadyCompleted(request_id.to_string()).into());
+        }
+        Ok(request)
+    }
+
+    /// Record a signed receipt, closing its request.
+    pub fn complete(&self, receipt: &ForgetReceipt) -> anyhow::Result<()> {
+        self.pending(&receipt.request_id)?;
+        receipt.verify_signature()?;
+        self.append(&ForgetEvent::Receipt(receipt.clone()))
+    }
+
+    /// Requests that were open, or completed, past `sla_hours` as of `now`.
+    pub fn sweep(&self, now: OffsetDateTime, sla_hours: u32) -> anyhow::Result<Vec<OverdueForget>> {
+        let events = self.events()?;
+        let mut overdue = Vec::new();
+        for event in &events {
+            let ForgetEvent::Request(request) = event else {
+                continue;
+            };
+            let deadline = parse_utc(&request.requested_utc)? + Duration::hours(sla_hours.into());
+            let completed_utc =
+                receipt_for(&events, &request.request_id).map(|r| r.completed_utc.clone());
+            let done_at = match &completed_utc {
+                Some(t) => parse_utc(t)?,
+                None => now,
+            };
+            if done_at > deadline {
+                overdue.push(OverdueForget {
+                    request: request.clone(),
+                    deadline_utc: deadline.format(&Rfc3339)?,
+                    completed_utc,
+                });
+            }
+        }
+        Ok(overdue)
+    }
+
+    fn append(&self, event: &ForgetEvent) -> anyhow::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("opening forget log {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(event)?)?;
+        file.sync_data()?;
+        Ok(())
+    }
+}
+
+fn receipt_for<'a>(events: &'a [ForgetEvent], request_id: &str) -> Option<&'a ForgetReceipt> {
+    events.iter().find_map(|e| match e {
+        ForgetEvent::Receipt(r) if r.request_id == request_id => Some(r),
+        _ => None,
+    })
+}
+
+fn parse_utc(s: &str) -> Result<OffsetDateTime, ForgetError> {
+    OffsetDateTime::parse(s, &Rfc3339).map_err(|_| ForgetError::BadTimestamp(s.to_string()))
+}
+
+/// Tombstone the payload of every record of `subject_id` in an evolve log.
+/// The log is rewritten to a temporary file and renamed into place, so a
+/// crash leaves either the old or the redacted log.
+pub fn redact_evolve_log(
+    path: &Path,
+    subject_id: &str,
+    forgotten_utc: &str,
+) -> anyhow::Result<Vec<ForgottenRecord>> {
+    let file =
+        File::open(path).with_context(|| format!("opening evolve log {}", path.display()))?;
+    let mut lines = Vec::new();
+    let mut forgotten = Vec::new();
+    for (n, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut rec = EvolutionProposalRecord::from_json(&line)
+            .with_context(|| format!("{}:{}", path.display(), n + 1))?;
+        if rec.subjectid != subject_id {
+            lines.push(line);
+            continue;
+        }
+        let restamped_from = (rec.schema_version < 2).then(|| rec.hexstamp.clone());
+        rec.forget(forgotten_utc.to_string())
+            .with_context(|| format!("{}:{}", path.display(), n + 1))?;
+        forgotten.push(ForgottenRecord {
+            proposal_id: rec.proposalid.clone(),
+            hexstamp: rec.hexstamp.clone(),
+            payload_commitment: rec.payload_commitment.clone(),
+            restamped_from,
+        });
+        lines.push(serde_json::to_string(&rec)?);
+    }
+
+    let tmp = path.with_extension("jsonl.tmp");
+    {
+        let mut f = File::create(&tmp).with_context(|| format!("creating {}", tmp.display()))?;
+        for line in &lines {
+            writeln!(f, "{line}")?;
+        }
+        f.sync_all()?;
+    }
+    fs::rename(&tmp, path)?;
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        File::open(dir)?.sync_all()?;
+    }
+    Ok(forgotten)
+}
@@ -0,0 +1,135 @@
+use std::fmt::Write;
+
+use sha2::{Digest, Sha256};
+
+/// Canonical byte form of a record, the input to its hexstamp.
+///
+/// The first line names the record type and its canonical version
+/// (`@evolve-proposal/1`); every field follows on its own line as
+/// `name=value`, in the order the record type fixes for that version.
+/// Strings and string lists are JSON-encoded, floats use the shortest
+/// decimal that round-trips (`-0` is written as `0`), booleans are
+/// `true`/`false`. The hexstamp field itself is never included. A field
+/// order, once released under a version, never changes: new fields mean a
+/// new version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Canonical {
+    text: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum HexstampError {
+    #[error("record has no hexstamp")]
+    Missing,
+    #[error("hexstamp `{0}` is not 0x followed by 64 lowercase hex digits")]
+    Malformed(String),
+    #[error("hexstamp {found} does not match record contents ({expected})")]
+    Mismatch { expected: String, found: String },
+    #[error("payload_commitment {found} does not match record payload ({expected})")]
+    PayloadMismatch { expected: String, found: String },
+    #[error("record is marked forgotten but still carries payload")]
+    NotTombstoned,
+}
+
+impl Canonical {
+    pub fn new(kind: &str, version: u32) -> Self {
+        Self {
+            text: format!("@{kind}/{version}\n"),
+        }
+    }
+
+    pub fn str(mut self, name: &str, value: &str) -> Self {
+        let value = serde_json::Value::from(value).to_string();
+        self.field(name, &value);
+        self
+    }
+
+    pub fn f32(mut self, name: &str, value: f32) -> Self {
+        let value = if value == 0.0 { 0.0 } else { value };
+        self.field(name, &value.to_string());
+        self
+    }
+
+    pub fn bool(mut self, name: &str, value: bool) -> Self {
+        self.field(name, if value { "true" } else { "false" });
+        self
+    }
+
+    pub fn u32(mut self, name: &str, value: u32) -> Self {
+        self.field(name, &value.to_string());
+        self
+    }
+
+    pub fn list(mut self, name: &str, values: &[String]) -> Self {
+        let value = serde_json::Value::from(values).to_string();
+        self.field(name, &value);
+        self
+    }
+
+    fn field(&mut self, name: &str, value: &str) {
+        let _ = writeln!(self.text, "{name}={value}");
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// `0x` + lowercase hex SHA-256 of the canonical bytes.
+    pub fn hexstamp(&self) -> String {
+        format!("0x{}", hex::encode(Sha256::digest(self.text.as_bytes())))
+    }
+}
+
+/// A record carrying a hexstamp over its canonical form.
+pub trait Hexstamped {
+    fn canonical(&self) -> Canonical;
+
+    fn hexstamp(&self) -> &str;
+
+    fn set_hexstamp(&mut self, hexstamp: String);
+
+    fn compute_hexstamp(&self) -> String {
+        self.canonical().hexstamp()
+    }
+
+    /// Stamp the record as it stands; call after its last field change.
+    fn stamp(&mut self) {
+        let hexstamp = self.compute_hexstamp();
+        self.set_hexstamp(hexstamp);
+    }
+
+    /// Recompute the stamp and compare: detects any edit after stamping.
+    fn verify_hexstamp(&self) -> Result<(), HexstampError> {
+        check(self.hexstamp(), self.compute_hexstamp())
+    }
+}
+
+/// Compare a stored stamp with the recomputed one.
+pub fn check(found: &str, expected: String) -> Result<(), HexstampError> {
+    if found.is_empty() {
+        return Err(HexstampError::Missing);
+    }
+    if !is_hexstamp(found) {
+        return Err(HexstampError::Malformed(found.to_string()));
+    }
+    if found != expected {
+        return Err(HexstampError::Mismatch {
+            expected,
+            found: found.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// 32 random bytes from the OS, hex-encoded, for salting payload commitments.
+pub fn random_salt() -> Result<String, getrandom::Error> {
+    let mut salt = [0u8; 32];
+    getrandom::getrandom(&mut salt)?;
+    Ok(hex::encode(salt))
+}
+
+/// `0x` followed by 64 lowercase hex digits.
+pub fn is_hexstamp(s: &str) -> bool {
+    s.strip_prefix("0x")
+        .is_some_and(|h| h.len() == 64 && h.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')))
+}
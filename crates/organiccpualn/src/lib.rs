@@ -0,0 +1,18 @@
+pub mod aln;
+pub mod donutloopledger;
+pub mod evolvelog;
+pub mod evolvestream;
+pub mod forget;
+pub mod hexstamp;
+pub mod manifest;
+pub mod neurorights;
+pub mod nnetbind;
+pub mod nnetcap;
+pub mod nnetevolve;
+pub mod nnetfs;
+pub mod nnetpolicy;
+pub mod nnetproof;
+pub mod proposalsig;
+pub mod rohmodel;
+pub mod schema;
+pub mod stake;
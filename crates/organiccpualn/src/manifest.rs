@@ -0,0 +1,199 @@
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+use crate::aln::{AlnDocument, AlnEntry, AlnError, AlnSection};
+use crate::donutloopledger::DonutloopLedger;
+use crate::neurorights::NeurorightsPolicyDocument;
+use crate::rohmodel::RohModelShard;
+use crate::stake::{StakeRequirements, StakeShard};
+
+// Decision uses a `nondiscriminate` workspace must forbid.
+const NONDISCRIMINATE_USES: [&str; 4] = ["employment", "housing", "credit", "insurance"];
+
+/// `neuro-workspace.manifest.aln`: subject, shard locations, invariants and
+/// guard pipeline for one sovereign workspace.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WorkspaceManifest {
+    pub manifest_id: String,
+    pub subject_id: String,
+    pub version: String,
+    pub paths: ManifestPaths,
+    pub coreshards: CoreShards,
+    pub invariants: ManifestInvariants,
+    pub stake_requirements: StakeRequirements,
+    pub guard_pipeline: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManifestPaths {
+    pub qpudataroot: PathBuf,
+    pub policiesroot: PathBuf,
+    pub logsroot: PathBuf,
+}
+
+/// Shard paths, already resolved against their root in `paths`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CoreShards {
+    pub rohmodel: PathBuf,
+    pub stake: PathBuf,
+    pub neurorights: PathBuf,
+    pub evolvestream: PathBuf,
+    pub donutloop: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManifestInvariants {
+    pub roh_ceiling: f32,
+    pub roh_monotone: bool,
+    pub env_monotone: bool,
+    pub dream_no_comm: bool,
+    pub nondiscriminate: bool,
+}
+
+impl WorkspaceManifest {
+    /// Load a manifest; relative roots resolve against the manifest's directory.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let doc = AlnDocument::load(path)?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+        Ok(Self::from_aln(&doc, base)?)
+    }
+
+    pub fn from_aln(doc: &AlnDocument, base: &Path) -> Result<Self, AlnError> {
+        let meta = doc.require_section("meta")?;
+        let paths = doc.require_section("paths")?;
+        let paths = ManifestPaths {
+            qpudataroot: resolve_root(paths.require_entry("qpudataroot")?, base),
+            policiesroot: resolve_root(paths.require_entry("policiesroot")?, base),
+            logsroot: resolve_root(paths.require_entry("logsroot")?, base),
+        };
+
+        let shards = doc.require_section("coreshards")?;
+        let coreshards = CoreShards {
+            rohmodel: resolve_shard(shards, "rohmodel", &paths.qpudataroot)?,
+            stake: resolve_shard(shards, "stake", &paths.qpudataroot)?,
+            neurorights: resolve_shard(shards, "neurorights", &paths.policiesroot)?,
+            evolvestream: resolve_shard(shards, "evolvestream", &paths.qpudataroot)?,
+            donutloop: resolve_shard(shards, "donutloop", &paths.logsroot)?,
+        };
+
+        let inv = doc.require_section("invariants")?;
+        let invariants = ManifestInvariants {
+            roh_ceiling: inv.require_entry("rohceiling")?.parse()?,
+            roh_monotone: inv.require_entry("rohmonotone")?.bool()?,
+            env_monotone: inv.require_entry("envmonotone")?.bool()?,
+            dream_no_comm: inv.require_entry("dreamnocomm")?.bool()?,
+            nondiscriminate: inv.require_entry("nondiscriminate")?.bool()?,
+        };
+
+        Ok(Self {
+            manifest_id: meta.require_entry("manifestid")?.value.clone(),
+            subject_id: meta.require_entry("subjectid")?.value.clone(),
+            version: meta.require_entry("version")?.value.clone(),
+            paths,
+            coreshards,
+            invariants,
+            stake_requirements: StakeRequirements::from_aln(
+                doc.require_section("stakerequirements")?,
+            )?,
+            guard_pipeline: doc
+                .require_section("guardpipeline")?
+                .require_entry("order")?
+                .list(),
+        })
+    }
+
+    /// Cross-check the manifest's invariants against the loaded shards.
+    pub fn check_shards(
+        &self,
+        roh_model: &RohModelShard,
+        stake: &StakeShard,
+        neurorights: &NeurorightsPolicyDocument,
+        donutloop: &DonutloopLedger,
+    ) -> anyhow::Result<()> {
+        let inv = &self.invariants;
+        if (inv.roh_ceiling - roh_model.roh_ceiling()).abs() > f32::EPSILON {
+            bail!(
+                "manifest rohceiling {} does not match RoH model ceiling {}",
+                inv.roh_ceiling,
+                roh_model.roh_ceiling()
+            );
+        }
+        for (name, enabled) in [
+            ("rohmonotone", inv.roh_monotone),
+            ("envmonotone", inv.env_monotone),
+            ("dreamnocomm", inv.dream_no_comm),
+            ("nondiscriminate", inv.nondiscriminate),
+        ] {
+            if !enabled {
+                bail!("manifest invariant `{name}` is mandatory and cannot be disabled");
+            }
+        }
+        if !(neurorights.dream_state_sensitive
+            && neurorights.noncommercial
+            && !neurorights.data_scope.may_read_dream_metrics)
+        {
+            bail!("invariant `dreamnocomm` requires a dream-sensitive, noncommercial neurorights policy without dream-metric reads");
+        }
+        if let Some(missing) = NONDISCRIMINATE_USES
+            .iter()
+            .find(|u| !neurorights.forbid_decision_use.iter().any(|f| f == *u))
+        {
+            bail!(
+                "invariant `nondiscriminate` requires forbid_decision_use to include `{missing}`"
+            );
+        }
+        if stake.requirements != self.stake_requirements {
+            bail!("stake shard requirements differ from manifest stakerequirements");
+        }
+        for (shard, subject) in [
+            ("stake", stake.subject_id.as_str()),
+            ("neurorights", neurorights.subject_id.as_str()),
+            ("donutloop", donutloop.subject_id()),
+        ] {
+            if subject != self.subject_id {
+                bail!(
+                    "coreshard `{shard}` belongs to subject {subject}, manifest subject is {}",
+                    self.subject_id
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails with the shard name and path when a core shard file is missing.
+    /// The evolve stream and donutloop are logs, created on first start.
+    pub fn require_shard_files(&self) -> anyhow::Result<()> {
+        let s = &self.coreshards;
+        for (name, path) in [
+            ("rohmodel", &s.rohmodel),
+            ("stake", &s.stake),
+            ("neurorights", &s.neurorights),
+        ] {
+            if !path.is_file() {
+                bail!("coreshard `{name}` not found at {}", path.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+fn resolve_root(entry: &AlnEntry, base: &Path) -> PathBuf {
+    base.join(&entry.value)
+}
+
+/// Relative shard paths join their root; absolute ones must already lie under it.
+fn resolve_shard(section: &AlnSection, key: &str, root: &Path) -> Result<PathBuf, AlnError> {
+    let entry = section.require_entry(key)?;
+    let path = Path::new(&entry.value);
+    if path.components().any(|c| c == Component::ParentDir) {
+        return Err(entry.invalid("`..` is not allowed in shard paths"));
+    }
+    let resolved = root.join(path);
+    if !resolved.starts_with(root) {
+        return Err(entry.invalid(format!("shard lies outside {}", root.display())));
+    }
+    Ok(resolved)
+}
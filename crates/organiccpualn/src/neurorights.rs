@@ -0,0 +1,240 @@
+use std::fmt;
+use std::path::Path;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::evolvestream::{EvolutionProposalRecord, Scope, TokenKind};
+use crate::rohmodel::RohModelShard;
+
+// Purpose prefixes treated as commercial use or as trading the subject's data.
+const COMMERCIAL_PREFIXES: [&str; 1] = ["commercial"];
+const TRADE_PREFIXES: [&str; 3] = ["trade", "transfer", "sale"];
+
+/// `.neurorights.json` / `.nnet-rights.json` policy document.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NeurorightsPolicyDocument {
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+    pub subject_id: String,
+    pub mental_privacy: bool,
+    pub dream_state_sensitive: bool,
+    pub noncommercial: bool,
+    pub soulnontradeable: bool,
+    pub right_to_forget: RightToForget,
+    pub forbid_decision_use: Vec<String>,
+    pub allowed_uses: Vec<String>,
+    pub data_scope: DataScope,
+    pub logging: LoggingPolicy,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RightToForget {
+    pub enabled: bool,
+    pub forget_sla_hours: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DataScope {
+    pub may_read_neural_patterns: bool,
+    pub may_read_biostate_summaries: bool,
+    pub may_read_dream_metrics: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingPolicy {
+    pub audit_required: bool,
+    pub donutloop_path: String,
+}
+
+/// Clause of the policy document, named after its JSON key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NeurorightsClause {
+    MentalPrivacy,
+    DreamStateSensitive,
+    Noncommercial,
+    Soulnontradeable,
+    RightToForget,
+    ForbidDecisionUse,
+    AllowedUses,
+    DataScope,
+    Logging,
+    RohCeiling,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+#[error("neurorights clause `{clause}` violated: {detail}")]
+pub struct NeurorightsViolation {
+    pub clause: NeurorightsClause,
+    pub detail: String,
+}
+
+impl fmt::Display for NeurorightsClause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NeurorightsClause::MentalPrivacy => "mental_privacy",
+            NeurorightsClause::DreamStateSensitive => "dream_state_sensitive",
+            NeurorightsClause::Noncommercial => "noncommercial",
+            NeurorightsClause::Soulnontradeable => "soulnontradeable",
+            NeurorightsClause::RightToForget => "right_to_forget",
+            NeurorightsClause::ForbidDecisionUse => "forbid_decision_use",
+            NeurorightsClause::AllowedUses => "allowed_uses",
+            NeurorightsClause::DataScope => "data_scope",
+            NeurorightsClause::Logging => "logging",
+            NeurorightsClause::RohCeiling => "roh_ceiling",
+        })
+    }
+}
+
+fn violation(clause: NeurorightsClause, detail: impl Into<String>) -> NeurorightsViolation {
+    NeurorightsViolation {
+        clause,
+        detail: detail.into(),
+    }
+}
+
+fn has_prefix(value: &str, prefixes: &[&str]) -> bool {
+    prefixes.iter().any(|p| value.starts_with(p))
+}
+
+impl NeurorightsPolicyDocument {
+    /// Load a policy document and check its internal consistency.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let doc: Self =
+            serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+        doc.validate()
+            .with_context(|| format!("invalid neurorights policy {}", path.display()))?;
+        Ok(doc)
+    }
+
+    /// Clauses must not contradict each other (e.g. dream-sensitive policies
+    /// cannot grant dream-metric reads, forbidden uses cannot be allowed).
+    pub fn validate(&self) -> Result<(), NeurorightsViolation> {
+        use NeurorightsClause as C;
+        if self.mental_privacy && self.data_scope.may_read_neural_patterns {
+            return Err(violation(
+                C::MentalPrivacy,
+                "data_scope.may_read_neural_patterns must be false",
+            ));
+        }
+        if self.dream_state_sensitive && self.data_scope.may_read_dream_metrics {
+            return Err(violation(
+                C::DreamStateSensitive,
+                "data_scope.may_read_dream_metrics must be false",
+            ));
+        }
+        if self.right_to_forget.enabled && self.right_to_forget.forget_sla_hours == 0 {
+            return Err(violation(C::RightToForget, "forget_sla_hours must be > 0"));
+        }
+        if !self.logging.audit_required {
+            return Err(violation(C::Logging, "audit_required must be true"));
+        }
+        for allowed in &self.allowed_uses {
+            if self.forbid_decision_use.contains(allowed) {
+                return Err(violation(
+                    C::ForbidDecisionUse,
+                    format!("`{allowed}` is both allowed and forbidden"),
+                ));
+            }
+            if self.noncommercial && has_prefix(allowed, &COMMERCIAL_PREFIXES) {
+                return Err(violation(
+                    C::Noncommercial,
+                    format!("allowed use `{allowed}` is commercial"),
+                ));
+            }
+            if self.soulnontradeable && has_prefix(allowed, &TRADE_PREFIXES) {
+                return Err(violation(
+                    C::Soulnontradeable,
+                    format!("allowed use `{allowed}` trades subject data"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check one proposal against every clause; returns the first clause violated.
+    pub fn enforce_for_proposal(
+        &self,
+        proposal: &EvolutionProposalRecord,
+        roh_model: &RohModelShard,
+    ) -> Result<(), NeurorightsViolation> {
+        use NeurorightsClause as C;
+        let ceiling = roh_model.roh_ceiling();
+        if proposal.roh_after > ceiling + f32::EPSILON {
+            return Err(violation(
+                C::RohCeiling,
+                format!("roh_after {} exceeds {}", proposal.roh_after, ceiling),
+            ));
+        }
+        if self.dream_state_sensitive
+            && proposal.scope == Scope::LifeforceAlteration
+            && proposal.tokenkind == TokenKind::Smart
+        {
+            return Err(violation(
+                C::DreamStateSensitive,
+                "lifeforcealteration requires an EVOLVE token",
+            ));
+        }
+
+        // A proposal that declares no purpose cannot be checked against any.
+        if proposal.purposes.is_empty() {
+            return Err(violation(C::AllowedUses, "proposal declares no purpose"));
+        }
+        for purpose in &proposal.purposes {
+            if self.forbid_decision_use.contains(purpose) {
+                return Err(violation(
+                    C::ForbidDecisionUse,
+                    format!("`{purpose}` is a forbidden decision use"),
+                ));
+            }
+            if self.noncommercial && has_prefix(purpose, &COMMERCIAL_PREFIXES) {
+                return Err(violation(
+                    C::Noncommercial,
+                    format!("`{purpose}` is commercial"),
+                ));
+            }
+            if self.soulnontradeable && has_prefix(purpose, &TRADE_PREFIXES) {
+                return Err(violation(
+                    C::Soulnontradeable,
+                    format!("`{purpose}` trades subject data"),
+                ));
+            }
+            if !self.allowed_uses.contains(purpose) {
+                return Err(violation(
+                    C::AllowedUses,
+                    format!("`{purpose}` is not an allowed use"),
+                ));
+            }
+        }
+
+        for data in &proposal.data_access {
+            let scope = &self.data_scope;
+            let denied = match data.as_str() {
+                "neural_patterns" if self.mental_privacy => Some(C::MentalPrivacy),
+                "neural_patterns" => (!scope.may_read_neural_patterns).then_some(C::DataScope),
+                "dream_metrics" if self.dream_state_sensitive => Some(C::DreamStateSensitive),
+                "dream_metrics" => (!scope.may_read_dream_metrics).then_some(C::DataScope),
+                "biostate_summaries" => {
+                    (!scope.may_read_biostate_summaries).then_some(C::DataScope)
+                }
+                _ => Some(C::DataScope),
+            };
+            if let Some(clause) = denied {
+                return Err(violation(
+                    clause,
+                    format!("read of `{data}` is not permitted"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::aln::{AlnDocument, AlnError, AlnErrorKind};
+use crate::evolvestream::UnknownVariant;
+use crate::nnetcap::Capability;
+
+/// How deeply a bound model is integrated into its host module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegrationDepth {
+    Advisory,
+    LowRisk,
+}
+
+impl IntegrationDepth {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IntegrationDepth::Advisory => "advisory",
+            IntegrationDepth::LowRisk => "lowrisk",
+        }
+    }
+}
+
+impl FromStr for IntegrationDepth {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "advisory" => Ok(IntegrationDepth::Advisory),
+            "lowrisk" => Ok(IntegrationDepth::LowRisk),
+            _ => Err(UnknownVariant {
+                kind: "integration depth",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for IntegrationDepth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One `bindings` row: a module bound to a model and its artifacts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetBinding {
+    pub bind_id: String,
+    pub module_id: String,
+    pub model_id: String,
+    pub nnetx_path: PathBuf,
+    pub nnetw_path: PathBuf,
+    pub policy_path: PathBuf,
+    pub cap_path: PathBuf,
+    pub nnet_loop_path: PathBuf,
+    pub roh_ceiling: f32,
+    pub integration_depth: IntegrationDepth,
+    pub scope: String,
+    pub readonly: bool,
+    pub suggest_only: bool,
+}
+
+/// `.nnet-bind.aln`: module-to-model bindings, paths relative to the repo root.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetBindings {
+    pub version: String,
+    pub subject_id: String,
+    pub bindings: Vec<NnetBinding>,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum BindingError {
+    #[error("no binding for module `{module_id}`")]
+    UnknownModule { module_id: String },
+    #[error("binding `{bind_id}` roh_ceiling {roh_ceiling} must be within 0.0..=0.30")]
+    CeilingAboveGlobal { bind_id: String, roh_ceiling: f32 },
+    #[error("binding `{bind_id}` {field} {} does not exist", path.display())]
+    MissingFile {
+        bind_id: String,
+        field: &'static str,
+        path: PathBuf,
+    },
+    #[error("module `{module_id}` is bound readonly and cannot `{capability}`")]
+    Readonly {
+        module_id: String,
+        capability: Capability,
+    },
+    #[error("module `{module_id}` is bound suggest_only and cannot `{capability}`")]
+    SuggestOnly {
+        module_id: String,
+        capability: Capability,
+    },
+}
+
+impl NnetBinding {
+    /// The artifact paths this binding references, by column name.
+    pub fn paths(&self) -> [(&'static str, &Path); 5] {
+        [
+            ("nnetx_path", &self.nnetx_path),
+            ("nnetw_path", &self.nnetw_path),
+            ("policy_path", &self.policy_path),
+            ("cap_path", &self.cap_path),
+            ("nnet_loop_path", &self.nnet_loop_path),
+        ]
+    }
+}
+
+impl NnetBindings {
+    /// Load a `.nnet-bind.aln` and check every binding ceiling.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let doc = AlnDocument::load(path)?;
+        let bindings = Self::from_aln(&doc)?;
+        bindings
+            .validate()
+            .with_context(|| format!("invalid nnet bindings {}", path.display()))?;
+        Ok(bindings)
+    }
+
+    pub fn from_aln(doc: &AlnDocument) -> Result<Self, AlnError> {
+        let meta = doc.require_section("meta")?;
+        let mut bindings: Vec<NnetBinding> = Vec::new();
+        for rec in doc.require_section("bindings")?.records()? {
+            let binding = NnetBinding {
+                bind_id: rec.str("bind_id")?.to_string(),
+                module_id: rec.str("module_id")?.to_string(),
+                model_id: rec.str("model_id")?.to_string(),
+                nnetx_path: rec.str("nnetx_path")?.into(),
+                nnetw_path: rec.str("nnetw_path")?.into(),
+                policy_path: rec.str("policy_path")?.into(),
+                cap_path: rec.str("cap_path")?.into(),
+                nnet_loop_path: rec.str("nnet_loop_path")?.into(),
+                roh_ceiling: rec.parse("roh_ceiling")?,
+                integration_depth: rec.parse("integration_depth")?,
+                scope: rec.str("scope")?.to_string(),
+                readonly: rec.bool("readonly")?,
+                suggest_only: rec.bool("suggest_only")?,
+            };
+            // A module resolves to exactly one binding.
+            if let Some(dup) = bindings
+                .iter()
+                .find(|b| b.bind_id == binding.bind_id || b.module_id == binding.module_id)
+            {
+                let key = if dup.bind_id == binding.bind_id {
+                    binding.bind_id
+                } else {
+                    binding.module_id
+                };
+                return Err(rec.error(AlnErrorKind::DuplicateKey(key)));
+            }
+            bindings.push(binding);
+        }
+        Ok(Self {
+            version: meta.require_entry("version")?.value.clone(),
+            subject_id: meta.require_entry("subject_id")?.value.clone(),
+            bindings,
+        })
+    }
+
+    pub fn validate(&self) -> Result<(), BindingError> {
+        for b in &self.bindings {
+            if !(0.0..=0.30 + f32::EPSILON).contains(&b.roh_ceiling) {
+                return Err(BindingError::CeilingAboveGlobal {
+                    bind_id: b.bind_id.clone(),
+                    roh_ceiling: b.roh_ceiling,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Every referenced artifact must exist under `root`.
+    pub fn check_files(&self, root: &Path) -> Result<(), BindingError> {
+        for b in &self.bindings {
+            for (field, path) in b.paths() {
+                let path = root.join(path);
+                if !path.is_file() {
+                    return Err(BindingError::MissingFile {
+                        bind_id: b.bind_id.clone(),
+                        field,
+                        path,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn for_module(&self, module_id: &str) -> Option<&NnetBinding> {
+        self.bindings.iter().find(|b| b.module_id == module_id)
+    }
+
+    /// Runtime gate: readonly modules may not mutate anything, and
+    /// suggest_only modules may only suggest and read.
+    pub fn check_call(
+        &self,
+        module_id: &str,
+        capability: Capability,
+    ) -> Result<&NnetBinding, BindingError> {
+        let binding = self
+            .for_module(module_id)
+            .ok_or_else(|| BindingError::UnknownModule {
+                module_id: module_id.to_string(),
+            })?;
+        if binding.readonly && capability.is_mutating() {
+            return Err(BindingError::Readonly {
+                module_id: module_id.to_string(),
+                capability,
+            });
+        }
+        if binding.suggest_only && !capability.is_suggest_or_read() {
+            return Err(BindingError::SuggestOnly {
+                module_id: module_id.to_string(),
+                capability,
+            });
+        }
+        Ok(binding)
+    }
+}
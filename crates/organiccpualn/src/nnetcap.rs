@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::aln::{AlnDocument, AlnError, AlnErrorKind};
+use crate::evolvestream::UnknownVariant;
+use crate::nnetfs::NnetfsIndex;
+
+// Guards every capability manifest must mark as required.
+const MANDATORY_GUARDS: [&str; 5] = [
+    "stake_guard_required",
+    "neurorights_guard_required",
+    "roh_guard_required",
+    "token_guard_required",
+    "donutloop_logging_required",
+];
+
+/// Action a model asks to perform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    Suggest,
+    ProposeEvolve,
+    ReadMetrics,
+    EditDocs,
+    EditPolicies,
+    ReadDreamMetrics,
+    WriteConfig,
+    Actuate,
+}
+
+impl Capability {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Capability::Suggest => "suggest",
+            Capability::ProposeEvolve => "propose_evolve",
+            Capability::ReadMetrics => "read_metrics",
+            Capability::EditDocs => "edit_docs",
+            Capability::EditPolicies => "edit_policies",
+            Capability::ReadDreamMetrics => "read_dream_metrics",
+            Capability::WriteConfig => "write_config",
+            Capability::Actuate => "actuate",
+        }
+    }
+
+    /// Applies a change directly rather than proposing one.
+    pub fn is_direct_write(&self) -> bool {
+        matches!(
+            self,
+            Capability::EditPolicies | Capability::WriteConfig | Capability::Actuate
+        )
+    }
+
+    /// Changes state, directly or through docs.
+    pub fn is_mutating(&self) -> bool {
+        self.is_direct_write() || *self == Capability::EditDocs
+    }
+
+    /// Only reads metrics.
+    pub fn is_read(&self) -> bool {
+        matches!(self, Capability::ReadMetrics | Capability::ReadDreamMetrics)
+    }
+
+    /// What `suggest_only` leaves, in a capability manifest or a binding:
+    /// suggesting and reading.
+    pub fn is_suggest_or_read(&self) -> bool {
+        *self == Capability::Suggest || self.is_read()
+    }
+}
+
+impl FromStr for Capability {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "suggest" => Ok(Capability::Suggest),
+            "propose_evolve" => Ok(Capability::ProposeEvolve),
+            "read_metrics" => Ok(Capability::ReadMetrics),
+            "edit_docs" => Ok(Capability::EditDocs),
+            "edit_policies" => Ok(Capability::EditPolicies),
+            "read_dream_metrics" => Ok(Capability::ReadDreamMetrics),
+            "write_config" => Ok(Capability::WriteConfig),
+            "actuate" => Ok(Capability::Actuate),
+            _ => Err(UnknownVariant {
+                kind: "capability",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `capabilities` table of a `.nnet-cap.aln`; every flag must be present.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityFlags {
+    pub suggest_only: bool,
+    pub may_propose_evolve: bool,
+    pub never_actuate: bool,
+    pub may_read_metrics: bool,
+    pub may_edit_docs: bool,
+    pub may_edit_policies: bool,
+    pub may_read_dreammetrics: bool,
+    pub may_write_config: bool,
+    pub may_actuate: bool,
+}
+
+/// `.nnet-cap.aln`: what one model may do, and in which domains.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapabilityManifest {
+    pub version: String,
+    pub model_id: String,
+    pub subject_id: String,
+    pub capabilities: CapabilityFlags,
+    // domain_tag -> max_integration_depth
+    pub domains: BTreeMap<String, u32>,
+    // guard -> required
+    pub guards: BTreeMap<String, bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum CapabilityDenial {
+    #[error("model `{model_id}` has no capability manifest")]
+    UnknownModel { model_id: String },
+    #[error("model `{model_id}` is not declared for domain `{domain}`")]
+    UnknownDomain { model_id: String, domain: String },
+    #[error("model `{model_id}` is not granted `{capability}`")]
+    NotGranted {
+        model_id: String,
+        capability: Capability,
+    },
+    #[error("model `{model_id}` is suggest_only and cannot `{capability}`")]
+    SuggestOnly {
+        model_id: String,
+        capability: Capability,
+    },
+    #[error("model `{model_id}` is never_actuate and cannot `{capability}`")]
+    NeverActuate {
+        model_id: String,
+        capability: Capability,
+    },
+    #[error("model `{model_id}` integration depth {depth} exceeds {max} in domain `{domain}`")]
+    DepthExceeded {
+        model_id: String,
+        domain: String,
+        depth: u32,
+        max: u32,
+    },
+}
+
+impl CapabilityManifest {
+    /// Load a `.nnet-cap.aln` and check that every mandatory guard is required.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let doc = AlnDocument::load(path)?;
+        let caps = Self::from_aln(&doc)?;
+        caps.validate()
+            .with_context(|| format!("invalid capability manifest {}", path.display()))?;
+        Ok(caps)
+    }
+
+    pub fn from_aln(doc: &AlnDocument) -> Result<Self, AlnError> {
+        let meta = doc.require_section("meta")?;
+
+        let section = doc.require_section("capabilities")?;
+        let mut flags = BTreeMap::new();
+        for rec in section.records()? {
+            let name = rec.str("capability")?;
+            if flags.insert(name, rec.bool("enabled")?).is_some() {
+                return Err(rec.error(AlnErrorKind::DuplicateKey(name.to_string())));
+            }
+        }
+        let mut flag = |name: &str| {
+            flags
+                .remove(name)
+                .ok_or_else(|| section.error(AlnErrorKind::MissingKey(name.to_string())))
+        };
+        let capabilities = CapabilityFlags {
+            suggest_only: flag("suggest_only")?,
+            may_propose_evolve: flag("may_propose_evolve")?,
+            never_actuate: flag("never_actuate")?,
+            may_read_metrics: flag("may_read_metrics")?,
+            may_edit_docs: flag("may_edit_docs")?,
+            may_edit_policies: flag("may_edit_policies")?,
+            may_read_dreammetrics: flag("may_read_dreammetrics")?,
+            may_write_config: flag("may_write_config")?,
+            may_actuate: flag("may_actuate")?,
+        };
+        // Unknown flags fail closed rather than being silently ignored.
+        if let Some(name) = flags.keys().next() {
+            return Err(section.error(AlnErrorKind::InvalidValue {
+                field: "capability".into(),
+                value: name.to_string(),
+                reason: "unknown capability flag".into(),
+            }));
+        }
+
+        let mut domains = BTreeMap::new();
+        for rec in doc.require_section("domains")?.records()? {
+            let tag = rec.str("domain_tag")?.to_string();
+            let depth = rec.parse("max_integration_depth")?;
+            if domains.insert(tag.clone(), depth).is_some() {
+                return Err(rec.error(AlnErrorKind::DuplicateKey(tag)));
+            }
+        }
+
+        let mut guards = BTreeMap::new();
+        for rec in doc.require_section("guards")?.records()? {
+            let guard = rec.str("guard")?.to_string();
+            let required = rec.bool("required")?;
+            if guards.insert(guard.clone(), required).is_some() {
+                return Err(rec.error(AlnErrorKind::DuplicateKey(guard)));
+            }
+        }
+
+        Ok(Self {
+            version: meta.require_entry("version")?.value.clone(),
+            model_id: meta.require_entry("model_id")?.value.clone(),
+            subject_id: meta.require_entry("subject_id")?.value.clone(),
+            capabilities,
+            domains,
+            guards,
+        })
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for guard in MANDATORY_GUARDS {
+            if self.guards.get(guard) != Some(&true) {
+                bail!("guard `{guard}` is mandatory and must be required");
+            }
+        }
+        let c = &self.capabilities;
+        let actuating = [
+            ("may_write_config", c.may_write_config),
+            ("may_actuate", c.may_actuate),
+        ];
+        let edits = [
+            ("may_propose_evolve", c.may_propose_evolve),
+            ("may_edit_docs", c.may_edit_docs),
+            ("may_edit_policies", c.may_edit_policies),
+        ];
+        for (name, granted) in edits.into_iter().chain(actuating) {
+            if granted && c.suggest_only {
+                bail!("suggest_only contradicts {name}");
+            }
+        }
+        for (name, granted) in actuating {
+            if granted && c.never_actuate {
+                bail!("never_actuate contradicts {name}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Check one capability in one domain against this manifest.
+    pub fn authorize(&self, capability: Capability, domain: &str) -> Result<(), CapabilityDenial> {
+        let model_id = || self.model_id.clone();
+        if !self.domains.contains_key(domain) {
+            return Err(CapabilityDenial::UnknownDomain {
+                model_id: model_id(),
+                domain: domain.to_string(),
+            });
+        }
+        let c = &self.capabilities;
+        let granted = match capability {
+            Capability::Suggest => true,
+            Capability::ProposeEvolve => c.may_propose_evolve,
+            Capability::ReadMetrics => c.may_read_metrics,
+            Capability::EditDocs => c.may_edit_docs,
+            Capability::EditPolicies => c.may_edit_policies,
+            Capability::ReadDreamMetrics => c.may_read_dreammetrics,
+            Capability::WriteConfig => c.may_write_config,
+            Capability::Actuate => c.may_actuate,
+        };
+        if !granted {
+            return Err(CapabilityDenial::NotGranted {
+                model_id: model_id(),
+                capability,
+            });
+        }
+        if c.never_actuate && matches!(capability, Capability::WriteConfig | Capability::Actuate) {
+            return Err(CapabilityDenial::NeverActuate {
+                model_id: model_id(),
+                capability,
+            });
+        }
+        if c.suggest_only && !capability.is_suggest_or_read() {
+            return Err(CapabilityDenial::SuggestOnly {
+                model_id: model_id(),
+                capability,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn check_integration_depth(
+        &self,
+        domain: &str,
+        depth: u32,
+    ) -> Result<(), CapabilityDenial> {
+        match self.domains.get(domain) {
+            None => Err(CapabilityDenial::UnknownDomain {
+                model_id: self.model_id.clone(),
+                domain: domain.to_string(),
+            }),
+            Some(&max) if depth > max => Err(CapabilityDenial::DepthExceeded {
+                model_id: self.model_id.clone(),
+                domain: domain.to_string(),
+                depth,
+                max,
+            }),
+            Some(_) => Ok(()),
+        }
+    }
+}
+
+/// Capability manifests by model_id; models without one are denied everything.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct CapabilityRegistry {
+    manifests: BTreeMap<String, CapabilityManifest>,
+}
+
+impl CapabilityRegistry {
+    /// Load the cap file of every model in the index. Missing files are left
+    /// out (and so denied); `NnetfsIndex::check_integrity` reports them.
+    pub fn from_index(index: &NnetfsIndex) -> anyhow::Result<Self> {
+        let mut registry = Self::default();
+        for row in &index.caps {
+            let path = index.resolve(&row.path);
+            if !path.is_file() {
+                continue;
+            }
+            let caps = CapabilityManifest::load(path)
+                .with_context(|| format!("loading caps for `{}`", row.model_id))?;
+            if caps.model_id != row.model_id {
+                bail!(
+                    "cap file for `{}` declares model_id `{}`",
+                    row.model_id,
+                    caps.model_id
+                );
+            }
+            registry.insert(caps)?;
+        }
+        Ok(registry)
+    }
+
+    pub fn insert(&mut self, caps: CapabilityManifest) -> anyhow::Result<()> {
+        if self.manifests.contains_key(&caps.model_id) {
+            bail!("duplicate capability manifest for `{}`", caps.model_id);
+        }
+        self.manifests.insert(caps.model_id.clone(), caps);
+        Ok(())
+    }
+
+    pub fn get(&self, model_id: &str) -> Option<&CapabilityManifest> {
+        self.manifests.get(model_id)
+    }
+
+    pub fn authorize(
+        &self,
+        model_id: &str,
+        capability: Capability,
+        domain: &str,
+    ) -> Result<(), CapabilityDenial> {
+        self.get(model_id)
+            .ok_or_else(|| CapabilityDenial::UnknownModel {
+                model_id: model_id.to_string(),
+            })?
+            .authorize(capability, domain)
+    }
+}
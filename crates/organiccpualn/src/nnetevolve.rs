@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use time::Date;
+
+use crate::evolvelog::EvolveRecord;
+use crate::evolvestream::{Decision, Scope, TokenKind, UnknownVariant};
+use crate::hexstamp::{Canonical, Hexstamped};
+use crate::proposalsig::{self, ProposalSignature, Signable};
+
+/// What an `NnetUpdate` changes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NnetScope {
+    Architecture,
+    Hyperparams,
+    TrainingData,
+    Checkpoint,
+}
+
+impl NnetScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NnetScope::Architecture => "architecture",
+            NnetScope::Hyperparams => "hyperparams",
+            NnetScope::TrainingData => "training_data",
+            NnetScope::Checkpoint => "checkpoint",
+        }
+    }
+
+    /// Stake scope whose signer and token rules apply.
+    pub fn stake_scope(&self) -> Scope {
+        match self {
+            NnetScope::Architecture => Scope::ArchChange,
+            _ => Scope::DayToDayTuning,
+        }
+    }
+}
+
+impl FromStr for NnetScope {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "architecture" => Ok(NnetScope::Architecture),
+            "hyperparams" => Ok(NnetScope::Hyperparams),
+            "training_data" => Ok(NnetScope::TrainingData),
+            "checkpoint" => Ok(NnetScope::Checkpoint),
+            _ => Err(UnknownVariant {
+                kind: "nnet scope",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for NnetScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetEffectBounds {
+    pub max_param_delta_per_day: f32,
+    pub max_effect_size_l2: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TrainWindow {
+    pub start: String,
+    pub end: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetDelta {
+    pub arch_hash_before: String,
+    pub arch_hash_after: String,
+    pub params_hash_before: String,
+    pub params_hash_after: String,
+    // L2 norm of this update's parameter change, charged against the daily budget.
+    pub param_delta_l2: f32,
+    pub trainset_id: String,
+    pub train_window_utc: TrainWindow,
+}
+
+/// One line of `nnet-evolve-YYYYMMDD.jsonl` (`kind` = `NnetUpdate`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetUpdateRecord {
+    pub proposal_id: String,
+    pub subject_id: String,
+    pub model_id: String,
+    pub kind: String,
+    pub scope: NnetScope,
+    pub roh_before: f32,
+    pub roh_after: f32,
+    pub effect_bounds: NnetEffectBounds,
+    pub nnet_delta: NnetDelta,
+    pub token_kind: TokenKind,
+    pub decision: Decision,
+    pub hexstamp: String,
+    pub timestamp_utc: String,
+    // Signers' ed25519 signatures over `signing_canonical`, checked against
+    // the stake shard's rule for `scope.stake_scope()`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<ProposalSignature>,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum NnetUpdateError {
+    #[error("record kind `{0}` is not NnetUpdate")]
+    WrongKind(String),
+    #[error("invalid timestamp_utc `{0}`")]
+    BadTimestamp(String),
+    #[error("param_delta_l2 {delta} must be finite and non-negative")]
+    InvalidDelta { delta: f32 },
+    #[error("param_delta_l2 {delta} exceeds max_effect_size_l2 {bound}")]
+    EffectSizeExceeded { delta: f32, bound: f32 },
+    #[error(
+        "model `{model_id}` would reach parameter delta {total} on {day}, above daily budget {budget}"
+    )]
+    DailyBudgetExceeded {
+        model_id: String,
+        day: Date,
+        total: f32,
+        budget: f32,
+    },
+    #[error(
+        "model `{model_id}` params_hash_before `{found}` does not match last accepted `{expected}`"
+    )]
+    ParamsHashMismatch {
+        model_id: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl NnetUpdateRecord {
+    pub const KIND: &'static str = "NnetUpdate";
+
+    /// UTC calendar day of `timestamp_utc`.
+    pub fn utc_day(&self) -> Result<Date, NnetUpdateError> {
+        self.timestamp()
+            .map(|t| t.date())
+            .ok_or_else(|| NnetUpdateError::BadTimestamp(self.timestamp_utc.clone()))
+    }
+
+    /// What signers sign: the update as submitted, without decision, time,
+    /// stamp or the signatures themselves.
+    pub fn signing_canonical(&self) -> Canonical {
+        let d = &self.nnet_delta;
+        Canonical::new("nnet-update-signing", 1)
+            .str("proposal_id", &self.proposal_id)
+            .str("subject_id", &self.subject_id)
+            .str("model_id", &self.model_id)
+            .str("kind", &self.kind)
+            .str("scope", self.scope.as_str())
+            .f32("roh_before", self.roh_before)
+            .f32("roh_after", self.roh_after)
+            .f32(
+                "effect_bounds.max_param_delta_per_day",
+                self.effect_bounds.max_param_delta_per_day,
+            )
+            .f32(
+                "effect_bounds.max_effect_size_l2",
+                self.effect_bounds.max_effect_size_l2,
+            )
+            .str("nnet_delta.arch_hash_before", &d.arch_hash_before)
+            .str("nnet_delta.arch_hash_after", &d.arch_hash_after)
+            .str("nnet_delta.params_hash_before", &d.params_hash_before)
+            .str("nnet_delta.params_hash_after", &d.params_hash_after)
+            .f32("nnet_delta.param_delta_l2", d.param_delta_l2)
+            .str("nnet_delta.trainset_id", &d.trainset_id)
+            .str("nnet_delta.train_window_utc.start", &d.train_window_utc.start)
+            .str("nnet_delta.train_window_utc.end", &d.train_window_utc.end)
+            .str("token_kind", self.token_kind.as_str())
+    }
+
+    /// Add `address`'s signature. Re-stamp before submitting.
+    pub fn sign(&mut self, address: &str, key: &SigningKey) {
+        let signature = proposalsig::sign(self, address, key);
+        self.signatures.push(signature);
+    }
+}
+
+impl Signable for NnetUpdateRecord {
+    fn signing_canonical(&self) -> Canonical {
+        NnetUpdateRecord::signing_canonical(self)
+    }
+
+    fn proposal_id(&self) -> &str {
+        &self.proposal_id
+    }
+
+    fn subject_id(&self) -> &str {
+        &self.subject_id
+    }
+
+    fn signatures(&self) -> &[ProposalSignature] {
+        &self.signatures
+    }
+}
+
+impl Hexstamped for NnetUpdateRecord {
+    /// Signatures are only stamped when present, so unsigned records keep
+    /// the hexstamp they were written with.
+    fn canonical(&self) -> Canonical {
+        let d = &self.nnet_delta;
+        let canonical = Canonical::new("nnet-update", 1)
+            .str("proposal_id", &self.proposal_id)
+            .str("subject_id", &self.subject_id)
+            .str("model_id", &self.model_id)
+            .str("kind", &self.kind)
+            .str("scope", self.scope.as_str())
+            .f32("roh_before", self.roh_before)
+            .f32("roh_after", self.roh_after)
+            .f32(
+                "effect_bounds.max_param_delta_per_day",
+                self.effect_bounds.max_param_delta_per_day,
+            )
+            .f32(
+                "effect_bounds.max_effect_size_l2",
+                self.effect_bounds.max_effect_size_l2,
+            )
+            .str("nnet_delta.arch_hash_before", &d.arch_hash_before)
+            .str("nnet_delta.arch_hash_after", &d.arch_hash_after)
+            .str("nnet_delta.params_hash_before", &d.params_hash_before)
+            .str("nnet_delta.params_hash_after", &d.params_hash_after)
+            .f32("nnet_delta.param_delta_l2", d.param_delta_l2)
+            .str("nnet_delta.trainset_id", &d.trainset_id)
+            .str("nnet_delta.train_window_utc.start", &d.train_window_utc.start)
+            .str("nnet_delta.train_window_utc.end", &d.train_window_utc.end)
+            .str("token_kind", self.token_kind.as_str())
+            .str("decision", self.decision.as_str())
+            .str("timestamp_utc", &self.timestamp_utc);
+        if self.signatures.is_empty() {
+            return canonical;
+        }
+        let signatures: Vec<String> = self
+            .signatures
+            .iter()
+            .map(|s| format!("{} {} {}", s.address, s.public_key, s.signature))
+            .collect();
+        canonical.list("signatures", &signatures)
+    }
+
+    fn hexstamp(&self) -> &str {
+        &self.hexstamp
+    }
+
+    fn set_hexstamp(&mut self, hexstamp: String) {
+        self.hexstamp = hexstamp;
+    }
+}
+
+/// Per-model state of accepted updates: last params hash and the current
+/// UTC day's spent delta.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetModelState {
+    pub params_hash: String,
+    pub day: Date,
+    pub spent: f32,
+    // Tightest budget declared by an accepted update on `day`.
+    pub budget: f32,
+}
+
+/// Tracks accepted `NnetUpdate`s per model so that each UTC day's cumulative
+/// parameter delta stays within budget and params hashes chain.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NnetUpdateTracker {
+    models: BTreeMap<String, NnetModelState>,
+}
+
+impl NnetUpdateTracker {
+    pub fn state(&self, model_id: &str) -> Option<&NnetModelState> {
+        self.models.get(model_id)
+    }
+
+    /// Check `rec` as if accepted on `day`, without recording it. The
+    /// bounds the record declares are clamped to `policy`, the model's
+    /// nnet-policy bounds. A model's daily budget can only tighten within a day.
+    pub fn check(
+        &self,
+        rec: &NnetUpdateRecord,
+        day: Date,
+        policy: &NnetEffectBounds,
+    ) -> Result<(), NnetUpdateError> {
+        if rec.kind != NnetUpdateRecord::KIND {
+            return Err(NnetUpdateError::WrongKind(rec.kind.clone()));
+        }
+        let delta = rec.nnet_delta.param_delta_l2;
+        if !delta.is_finite() || delta < 0.0 {
+            return Err(NnetUpdateError::InvalidDelta { delta });
+        }
+        let bound = rec
+            .effect_bounds
+            .max_effect_size_l2
+            .min(policy.max_effect_size_l2);
+        if delta > bound + f32::EPSILON {
+            return Err(NnetUpdateError::EffectSizeExceeded { delta, bound });
+        }
+
+        let mut spent = 0.0;
+        let mut budget = rec
+            .effect_bounds
+            .max_param_delta_per_day
+            .min(policy.max_param_delta_per_day);
+        if let Some(state) = self.models.get(&rec.model_id) {
+            if state.params_hash != rec.nnet_delta.params_hash_before {
+                return Err(NnetUpdateError::ParamsHashMismatch {
+                    model_id: rec.model_id.clone(),
+                    expected: state.params_hash.clone(),
+                    found: rec.nnet_delta.params_hash_before.clone(),
+                });
+            }
+            if state.day == day {
+                spent = state.spent;
+                budget = budget.min(state.budget);
+            }
+        }
+        let total = spent + delta;
+        if total > budget + f32::EPSILON {
+            return Err(NnetUpdateError::DailyBudgetExceeded {
+                model_id: rec.model_id.clone(),
+                day,
+                total,
+                budget,
+            });
+        }
+        Ok(())
+    }
+
+    /// Record an accepted update; call only after `check` passed.
+    pub fn accept(&mut self, rec: &NnetUpdateRecord, day: Date) {
+        let delta = rec.nnet_delta.param_delta_l2;
+        let budget = rec.effect_bounds.max_param_delta_per_day;
+        let state = self
+            .models
+            .entry(rec.model_id.clone())
+            .or_insert_with(|| NnetModelState {
+                params_hash: String::new(),
+                day,
+                spent: 0.0,
+                budget,
+            });
+        if state.day == day {
+            state.spent += delta;
+            state.budget = state.budget.min(budget);
+        } else {
+            state.day = day;
+            state.spent = delta;
+            state.budget = budget;
+        }
+        state.params_hash = rec.nnet_delta.params_hash_after.clone();
+    }
+
+    /// Rebuild from logged records; only `Allowed` ones count, bucketed by
+    /// their own `timestamp_utc`.
+    pub fn replay<'a>(
+        records: impl IntoIterator<Item = &'a NnetUpdateRecord>,
+    ) -> Result<Self, NnetUpdateError> {
+        let mut tracker = Self::default();
+        tracker.extend(records)?;
+        Ok(tracker)
+    }
+
+    /// Replay records logged after the state this tracker was restored from.
+    pub fn extend<'a>(
+        &mut self,
+        records: impl IntoIterator<Item = &'a NnetUpdateRecord>,
+    ) -> Result<(), NnetUpdateError> {
+        for rec in records
+            .into_iter()
+            .filter(|r| r.decision == Decision::Allowed)
+        {
+            self.accept(rec, rec.utc_day()?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,381 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::aln::{AlnDocument, AlnError, AlnErrorKind, AlnRecord};
+
+/// `.nnetfs-index.aln`: models and their artifacts, paths relative to the repo root.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetfsIndex {
+    pub root: PathBuf,
+    pub version: String,
+    pub repo: String,
+    pub subject_id: String,
+    pub models: Vec<NnetModel>,
+    pub weights: Vec<NnetWeights>,
+    pub policies: Vec<ModelPath>,
+    pub caps: Vec<ModelPath>,
+    pub bindings: Vec<NnetBindingRef>,
+    pub logs: Vec<NnetLogs>,
+    pub proofs: Vec<ModelPath>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetModel {
+    pub model_id: String,
+    pub kind: String,
+    pub path: PathBuf,
+    pub roh_class: String,
+    pub neurorights_class: String,
+    pub nonfinancial: bool,
+    pub notes: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetWeights {
+    pub model_id: String,
+    pub format: String,
+    pub path: PathBuf,
+    pub quantized: bool,
+    pub roh_tag: String,
+}
+
+/// A `model_id, path` row (`policies`, `caps`, `proofs`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModelPath {
+    pub model_id: String,
+    pub path: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetBindingRef {
+    pub bind_id: String,
+    pub path: PathBuf,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetLogs {
+    pub model_id: String,
+    // Glob pattern, e.g. `sovereignty/nnet/nnet-evolve-*.jsonl`.
+    pub nnet_evolve: String,
+    pub nnet_loop: PathBuf,
+}
+
+/// Everything the index records for one model, with paths resolved against the root.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ModelArtifacts {
+    pub model_id: String,
+    pub model: PathBuf,
+    pub weights: Option<PathBuf>,
+    pub roh_tag: Option<String>,
+    pub policy: Option<PathBuf>,
+    pub cap: Option<PathBuf>,
+    pub nnet_evolve: Option<String>,
+    pub nnet_loop: Option<PathBuf>,
+    pub proof: Option<PathBuf>,
+}
+
+/// One problem found by [`NnetfsIndex::check_integrity`].
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum IndexIssue {
+    #[error("model `{model_id}` has no `{section}` entry")]
+    MissingEntry {
+        section: &'static str,
+        model_id: String,
+    },
+    #[error("`{section}` entry refers to unknown model `{model_id}`")]
+    UnknownModel {
+        section: &'static str,
+        model_id: String,
+    },
+    #[error("`{section}` entry `{id}` points at missing file {}", path.display())]
+    MissingFile {
+        section: &'static str,
+        id: String,
+        path: PathBuf,
+    },
+    #[error("log pattern `{pattern}` for model `{model_id}` matches no files")]
+    EmptyGlob { model_id: String, pattern: String },
+    #[error("log pattern `{pattern}` for model `{model_id}` is invalid: {reason}")]
+    InvalidGlob {
+        model_id: String,
+        pattern: String,
+        reason: String,
+    },
+    #[error("weights for `{model_id}` are tagged `{roh_tag}`, active RoH model is `{expected}`")]
+    RohTagMismatch {
+        model_id: String,
+        roh_tag: String,
+        expected: String,
+    },
+}
+
+impl NnetfsIndex {
+    /// Load an index; its directory is the root all paths resolve against.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, AlnError> {
+        let path = path.as_ref();
+        let doc = AlnDocument::load(path)?;
+        let root = path.parent().unwrap_or_else(|| Path::new("."));
+        Self::from_aln(&doc, root)
+    }
+
+    pub fn from_aln(doc: &AlnDocument, root: &Path) -> Result<Self, AlnError> {
+        let meta = doc.require_section("meta")?;
+        let mut keys = Vec::new();
+
+        let mut models = Vec::new();
+        for rec in doc.require_section("models")?.records()? {
+            unique(&rec, &mut keys, "models", "model_id")?;
+            models.push(NnetModel {
+                model_id: rec.str("model_id")?.to_string(),
+                kind: rec.str("kind")?.to_string(),
+                path: rec.str("path")?.into(),
+                roh_class: rec.str("roh_class")?.to_string(),
+                neurorights_class: rec.str("neurorights_class")?.to_string(),
+                nonfinancial: rec.bool("nonfinancial")?,
+                notes: rec.opt_str("notes").map(str::to_string),
+            });
+        }
+
+        let mut weights = Vec::new();
+        for rec in doc.require_section("weights")?.records()? {
+            unique(&rec, &mut keys, "weights", "model_id")?;
+            weights.push(NnetWeights {
+                model_id: rec.str("model_id")?.to_string(),
+                format: rec.str("format")?.to_string(),
+                path: rec.str("path")?.into(),
+                quantized: rec.bool("quantized")?,
+                roh_tag: rec.str("roh_tag")?.to_string(),
+            });
+        }
+
+        let mut bindings = Vec::new();
+        for rec in doc.require_section("bindings")?.records()? {
+            unique(&rec, &mut keys, "bindings", "bind_id")?;
+            bindings.push(NnetBindingRef {
+                bind_id: rec.str("bind_id")?.to_string(),
+                path: rec.str("path")?.into(),
+            });
+        }
+
+        let mut logs = Vec::new();
+        for rec in doc.require_section("logs")?.records()? {
+            unique(&rec, &mut keys, "logs", "model_id")?;
+            logs.push(NnetLogs {
+                model_id: rec.str("model_id")?.to_string(),
+                nnet_evolve: rec.str("nnet_evolve")?.to_string(),
+                nnet_loop: rec.str("nnet_loop")?.into(),
+            });
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            version: meta.require_entry("version")?.value.clone(),
+            repo: meta.require_entry("repo")?.value.clone(),
+            subject_id: meta.require_entry("subject_id")?.value.clone(),
+            models,
+            weights,
+            policies: model_paths(doc, "policies", &mut keys)?,
+            caps: model_paths(doc, "caps", &mut keys)?,
+            bindings,
+            logs,
+            proofs: model_paths(doc, "proofs", &mut keys)?,
+        })
+    }
+
+    pub fn resolve(&self, path: impl AsRef<Path>) -> PathBuf {
+        self.root.join(path)
+    }
+
+    pub fn model(&self, model_id: &str) -> Option<&NnetModel> {
+        self.models.iter().find(|m| m.model_id == model_id)
+    }
+
+    /// Resolved artifact paths for `model_id`, or `None` for an unindexed model.
+    pub fn artifacts(&self, model_id: &str) -> Option<ModelArtifacts> {
+        let model = self.model(model_id)?;
+        let weights = self.weights.iter().find(|w| w.model_id == model_id);
+        let logs = self.logs.iter().find(|l| l.model_id == model_id);
+        let path_for = |rows: &[ModelPath]| {
+            rows.iter()
+                .find(|r| r.model_id == model_id)
+                .map(|r| self.resolve(&r.path))
+        };
+        Some(ModelArtifacts {
+            model_id: model.model_id.clone(),
+            model: self.resolve(&model.path),
+            weights: weights.map(|w| self.resolve(&w.path)),
+            roh_tag: weights.map(|w| w.roh_tag.clone()),
+            policy: path_for(&self.policies),
+            cap: path_for(&self.caps),
+            nnet_evolve: logs.map(|l| self.resolve(&l.nnet_evolve).display().to_string()),
+            nnet_loop: logs.map(|l| self.resolve(&l.nnet_loop)),
+            proof: path_for(&self.proofs),
+        })
+    }
+
+    pub fn binding(&self, bind_id: &str) -> Option<PathBuf> {
+        self.bindings
+            .iter()
+            .find(|b| b.bind_id == bind_id)
+            .map(|b| self.resolve(&b.path))
+    }
+
+    /// Existing `nnet-evolve` files for `model_id`, sorted by path.
+    pub fn evolve_logs(&self, model_id: &str) -> Result<Vec<PathBuf>, glob::PatternError> {
+        let Some(logs) = self.logs.iter().find(|l| l.model_id == model_id) else {
+            return Ok(Vec::new());
+        };
+        let pattern = self.resolve(&logs.nnet_evolve);
+        let mut paths: Vec<PathBuf> = glob::glob(&pattern.to_string_lossy())?
+            .filter_map(Result::ok)
+            .collect();
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Cross-reference check: every model has weights, policy, cap and logs
+    /// rows; no row names an unknown model; every referenced file exists;
+    /// every log glob matches; every `roh_tag` names the active RoH model.
+    /// Proofs are optional per model. Returns all issues, not just the first.
+    pub fn check_integrity(&self, active_roh_model: &str) -> Vec<IndexIssue> {
+        let mut issues = Vec::new();
+        let known = |id: &str| self.model(id).is_some();
+
+        let per_model: [(&'static str, Vec<&str>); 4] = [
+            (
+                "weights",
+                self.weights.iter().map(|w| w.model_id.as_str()).collect(),
+            ),
+            ("policies", ids(&self.policies)),
+            ("caps", ids(&self.caps)),
+            (
+                "logs",
+                self.logs.iter().map(|l| l.model_id.as_str()).collect(),
+            ),
+        ];
+        for model in &self.models {
+            for (section, rows) in &per_model {
+                if !rows.contains(&model.model_id.as_str()) {
+                    issues.push(IndexIssue::MissingEntry {
+                        section,
+                        model_id: model.model_id.clone(),
+                    });
+                }
+            }
+        }
+        for (section, rows) in per_model.into_iter().chain([("proofs", ids(&self.proofs))]) {
+            for id in rows.into_iter().filter(|id| !known(id)) {
+                issues.push(IndexIssue::UnknownModel {
+                    section,
+                    model_id: id.to_string(),
+                });
+            }
+        }
+
+        let mut files: Vec<(&'static str, &str, &Path)> = Vec::new();
+        files.extend(
+            self.models
+                .iter()
+                .map(|m| ("models", m.model_id.as_str(), m.path.as_path())),
+        );
+        files.extend(
+            self.weights
+                .iter()
+                .map(|w| ("weights", w.model_id.as_str(), w.path.as_path())),
+        );
+        for (section, rows) in [
+            ("policies", &self.policies),
+            ("caps", &self.caps),
+            ("proofs", &self.proofs),
+        ] {
+            files.extend(
+                rows.iter()
+                    .map(|r| (section, r.model_id.as_str(), r.path.as_path())),
+            );
+        }
+        files.extend(
+            self.bindings
+                .iter()
+                .map(|b| ("bindings", b.bind_id.as_str(), b.path.as_path())),
+        );
+        files.extend(
+            self.logs
+                .iter()
+                .map(|l| ("logs", l.model_id.as_str(), l.nnet_loop.as_path())),
+        );
+        for (section, id, path) in files {
+            let path = self.resolve(path);
+            if !path.is_file() {
+                issues.push(IndexIssue::MissingFile {
+                    section,
+                    id: id.to_string(),
+                    path,
+                });
+            }
+        }
+
+        for logs in &self.logs {
+            match self.evolve_logs(&logs.model_id) {
+                Ok(found) if found.is_empty() => issues.push(IndexIssue::EmptyGlob {
+                    model_id: logs.model_id.clone(),
+                    pattern: logs.nnet_evolve.clone(),
+                }),
+                Ok(_) => {}
+                Err(e) => issues.push(IndexIssue::InvalidGlob {
+                    model_id: logs.model_id.clone(),
+                    pattern: logs.nnet_evolve.clone(),
+                    reason: e.to_string(),
+                }),
+            }
+        }
+
+        for w in self
+            .weights
+            .iter()
+            .filter(|w| w.roh_tag != active_roh_model)
+        {
+            issues.push(IndexIssue::RohTagMismatch {
+                model_id: w.model_id.clone(),
+                roh_tag: w.roh_tag.clone(),
+                expected: active_roh_model.to_string(),
+            });
+        }
+        issues
+    }
+}
+
+fn ids(rows: &[ModelPath]) -> Vec<&str> {
+    rows.iter().map(|r| r.model_id.as_str()).collect()
+}
+
+fn model_paths(
+    doc: &AlnDocument,
+    section: &'static str,
+    keys: &mut Vec<(&'static str, String)>,
+) -> Result<Vec<ModelPath>, AlnError> {
+    let mut rows = Vec::new();
+    for rec in doc.require_section(section)?.records()? {
+        unique(&rec, keys, section, "model_id")?;
+        rows.push(ModelPath {
+            model_id: rec.str("model_id")?.to_string(),
+            path: rec.str("path")?.into(),
+        });
+    }
+    Ok(rows)
+}
+
+// Each section may list an id at most once.
+fn unique(
+    rec: &AlnRecord<'_>,
+    keys: &mut Vec<(&'static str, String)>,
+    section: &'static str,
+    column: &str,
+) -> Result<(), AlnError> {
+    let key = (section, rec.str(column)?.to_string());
+    if keys.contains(&key) {
+        return Err(rec.error(AlnErrorKind::DuplicateKey(key.1)));
+    }
+    keys.push(key);
+    Ok(())
+}
@@ -0,0 +1,368 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use serde::{Deserialize, Serialize};
+
+use crate::aln::{AlnDocument, AlnError, AlnErrorKind};
+use crate::nnetevolve::NnetEffectBounds;
+use crate::nnetfs::NnetfsIndex;
+use crate::rohmodel::RohModelShard;
+
+/// `.nnet-policy.aln`: a model's share of the global RoH budget, its domain
+/// rules, safety flags and the bounds on its NnetUpdates.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetPolicy {
+    pub version: String,
+    pub model_id: String,
+    pub subject_id: String,
+    // axis -> weight, e.g. language_safety, neurorights, bioscale, dream_boundary.
+    pub roh_slice: BTreeMap<String, f32>,
+    pub domains: BTreeMap<String, DomainRule>,
+    pub safety: NnetSafety,
+    // Caps on what an NnetUpdate may declare; tighter declared bounds win.
+    pub update_bounds: NnetEffectBounds,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DomainRule {
+    pub allowed: bool,
+    pub monotone_only: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NnetSafety {
+    // Global rohmodel shard the slice is budgeted against, repo-relative.
+    pub roh_ceiling_ref: PathBuf,
+    pub enforce_monotone_roh: bool,
+    pub enforce_no_envelope_loosen: bool,
+    pub never_exceeds_global_roh: bool,
+    pub forbid_direct_actuation: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum NnetPolicyViolation {
+    #[error("model `{model_id}` has no nnet policy")]
+    UnknownModel { model_id: String },
+    #[error("nnet policy of `{model_id}` is missing, so the aggregate RoH slice is unbounded")]
+    PolicyMissing { model_id: String },
+    #[error("model `{model_id}` RoH slice {slice} exceeds global ceiling {ceiling}")]
+    SliceExceedsCeiling {
+        model_id: String,
+        slice: f32,
+        ceiling: f32,
+    },
+    #[error("RoH slices of all models sum to {total}, above global ceiling {ceiling}")]
+    AggregateExceedsCeiling { total: f32, ceiling: f32 },
+    #[error("model `{model_id}` RoH slice axis `{axis}` has invalid weight {weight}")]
+    InvalidWeight {
+        model_id: String,
+        axis: String,
+        weight: f32,
+    },
+    #[error("model `{model_id}` safety flag `{flag}` must be true")]
+    SafetyFlagDisabled {
+        model_id: String,
+        flag: &'static str,
+    },
+    #[error("model `{model_id}` update bound `{bound}` must be finite and positive, got {value}")]
+    InvalidUpdateBound {
+        model_id: String,
+        bound: &'static str,
+        value: f32,
+    },
+    #[error("model `{model_id}` has no rule for domain `{domain}`")]
+    UnknownDomain { model_id: String, domain: String },
+    #[error("model `{model_id}` is not allowed in domain `{domain}`")]
+    DomainNotAllowed { model_id: String, domain: String },
+    #[error("model `{model_id}` in domain `{domain}` must not raise RoH ({before} -> {after})")]
+    RohIncrease {
+        model_id: String,
+        domain: String,
+        before: f32,
+        after: f32,
+    },
+}
+
+impl NnetPolicy {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let doc = AlnDocument::load(path)?;
+        let policy = Self::from_aln(&doc)?;
+        policy
+            .validate()
+            .with_context(|| format!("invalid nnet policy {}", path.display()))?;
+        Ok(policy)
+    }
+
+    pub fn from_aln(doc: &AlnDocument) -> Result<Self, AlnError> {
+        let meta = doc.require_section("meta")?;
+
+        let mut roh_slice = BTreeMap::new();
+        for rec in doc.require_section("roh_slice")?.records()? {
+            let axis = rec.str("axis")?.to_string();
+            if roh_slice
+                .insert(axis.clone(), rec.parse("weight")?)
+                .is_some()
+            {
+                return Err(rec.error(AlnErrorKind::DuplicateKey(axis)));
+            }
+        }
+
+        let mut domains = BTreeMap::new();
+        for rec in doc.require_section("domains")?.records()? {
+            let tag = rec.str("domain_tag")?.to_string();
+            let rule = DomainRule {
+                allowed: rec.bool("allowed")?,
+                monotone_only: rec.bool("monotone_only")?,
+            };
+            if domains.insert(tag.clone(), rule).is_some() {
+                return Err(rec.error(AlnErrorKind::DuplicateKey(tag)));
+            }
+        }
+
+        let section = doc.require_section("safety")?;
+        let mut flags = BTreeMap::new();
+        for rec in section.records()? {
+            let name = rec.str("flag")?;
+            if flags.contains_key(name) {
+                return Err(rec.error(AlnErrorKind::DuplicateKey(name.to_string())));
+            }
+            flags.insert(name, rec);
+        }
+        let flag = |name: &str| {
+            flags
+                .get(name)
+                .ok_or_else(|| section.error(AlnErrorKind::MissingKey(name.to_string())))
+        };
+        let safety = NnetSafety {
+            roh_ceiling_ref: flag("roh_ceiling_ref")?.str("value")?.into(),
+            enforce_monotone_roh: flag("enforce_monotone_roh")?.bool("value")?,
+            enforce_no_envelope_loosen: flag("enforce_no_envelope_loosen")?.bool("value")?,
+            never_exceeds_global_roh: flag("never_exceeds_global_roh")?.bool("value")?,
+            forbid_direct_actuation: flag("forbid_direct_actuation")?.bool("value")?,
+        };
+
+        let section = doc.require_section("update_bounds")?;
+        let mut bounds = BTreeMap::new();
+        for rec in section.records()? {
+            let name = rec.str("bound")?;
+            if bounds.insert(name, rec.parse::<f32>("value")?).is_some() {
+                return Err(rec.error(AlnErrorKind::DuplicateKey(name.to_string())));
+            }
+        }
+        let bound = |name: &str| {
+            bounds
+                .get(name)
+                .copied()
+                .ok_or_else(|| section.error(AlnErrorKind::MissingKey(name.to_string())))
+        };
+        let update_bounds = NnetEffectBounds {
+            max_param_delta_per_day: bound("max_param_delta_per_day")?,
+            max_effect_size_l2: bound("max_effect_size_l2")?,
+        };
+
+        Ok(Self {
+            version: meta.require_entry("version")?.value.clone(),
+            model_id: meta.require_entry("model_id")?.value.clone(),
+            subject_id: meta.require_entry("subject_id")?.value.clone(),
+            roh_slice,
+            domains,
+            safety,
+            update_bounds,
+        })
+    }
+
+    /// Weights finite and non-negative; every boolean safety flag set;
+    /// update bounds finite and positive.
+    pub fn validate(&self) -> Result<(), NnetPolicyViolation> {
+        if let Some((axis, &weight)) = self
+            .roh_slice
+            .iter()
+            .find(|(_, w)| !w.is_finite() || **w < 0.0)
+        {
+            return Err(NnetPolicyViolation::InvalidWeight {
+                model_id: self.model_id.clone(),
+                axis: axis.clone(),
+                weight,
+            });
+        }
+        let s = &self.safety;
+        for (flag, value) in [
+            ("enforce_monotone_roh", s.enforce_monotone_roh),
+            ("enforce_no_envelope_loosen", s.enforce_no_envelope_loosen),
+            ("never_exceeds_global_roh", s.never_exceeds_global_roh),
+            ("forbid_direct_actuation", s.forbid_direct_actuation),
+        ] {
+            if !value {
+                return Err(NnetPolicyViolation::SafetyFlagDisabled {
+                    model_id: self.model_id.clone(),
+                    flag,
+                });
+            }
+        }
+        let b = &self.update_bounds;
+        for (bound, value) in [
+            ("max_param_delta_per_day", b.max_param_delta_per_day),
+            ("max_effect_size_l2", b.max_effect_size_l2),
+        ] {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(NnetPolicyViolation::InvalidUpdateBound {
+                    model_id: self.model_id.clone(),
+                    bound,
+                    value,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Total RoH this model may contribute.
+    pub fn slice_total(&self) -> f32 {
+        self.roh_slice.values().sum()
+    }
+
+    /// A proposal from this model in `domain`: the domain must be listed and
+    /// allowed, and RoH must not rise where monotone RoH is enforced.
+    pub fn check_proposal(
+        &self,
+        domain: &str,
+        roh_before: f32,
+        roh_after: f32,
+    ) -> Result<(), NnetPolicyViolation> {
+        let Some(rule) = self.domains.get(domain) else {
+            return Err(NnetPolicyViolation::UnknownDomain {
+                model_id: self.model_id.clone(),
+                domain: domain.to_string(),
+            });
+        };
+        if !rule.allowed {
+            return Err(NnetPolicyViolation::DomainNotAllowed {
+                model_id: self.model_id.clone(),
+                domain: domain.to_string(),
+            });
+        }
+        let monotone = rule.monotone_only || self.safety.enforce_monotone_roh;
+        if monotone && roh_after > roh_before + f32::EPSILON {
+            return Err(NnetPolicyViolation::RohIncrease {
+                model_id: self.model_id.clone(),
+                domain: domain.to_string(),
+                before: roh_before,
+                after: roh_after,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Policies of every model sharing the global RoH budget.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct NnetPolicySet {
+    policies: BTreeMap<String, NnetPolicy>,
+    // Indexed models whose policy file does not exist.
+    #[serde(default)]
+    missing: BTreeSet<String>,
+}
+
+impl NnetPolicySet {
+    /// Load the policy of every model in the index. Models whose file is
+    /// missing are recorded in `missing`; their proposals are rejected and
+    /// `validate` fails until a policy is inserted for them.
+    pub fn from_index(index: &NnetfsIndex) -> anyhow::Result<Self> {
+        let mut set = Self::default();
+        for row in &index.policies {
+            let path = index.resolve(&row.path);
+            if !path.is_file() {
+                set.missing.insert(row.model_id.clone());
+                continue;
+            }
+            let policy = NnetPolicy::load(&path)
+                .with_context(|| format!("loading nnet policy for `{}`", row.model_id))?;
+            if policy.model_id != row.model_id {
+                bail!(
+                    "nnet policy for `{}` declares model_id `{}`",
+                    row.model_id,
+                    policy.model_id
+                );
+            }
+            let ceiling_ref = index.resolve(&policy.safety.roh_ceiling_ref);
+            if !ceiling_ref.is_file() {
+                bail!(
+                    "nnet policy for `{}` references missing rohmodel {}",
+                    row.model_id,
+                    ceiling_ref.display()
+                );
+            }
+            set.insert(policy)?;
+        }
+        Ok(set)
+    }
+
+    pub fn insert(&mut self, policy: NnetPolicy) -> anyhow::Result<()> {
+        if self.policies.contains_key(&policy.model_id) {
+            bail!("duplicate nnet policy for `{}`", policy.model_id);
+        }
+        self.missing.remove(&policy.model_id);
+        self.policies.insert(policy.model_id.clone(), policy);
+        Ok(())
+    }
+
+    pub fn get(&self, model_id: &str) -> Option<&NnetPolicy> {
+        self.policies.get(model_id)
+    }
+
+    pub fn policies(&self) -> impl Iterator<Item = &NnetPolicy> {
+        self.policies.values()
+    }
+
+    /// Indexed models without a policy file.
+    pub fn missing(&self) -> &BTreeSet<String> {
+        &self.missing
+    }
+
+    /// Sum of every model's slice.
+    pub fn aggregate_slice(&self) -> f32 {
+        self.policies.values().map(NnetPolicy::slice_total).sum()
+    }
+
+    /// Each slice, and the sum of all slices, must fit under the global
+    /// ceiling. A missing policy fails the check: its slice is unknown.
+    pub fn validate(&self, roh_model: &RohModelShard) -> Result<(), NnetPolicyViolation> {
+        if let Some(model_id) = self.missing.first() {
+            return Err(NnetPolicyViolation::PolicyMissing {
+                model_id: model_id.clone(),
+            });
+        }
+        let ceiling = roh_model.roh_ceiling();
+        for policy in self.policies.values() {
+            policy.validate()?;
+            let slice = policy.slice_total();
+            if slice > ceiling + f32::EPSILON {
+                return Err(NnetPolicyViolation::SliceExceedsCeiling {
+                    model_id: policy.model_id.clone(),
+                    slice,
+                    ceiling,
+                });
+            }
+        }
+        let total = self.aggregate_slice();
+        if total > ceiling + f32::EPSILON {
+            return Err(NnetPolicyViolation::AggregateExceedsCeiling { total, ceiling });
+        }
+        Ok(())
+    }
+
+    pub fn check_proposal(
+        &self,
+        model_id: &str,
+        domain: &str,
+        roh_before: f32,
+        roh_after: f32,
+    ) -> Result<(), NnetPolicyViolation> {
+        self.get(model_id)
+            .ok_or_else(|| NnetPolicyViolation::UnknownModel {
+                model_id: model_id.to_string(),
+            })?
+            .check_proposal(domain, roh_before, roh_after)
+    }
+}
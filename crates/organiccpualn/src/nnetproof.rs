@@ -0,0 +1,178 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::donutloopledger::DonutloopLedger;
+use crate::hexstamp::Hexstamped;
+use crate::stake::{Role, StakeShard};
+
+/// `.nnet-proof.bchain.json`: chain anchors for a model's artifacts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NnetProof {
+    pub version: String,
+    pub subject_id: String,
+    pub model_id: String,
+    pub chain: String,
+    pub network: String,
+    pub asset_kind: String,
+    pub nonfinancial: bool,
+    pub soulnontradeable: bool,
+    pub anchors: Vec<ProofAnchor>,
+}
+
+/// One anchored artifact: its local hash, the donutloop entry that admitted
+/// it and the transaction that anchored it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProofAnchor {
+    pub anchor_id: String,
+    pub artifact_kind: String,
+    // Repo-relative; `local_hash` is 0x + SHA-256 of the file.
+    pub local_path: PathBuf,
+    pub local_hash: String,
+    pub donut_entry_id: String,
+    pub donut_hexstamp: String,
+    pub tx_hash: String,
+    pub block_height: u64,
+    pub timestamp_utc: String,
+    pub multisig: Vec<String>,
+}
+
+/// One problem found by [`NnetProof::verify`].
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum ProofIssue {
+    #[error("asset flag `{flag}` must be true")]
+    AssetNotProtected { flag: &'static str },
+    #[error("proof subject `{found}` is not the stake subject `{expected}`")]
+    SubjectMismatch { expected: String, found: String },
+    #[error("anchor `{anchor_id}`: artifact {} is missing", path.display())]
+    MissingArtifact { anchor_id: String, path: PathBuf },
+    #[error("anchor `{anchor_id}`: local_hash {expected} does not match artifact ({found})")]
+    ArtifactHashMismatch {
+        anchor_id: String,
+        expected: String,
+        found: String,
+    },
+    #[error("anchor `{anchor_id}`: donutloop entry `{entry_id}` not found")]
+    UnknownDonutEntry { anchor_id: String, entry_id: String },
+    #[error("anchor `{anchor_id}`: donut_hexstamp {expected} does not match entry ({found})")]
+    DonutHexstampMismatch {
+        anchor_id: String,
+        expected: String,
+        found: String,
+    },
+    #[error("anchor `{anchor_id}` has no Host signature")]
+    MissingHostSignature { anchor_id: String },
+    #[error("anchor `{anchor_id}`: signer {address} holds no Host or OrganicCPU role")]
+    UnauthorizedSigner { anchor_id: String, address: String },
+}
+
+impl NnetProof {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    /// Offline check of every anchor against local state: artifact hashes
+    /// under `root`, the donutloop entry each anchor names, and multisig
+    /// signers against the stake shard. `tx_hash` and `block_height` need a
+    /// chain client and are not checked. Returns all issues, not just the first.
+    pub fn verify(
+        &self,
+        root: &Path,
+        donutloop: &DonutloopLedger,
+        stake: &StakeShard,
+    ) -> anyhow::Result<Vec<ProofIssue>> {
+        let mut issues = Vec::new();
+        for (flag, value) in [
+            ("nonfinancial", self.nonfinancial),
+            ("soulnontradeable", self.soulnontradeable),
+        ] {
+            if !value {
+                issues.push(ProofIssue::AssetNotProtected { flag });
+            }
+        }
+        if self.subject_id != stake.subject_id {
+            issues.push(ProofIssue::SubjectMismatch {
+                expected: stake.subject_id.clone(),
+                found: self.subject_id.clone(),
+            });
+        }
+
+        for anchor in &self.anchors {
+            let anchor_id = || anchor.anchor_id.clone();
+
+            let path = root.join(&anchor.local_path);
+            if path.is_file() {
+                let found = file_hash(&path)?;
+                if !found.eq_ignore_ascii_case(&anchor.local_hash) {
+                    issues.push(ProofIssue::ArtifactHashMismatch {
+                        anchor_id: anchor_id(),
+                        expected: anchor.local_hash.clone(),
+                        found,
+                    });
+                }
+            } else {
+                issues.push(ProofIssue::MissingArtifact {
+                    anchor_id: anchor_id(),
+                    path,
+                });
+            }
+
+            match donutloop.entry(&anchor.donut_entry_id)? {
+                None => issues.push(ProofIssue::UnknownDonutEntry {
+                    anchor_id: anchor_id(),
+                    entry_id: anchor.donut_entry_id.clone(),
+                }),
+                // The entry's own stamp must hold too, or an edited entry
+                // could be made to match the proof.
+                Some(entry)
+                    if entry.hexstamp != anchor.donut_hexstamp
+                        || entry.verify_hexstamp().is_err() =>
+                {
+                    issues.push(ProofIssue::DonutHexstampMismatch {
+                        anchor_id: anchor_id(),
+                        expected: anchor.donut_hexstamp.clone(),
+                        found: entry.compute_hexstamp(),
+                    })
+                }
+                Some(_) => {}
+            }
+
+            let mut host_signed = false;
+            for address in &anchor.multisig {
+                let roles = stake.roles_for_address(&self.subject_id, address);
+                host_signed |= roles.contains(&Role::Host);
+                if !roles
+                    .iter()
+                    .any(|r| matches!(r, Role::Host | Role::OrganicCpu))
+                {
+                    issues.push(ProofIssue::UnauthorizedSigner {
+                        anchor_id: anchor_id(),
+                        address: address.clone(),
+                    });
+                }
+            }
+            if !host_signed {
+                issues.push(ProofIssue::MissingHostSignature {
+                    anchor_id: anchor_id(),
+                });
+            }
+        }
+        Ok(issues)
+    }
+}
+
+/// `0x` + lowercase hex SHA-256 of a file's bytes.
+pub fn file_hash(path: &Path) -> anyhow::Result<String> {
+    let mut file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).with_context(|| format!("hashing {}", path.display()))?;
+    Ok(format!("0x{}", hex::encode(hasher.finalize())))
+}
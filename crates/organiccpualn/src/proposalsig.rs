@@ -0,0 +1,123 @@
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+use crate::evolvestream::EvolutionProposalRecord;
+use crate::hexstamp::Canonical;
+
+/// One signer's ed25519 signature over a proposal's signing bytes. The
+/// address is the identity the stake shard binds `public_key` and roles to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalSignature {
+    pub address: String,
+    // Hex ed25519 public key and signature.
+    pub public_key: String,
+    pub signature: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum SignatureError {
+    #[error("signature by {address} is not a hex ed25519 key and signature")]
+    Malformed { address: String },
+    #[error("signature by {address} does not verify over proposal `{proposal_id}`")]
+    Invalid {
+        address: String,
+        proposal_id: String,
+    },
+}
+
+/// A proposal signers sign: evolve records and NnetUpdates.
+pub trait Signable {
+    /// The bytes each signature covers.
+    fn signing_canonical(&self) -> Canonical;
+    fn proposal_id(&self) -> &str;
+    fn subject_id(&self) -> &str;
+    fn signatures(&self) -> &[ProposalSignature];
+}
+
+/// `address`'s signature over `rec`.
+pub fn sign(rec: &impl Signable, address: &str, key: &SigningKey) -> ProposalSignature {
+    let signature = key.sign(rec.signing_canonical().as_str().as_bytes());
+    ProposalSignature {
+        address: address.to_string(),
+        public_key: hex::encode(key.verifying_key().as_bytes()),
+        signature: hex::encode(signature.to_bytes()),
+    }
+}
+
+impl Signable for EvolutionProposalRecord {
+    fn signing_canonical(&self) -> Canonical {
+        EvolutionProposalRecord::signing_canonical(self)
+    }
+
+    fn proposal_id(&self) -> &str {
+        &self.proposalid
+    }
+
+    fn subject_id(&self) -> &str {
+        &self.subjectid
+    }
+
+    fn signatures(&self) -> &[ProposalSignature] {
+        &self.signatures
+    }
+}
+
+impl EvolutionProposalRecord {
+    /// What signers sign: the proposal as submitted. Lifecycle state, time,
+    /// stamps, salt, claimed roles and the signatures themselves are left
+    /// out, so signatures survive each logged step.
+    pub fn signing_canonical(&self) -> Canonical {
+        Canonical::new("evolve-proposal-signing", 1)
+            .str("proposalid", &self.proposalid)
+            .str("subjectid", &self.subjectid)
+            .str("scope", self.scope.as_str())
+            .str("kind", &self.kind)
+            .str("module", &self.module)
+            .str("updatekind", &self.updatekind)
+            .f32(
+                "effectbounds.l2_delta_norm",
+                self.effectbounds.l2_delta_norm,
+            )
+            .bool("effectbounds.irreversible", self.effectbounds.irreversible)
+            .f32("roh_before", self.roh_before)
+            .f32("roh_after", self.roh_after)
+            .str("tsafe_mode", &self.tsafe_mode)
+            .str("tokenkind", self.tokenkind.as_str())
+            .list("purposes", &self.purposes)
+            .list("data_access", &self.data_access)
+    }
+
+    /// Add `address`'s signature. Re-stamp before submitting.
+    pub fn sign(&mut self, address: &str, key: &SigningKey) {
+        let signature = sign(self, address, key);
+        self.signatures.push(signature);
+    }
+}
+
+impl ProposalSignature {
+    /// Check the signature over `rec` against the embedded `public_key`.
+    /// Whether that key belongs to `address` is the stake shard's call.
+    pub fn verify(&self, rec: &impl Signable) -> Result<(), SignatureError> {
+        let malformed = || SignatureError::Malformed {
+            address: self.address.clone(),
+        };
+        let key: [u8; 32] = hex::decode(&self.public_key)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(malformed)?;
+        let signature: [u8; 64] = hex::decode(&self.signature)
+            .ok()
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(malformed)?;
+        VerifyingKey::from_bytes(&key)
+            .map_err(|_| malformed())?
+            .verify(
+                rec.signing_canonical().as_str().as_bytes(),
+                &Signature::from_bytes(&signature),
+            )
+            .map_err(|_| SignatureError::Invalid {
+                address: self.address.clone(),
+                proposal_id: rec.proposal_id().to_string(),
+            })
+    }
+}
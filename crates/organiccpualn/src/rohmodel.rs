@@ -1,5 +1,13 @@
+use std::path::Path;
+
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
+use crate::aln::{AlnDocument, AlnError, AlnErrorKind};
+
+/// The global RoH ceiling every shard must declare.
+pub const ROH_CEILING: f32 = 0.30;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RohWeights {
     pub energy_load: f32,
@@ -12,13 +20,19 @@ pub struct RohWeights {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RohModelCore {
     pub id: String,
+    #[serde(default)]
+    pub version: u32,
     pub weights: RohWeights,
     pub roh_ceiling: f32, // must be 0.30
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RohModelShard {
+    // The single active row.
     pub model: RohModelCore,
+    // Inactive rows kept for history, in file order.
+    #[serde(default)]
+    pub superseded: Vec<RohModelCore>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -46,10 +60,96 @@ impl RohModelShard {
     }
 
     pub fn validate_invariants(&self) -> anyhow::Result<()> {
-        use anyhow::{anyhow, bail};
-        let w = &self.model.weights;
-        if self.model.roh_ceiling != 0.30 {
-            bail!("RoH ceiling must be 0.30");
+        use anyhow::bail;
+        for row in std::iter::once(&self.model).chain(&self.superseded) {
+            row.validate()
+                .with_context(|| format!("RoH model `{}` v{}", row.id, row.version))?;
+        }
+        if let Some(dup) = self
+            .superseded
+            .iter()
+            .find(|r| r.id == self.model.id && r.version == self.model.version)
+        {
+            bail!(
+                "RoH model `{}` v{} is both active and superseded; exactly one active row allowed",
+                dup.id,
+                dup.version
+            );
+        }
+        Ok(())
+    }
+
+    /// Load a `.rohmodel.aln` shard and check its invariants.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let doc = AlnDocument::load(path)?;
+        let shard = Self::from_aln(&doc)?;
+        shard
+            .validate_invariants()
+            .with_context(|| format!("invalid RoH model shard {}", path.display()))?;
+        Ok(shard)
+    }
+
+    /// Build a shard from the `models` section: one row per model version,
+    /// exactly one of which has `active` set.
+    pub fn from_aln(doc: &AlnDocument) -> Result<Self, AlnError> {
+        let section = doc.require_section("models")?;
+        let mut active: Option<RohModelCore> = None;
+        let mut superseded = Vec::new();
+        let mut seen: Vec<(String, u32)> = Vec::new();
+
+        for rec in section.records()? {
+            let row = RohModelCore {
+                id: rec.str("model_id")?.to_string(),
+                version: rec.parse("version")?,
+                weights: RohWeights {
+                    energy_load: rec.parse("energy_load")?,
+                    thermal_load: rec.parse("thermal_load")?,
+                    cognitive_load: rec.parse("cognitive_load")?,
+                    inflammation: rec.parse("inflammation")?,
+                    eco_impact: rec.parse("eco_impact")?,
+                },
+                roh_ceiling: rec.parse("roh_ceiling")?,
+            };
+            let key = (row.id.clone(), row.version);
+            if seen.contains(&key) {
+                return Err(rec.error(AlnErrorKind::DuplicateKey(format!(
+                    "{} v{}",
+                    row.id, row.version
+                ))));
+            }
+            seen.push(key);
+
+            if rec.bool("active")? {
+                if let Some(first) = &active {
+                    return Err(rec.invalid(
+                        "active",
+                        format!("`{}` v{} is already active", first.id, first.version),
+                    ));
+                }
+                active = Some(row);
+            } else {
+                superseded.push(row);
+            }
+        }
+
+        let model = active.ok_or_else(|| {
+            section.error(AlnErrorKind::InvalidValue {
+                field: "active".into(),
+                value: "false".into(),
+                reason: "no RoH model row is active".into(),
+            })
+        })?;
+        Ok(Self { model, superseded })
+    }
+}
+
+impl RohModelCore {
+    fn validate(&self) -> anyhow::Result<()> {
+        use anyhow::bail;
+        let w = &self.weights;
+        if self.roh_ceiling != ROH_CEILING {
+            bail!("RoH ceiling must be {ROH_CEILING}");
         }
         let weights = [
             w.energy_load,
@@ -30,29 +30,31 @@ impl OcpuProfileAln {
 
 // Mapper from Kubernetes OrganicCpuProfile CRD to ALN-bound structs.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct OrganicCpuProfileSpec {
-    pub subjectId: String,
-    pub rohCeiling: f32,
+    pub subject_id: String,
+    pub roh_ceiling: f32,
     pub envelopes: EnvelopesSpec,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EnvelopesSpec {
-    pub fatigueMax: f32,
-    pub dutyCycleMax: f32,
-    pub cognitiveLoadMax: f32,
-    pub lifeforceMin: f32,
+    pub fatigue_max: f32,
+    pub duty_cycle_max: f32,
+    pub cognitive_load_max: f32,
+    pub lifeforce_min: f32,
 }
 
 impl From<OrganicCpuProfileSpec> for OcpuProfileAln {
     fn from(s: OrganicCpuProfileSpec) -> Self {
         Self {
-            subject_id: s.subjectId,
-            roh_ceiling: s.rohCeiling,
-            fatigue_max: s.envelopes.fatigueMax,
-            duty_cycle_max: s.envelopes.dutyCycleMax,
-            cognitive_load_max: s.envelopes.cognitiveLoadMax,
-            lifeforce_min: s.envelopes.lifeforceMin,
+            subject_id: s.subject_id,
+            roh_ceiling: s.roh_ceiling,
+            fatigue_max: s.envelopes.fatigue_max,
+            duty_cycle_max: s.envelopes.duty_cycle_max,
+            cognitive_load_max: s.envelopes.cognitive_load_max,
+            lifeforce_min: s.envelopes.lifeforce_min,
         }
     }
 }
@@ -0,0 +1,453 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use ed25519_dalek::VerifyingKey;
+use serde::{Deserialize, Serialize};
+
+use crate::aln::{AlnDocument, AlnEntry, AlnError, AlnSection};
+use crate::evolvestream::{EvolutionProposalRecord, Scope, TokenKind, UnknownVariant};
+use crate::proposalsig::{ProposalSignature, Signable, SignatureError};
+
+/// Governance role a signer can hold in `.stake.aln`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Role {
+    Host,
+    #[serde(rename = "OrganicCPU")]
+    OrganicCpu,
+    ResearchAgent,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Host => "Host",
+            Role::OrganicCpu => "OrganicCPU",
+            Role::ResearchAgent => "ResearchAgent",
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = UnknownVariant;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Host" => Ok(Role::Host),
+            "OrganicCPU" => Ok(Role::OrganicCpu),
+            "ResearchAgent" => Ok(Role::ResearchAgent),
+            _ => Err(UnknownVariant {
+                kind: "role",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One `roles` row: a role held by an address for a subject.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakeRole {
+    pub subject_id: String,
+    pub role: Role,
+    pub address: String,
+}
+
+/// One `signerkeys` row: the hex ed25519 public key an address signs with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignerKey {
+    pub address: String,
+    pub public_key: String,
+}
+
+impl SignerKey {
+    pub fn new(address: &str, key: &VerifyingKey) -> Self {
+        Self {
+            address: address.to_string(),
+            public_key: hex::encode(key.as_bytes()),
+        }
+    }
+}
+
+/// Signers and tokens required for one scope.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScopeRequirement {
+    pub required_roles: Vec<Role>,
+    pub token_kinds: Vec<TokenKind>,
+}
+
+/// `stakerequirements` block, shared by `.stake.aln` and the workspace manifest.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StakeRequirements {
+    pub lifeforce: ScopeRequirement,
+    pub arch: ScopeRequirement,
+    pub tuning: ScopeRequirement,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StakeShard {
+    pub subject_id: String,
+    pub roles: Vec<StakeRole>,
+    pub requirements: StakeRequirements,
+    #[serde(default)]
+    pub signer_keys: Vec<SignerKey>,
+    /// Hours a proposal may wait for missing signers before it expires.
+    #[serde(default = "default_deferral_ttl")]
+    pub deferral_ttl_hours: u32,
+}
+
+/// Deferral window used when the shard has no `deferral` section.
+pub const DEFAULT_DEFERRAL_TTL_HOURS: u32 = 72;
+
+fn default_deferral_ttl() -> u32 {
+    DEFAULT_DEFERRAL_TTL_HOURS
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum StakeError {
+    #[error(transparent)]
+    Unknown(#[from] UnknownVariant),
+    #[error("scope {scope} is missing required signer roles {missing:?}")]
+    MissingSigners { scope: Scope, missing: Vec<Role> },
+    #[error("token kind {token} is not allowed for scope {scope}")]
+    TokenNotAllowed { scope: Scope, token: TokenKind },
+    #[error("subject {subject_id} must have exactly one Host, found {count}")]
+    HostCount { subject_id: String, count: usize },
+    #[error("scope {scope} requirement is weaker than the {missing} floor")]
+    WeakRequirement { scope: Scope, missing: String },
+    #[error("scope {scope} requires {role}, but no address holds it for subject {subject_id}")]
+    UnheldRole {
+        scope: Scope,
+        role: Role,
+        subject_id: String,
+    },
+    #[error("deferral ttlhours must be > 0")]
+    ZeroDeferralTtl,
+    #[error("signer key of {address} is not a hex ed25519 public key")]
+    BadSignerKey { address: String },
+    #[error("signer key of {address} is bound to an address without a role")]
+    KeyWithoutRole { address: String },
+    #[error("key used by {address} is not bound to it in the stake shard")]
+    UnboundSignerKey { address: String },
+    #[error("signer {address} holds no role for subject {subject_id}")]
+    SignerHasNoRole { subject_id: String, address: String },
+    #[error(transparent)]
+    Signature(#[from] SignatureError),
+}
+
+impl Default for StakeRequirements {
+    fn default() -> Self {
+        Self {
+            lifeforce: ScopeRequirement {
+                required_roles: vec![Role::Host, Role::OrganicCpu],
+                token_kinds: vec![TokenKind::Evolve],
+            },
+            arch: ScopeRequirement {
+                required_roles: vec![Role::Host, Role::OrganicCpu, Role::ResearchAgent],
+                token_kinds: vec![TokenKind::Evolve],
+            },
+            tuning: ScopeRequirement {
+                required_roles: vec![Role::Host],
+                token_kinds: vec![TokenKind::Smart, TokenKind::Evolve],
+            },
+        }
+    }
+}
+
+impl StakeRequirements {
+    pub fn for_scope(&self, scope: Scope) -> &ScopeRequirement {
+        match scope {
+            Scope::LifeforceAlteration => &self.lifeforce,
+            Scope::ArchChange => &self.arch,
+            Scope::DayToDayTuning => &self.tuning,
+        }
+    }
+
+    /// Parse a `stakerequirements` section (`lifeforcescope`, `archscope`,
+    /// `tuningscope` blocks, each with `requiredroles` and `tokenkinds`).
+    pub fn from_aln(section: &AlnSection) -> Result<Self, AlnError> {
+        Ok(Self {
+            lifeforce: ScopeRequirement::from_aln(section.require_entry("lifeforcescope")?)?,
+            arch: ScopeRequirement::from_aln(section.require_entry("archscope")?)?,
+            tuning: ScopeRequirement::from_aln(section.require_entry("tuningscope")?)?,
+        })
+    }
+
+    /// Configuration may add signers, but never drop below: Host on every
+    /// scope, Host+OrganicCPU and EVOLVE-only on lifeforce and arch scopes,
+    /// and ResearchAgent as well on the arch scope.
+    pub fn validate(&self) -> Result<(), StakeError> {
+        for scope in [
+            Scope::LifeforceAlteration,
+            Scope::ArchChange,
+            Scope::DayToDayTuning,
+        ] {
+            let req = self.for_scope(scope);
+            let mut floor = vec![Role::Host];
+            if scope != Scope::DayToDayTuning {
+                floor.push(Role::OrganicCpu);
+            }
+            if scope == Scope::ArchChange {
+                floor.push(Role::ResearchAgent);
+            }
+            if let Some(role) = floor.iter().find(|r| !req.required_roles.contains(r)) {
+                return Err(StakeError::WeakRequirement {
+                    scope,
+                    missing: format!("{role} signer"),
+                });
+            }
+            if req.token_kinds.is_empty() {
+                return Err(StakeError::WeakRequirement {
+                    scope,
+                    missing: "token kind".into(),
+                });
+            }
+            if scope != Scope::DayToDayTuning && req.token_kinds != [TokenKind::Evolve] {
+                return Err(StakeError::WeakRequirement {
+                    scope,
+                    missing: "EVOLVE-only".into(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ScopeRequirement {
+    fn from_aln(block: &AlnEntry) -> Result<Self, AlnError> {
+        let roles = block.require_child("requiredroles")?;
+        let tokens = block.require_child("tokenkinds")?;
+        Ok(Self {
+            required_roles: parse_list(roles)?,
+            token_kinds: parse_list(tokens)?,
+        })
+    }
+}
+
+fn parse_list<T: FromStr<Err = UnknownVariant>>(entry: &AlnEntry) -> Result<Vec<T>, AlnError> {
+    entry
+        .list()
+        .iter()
+        .map(|v| v.parse().map_err(|e| entry.invalid(e)))
+        .collect()
+}
+
+impl StakeShard {
+    /// Load a `.stake.aln` shard and check its invariants.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let doc = AlnDocument::load(path)?;
+        let shard = Self::from_aln(&doc)?;
+        shard
+            .validate()
+            .with_context(|| format!("invalid stake shard {}", path.display()))?;
+        Ok(shard)
+    }
+
+    /// Build from `meta` (`subject_id`), `roles` rows, an optional
+    /// `stakerequirements` section (defaults to the workspace baseline), an
+    /// optional `deferral` section (`ttlhours`, default 72) and optional
+    /// `signerkeys` rows (`address`, `public_key`).
+    pub fn from_aln(doc: &AlnDocument) -> Result<Self, AlnError> {
+        let subject_id = doc
+            .require_section("meta")?
+            .require_entry("subject_id")?
+            .value
+            .clone();
+        let mut roles = Vec::new();
+        for rec in doc.require_section("roles")?.records()? {
+            roles.push(StakeRole {
+                subject_id: rec.str("subject_id")?.to_string(),
+                role: rec.parse("role")?,
+                address: rec.str("address")?.to_string(),
+            });
+        }
+        let requirements = match doc.section("stakerequirements") {
+            Some(section) => StakeRequirements::from_aln(section)?,
+            None => StakeRequirements::default(),
+        };
+        let mut signer_keys = Vec::new();
+        if let Some(section) = doc.section("signerkeys") {
+            for rec in section.records()? {
+                signer_keys.push(SignerKey {
+                    address: rec.str("address")?.to_string(),
+                    public_key: rec.str("public_key")?.to_string(),
+                });
+            }
+        }
+        let deferral_ttl_hours = match doc.section("deferral") {
+            Some(section) => section.require_entry("ttlhours")?.parse()?,
+            None => DEFAULT_DEFERRAL_TTL_HOURS,
+        };
+        Ok(Self {
+            subject_id,
+            roles,
+            requirements,
+            signer_keys,
+            deferral_ttl_hours,
+        })
+    }
+
+    /// Exactly one Host per subject (including the shard's own subject),
+    /// scope requirements no weaker than the baseline and each held by an
+    /// address, a non-zero deferral window, and signer keys that parse and
+    /// belong to addresses with a role.
+    pub fn validate(&self) -> Result<(), StakeError> {
+        let mut hosts: BTreeMap<&str, usize> = BTreeMap::new();
+        hosts.insert(&self.subject_id, 0);
+        for r in &self.roles {
+            let count = hosts.entry(&r.subject_id).or_default();
+            if r.role == Role::Host {
+                *count += 1;
+            }
+        }
+        if let Some((subject_id, count)) = hosts.into_iter().find(|(_, n)| *n != 1) {
+            return Err(StakeError::HostCount {
+                subject_id: subject_id.to_string(),
+                count,
+            });
+        }
+        if self.deferral_ttl_hours == 0 {
+            return Err(StakeError::ZeroDeferralTtl);
+        }
+        for key in &self.signer_keys {
+            let address = || key.address.clone();
+            let bytes: [u8; 32] = hex::decode(&key.public_key)
+                .ok()
+                .and_then(|b| b.try_into().ok())
+                .ok_or_else(|| StakeError::BadSignerKey { address: address() })?;
+            VerifyingKey::from_bytes(&bytes)
+                .map_err(|_| StakeError::BadSignerKey { address: address() })?;
+            if !self
+                .roles
+                .iter()
+                .any(|r| r.address.eq_ignore_ascii_case(&key.address))
+            {
+                return Err(StakeError::KeyWithoutRole { address: address() });
+            }
+        }
+        self.requirements.validate()?;
+        // A role nobody holds would leave its scope without a reachable quorum.
+        for scope in [
+            Scope::LifeforceAlteration,
+            Scope::ArchChange,
+            Scope::DayToDayTuning,
+        ] {
+            for &role in &self.requirements.for_scope(scope).required_roles {
+                if !self
+                    .roles
+                    .iter()
+                    .any(|r| r.subject_id == self.subject_id && r.role == role)
+                {
+                    return Err(StakeError::UnheldRole {
+                        scope,
+                        role,
+                        subject_id: self.subject_id.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Roles `address` holds for `subject_id`. Addresses compare
+    /// case-insensitively, so EIP-55 checksummed spellings match.
+    pub fn roles_for_address(&self, subject_id: &str, address: &str) -> Vec<Role> {
+        self.roles
+            .iter()
+            .filter(|r| r.subject_id == subject_id && r.address.eq_ignore_ascii_case(address))
+            .map(|r| r.role)
+            .collect()
+    }
+
+    /// Roles `signature` proves on `rec`: its key must be bound to its
+    /// address here, it must verify, and the address must hold a role for
+    /// the record's subject.
+    pub fn signer_roles(
+        &self,
+        rec: &impl Signable,
+        signature: &ProposalSignature,
+    ) -> Result<Vec<Role>, StakeError> {
+        let bound = self.signer_keys.iter().any(|k| {
+            k.address.eq_ignore_ascii_case(&signature.address)
+                && k.public_key.eq_ignore_ascii_case(&signature.public_key)
+        });
+        if !bound {
+            return Err(StakeError::UnboundSignerKey {
+                address: signature.address.clone(),
+            });
+        }
+        signature.verify(rec)?;
+        let roles = self.roles_for_address(rec.subject_id(), &signature.address);
+        if roles.is_empty() {
+            return Err(StakeError::SignerHasNoRole {
+                subject_id: rec.subject_id().to_string(),
+                address: signature.address.clone(),
+            });
+        }
+        Ok(roles)
+    }
+
+    /// Roles proven by the record's signatures that verify, sorted; the
+    /// rest prove nothing.
+    pub fn verified_roles(&self, rec: &impl Signable) -> Vec<Role> {
+        let mut roles: Vec<Role> = rec
+            .signatures()
+            .iter()
+            .filter_map(|sig| self.signer_roles(rec, sig).ok())
+            .flatten()
+            .collect();
+        roles.sort();
+        roles.dedup();
+        roles
+    }
+
+    /// Check that the signers' roles, as proven by `verified_roles`, cover
+    /// every role required for `scope`.
+    pub fn check_signers_for_scope(
+        &self,
+        scope: Scope,
+        signers: &[Role],
+    ) -> Result<(), StakeError> {
+        let missing: Vec<Role> = self
+            .requirements
+            .for_scope(scope)
+            .required_roles
+            .iter()
+            .filter(|r| !signers.contains(r))
+            .copied()
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(StakeError::MissingSigners { scope, missing })
+        }
+    }
+
+    pub fn check_token_for_scope(&self, scope: Scope, token: TokenKind) -> Result<(), StakeError> {
+        if self
+            .requirements
+            .for_scope(scope)
+            .token_kinds
+            .contains(&token)
+        {
+            Ok(())
+        } else {
+            Err(StakeError::TokenNotAllowed { scope, token })
+        }
+    }
+}
+
+impl EvolutionProposalRecord {
+    /// Token guard: the proposal's token kind must be allowed for its scope.
+    pub fn enforce_token_scopes(&self, stake: &StakeShard) -> Result<(), StakeError> {
+        stake.check_token_for_scope(self.scope, self.tokenkind)
+    }
+}
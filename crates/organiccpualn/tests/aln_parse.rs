@@ -0,0 +1,87 @@
+use organiccpualn::aln::{AlnDocument, AlnErrorKind};
+
+const MANIFEST: &str = "\
+meta
+  manifestid neuro-workspace-v1
+  description NeuroPC/hexen unified shell manifest.
+stakerequirements
+  lifeforcescope
+    requiredroles Host,OrganicCPU
+    tokenkinds EVOLVE
+  tuningscope
+    requiredroles Host
+    tokenkinds SMART,EVOLVE
+guardpipeline
+  order stakeguard,rohguard
+";
+
+const CAPS: &str = "\
+meta
+  model_id bostrom-chat-gov-v1
+
+capabilities
+  # capability,          enabled, comment
+  suggest_only,          true,   \"may produce text suggestions only\"
+  may_edit_policies,     false,  \"no direct .aln/.json policy edits, ever\"
+";
+
+#[test]
+fn parses_nested_entries_and_lists() {
+    let doc = AlnDocument::parse("manifest.aln", MANIFEST).unwrap();
+    let meta = doc.require_section("meta").unwrap();
+    assert_eq!(
+        meta.require_entry("description").unwrap().value,
+        "NeuroPC/hexen unified shell manifest."
+    );
+
+    let stake = doc.require_section("stakerequirements").unwrap();
+    let tuning = stake.require_entry("tuningscope").unwrap();
+    assert_eq!(
+        tuning.require_child("tokenkinds").unwrap().list(),
+        ["SMART", "EVOLVE"]
+    );
+    assert_eq!(stake.entries.len(), 2);
+
+    let order = doc
+        .require_section("guardpipeline")
+        .unwrap()
+        .require_entry("order")
+        .unwrap();
+    assert_eq!(order.list(), ["stakeguard", "rohguard"]);
+}
+
+#[test]
+fn parses_rows_by_header_with_quoted_commas() {
+    let doc = AlnDocument::parse("caps.aln", CAPS).unwrap();
+    let caps = doc.require_section("capabilities").unwrap();
+    let rows: Vec<_> = caps.records().unwrap().collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1].str("capability").unwrap(), "may_edit_policies");
+    assert!(!rows[1].bool("enabled").unwrap());
+    assert_eq!(
+        rows[1].str("comment").unwrap(),
+        "no direct .aln/.json policy edits, ever"
+    );
+
+    let err = rows[0].parse::<f32>("capability").unwrap_err();
+    assert_eq!((err.line, err.column), (6, 3));
+}
+
+#[test]
+fn reports_file_line_and_column() {
+    let text = "rows\n  # a, b\n  x, \"unterminated\n";
+    let err = AlnDocument::parse("bad.aln", text).unwrap_err();
+    assert!(matches!(err.kind, AlnErrorKind::UnterminatedQuote));
+    assert_eq!((err.line, err.column), (3, 6));
+    assert!(err.to_string().starts_with("bad.aln:3:6:"));
+
+    let text = "rows\n  # a, b\n  x, y, z\n";
+    let err = AlnDocument::parse("bad.aln", text).unwrap_err();
+    assert!(matches!(
+        err.kind,
+        AlnErrorKind::ColumnCount {
+            expected: 2,
+            found: 3
+        }
+    ));
+}
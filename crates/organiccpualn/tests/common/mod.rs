@@ -0,0 +1,77 @@
+//! Fixtures shared by the organiccpualn integration tests.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use organiccpualn::donutloopledger::DonutloopEntry;
+use organiccpualn::evolvestream::{
+    Decision, EffectBounds, EvolutionProposalRecord, Scope, TokenKind, EVOLVE_SCHEMA_VERSION,
+};
+use organiccpualn::hexstamp::Hexstamped;
+
+pub const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+pub const ORGANIC_CPU: &str = "0x519fC0eB4111323Cac44b70e1aE31c30e405802D";
+pub const RESEARCH_AGENT: &str = "0x8f72a93fb287a0f96b69c6f3f86e1e44134177ec";
+
+pub fn repo(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(path)
+}
+
+/// A fresh, empty directory for one test.
+pub fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("organiccpualn-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// An allowed tuning proposal for `subject`, stamped.
+pub fn record(id: &str, subject: &str) -> EvolutionProposalRecord {
+    let mut rec = EvolutionProposalRecord {
+        schema_version: EVOLVE_SCHEMA_VERSION,
+        proposalid: id.into(),
+        subjectid: subject.into(),
+        scope: Scope::DayToDayTuning,
+        kind: "UpdateProposal".into(),
+        module: "hud".into(),
+        updatekind: "tuning".into(),
+        effectbounds: EffectBounds {
+            l2_delta_norm: 0.01,
+            irreversible: false,
+        },
+        roh_before: 0.2,
+        roh_after: 0.1,
+        tsafe_mode: "viability-kernel".into(),
+        signer_roles: vec!["Host".into()],
+        tokenkind: TokenKind::Smart,
+        decision: Decision::Allowed,
+        hexstamp: String::new(),
+        timestamp_utc: "2026-02-04T02:00:00Z".into(),
+        purposes: vec!["calibration".into()],
+        data_access: vec!["eeg".into()],
+        payload_salt: String::new(),
+        payload_commitment: String::new(),
+        signatures: Vec::new(),
+        forgotten_utc: None,
+    };
+    rec.stamp();
+    rec
+}
+
+/// An unlinked donutloop entry; `append` fills in the stamps.
+pub fn entry(id: &str, roh_before: f32, roh_after: f32) -> DonutloopEntry {
+    DonutloopEntry {
+        entry_id: id.into(),
+        proposal_id: format!("prop-{id}"),
+        subject_id: "subject".into(),
+        decision: "Allowed".into(),
+        roh_before,
+        roh_after,
+        prev_hexstamp: String::new(),
+        hexstamp: String::new(),
+        policy_refs: vec!["rohmodel:roh-model-rohv1".into()],
+        timestamp_utc: "2026-02-04T02:00:00Z".into(),
+    }
+}
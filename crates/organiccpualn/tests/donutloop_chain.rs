@@ -0,0 +1,152 @@
+mod common;
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use organiccpualn::donutloopledger::{ChainError, ChainErrorKind, DonutloopLedger};
+
+#[test]
+fn appends_survive_reopen_and_torn_tail_is_repaired() {
+    let path = common::scratch("torn").join("donutloopledger.aln");
+    let mut ledger = DonutloopLedger::create(&path, "subject").unwrap();
+    ledger.append(common::entry("e1", 0.20, 0.18)).unwrap();
+    ledger.append(common::entry("e2", 0.18, 0.17)).unwrap();
+
+    let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+    f.write_all(b"  e3, prop-e3, subj").unwrap();
+    drop(f);
+
+    let reopened = DonutloopLedger::open(&path).unwrap();
+    assert_eq!(reopened.len(), 2);
+    assert_eq!(reopened.repaired_bytes(), 19);
+    reopened.validate_chain().unwrap();
+    assert_eq!(reopened.head_hexstamp(), ledger.head_hexstamp());
+}
+
+#[test]
+fn rejects_roh_increase_and_reports_edited_hexstamp_index() {
+    let path = common::scratch("broken").join("donutloopledger.aln");
+    let mut ledger = DonutloopLedger::create(&path, "subject").unwrap();
+    ledger.append(common::entry("e1", 0.20, 0.18)).unwrap();
+    assert!(ledger.append(common::entry("e2", 0.18, 0.19)).is_err());
+    ledger.append(common::entry("e2", 0.18, 0.17)).unwrap();
+    ledger.append(common::entry("e3", 0.17, 0.16)).unwrap();
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    let e2 = ledger.entries()[1].hexstamp.clone();
+    std::fs::write(
+        &path,
+        text.replacen(&format!("{e2}, rohmodel"), "0xdead, rohmodel", 1),
+    )
+    .unwrap();
+
+    let err = DonutloopLedger::open(&path)
+        .unwrap()
+        .validate_chain()
+        .unwrap_err();
+    assert_eq!(err.index, 1);
+    assert!(matches!(err.kind, ChainErrorKind::HexstampMismatch { .. }));
+}
+
+#[test]
+fn removed_or_reordered_rows_report_the_broken_link_index() {
+    let path = common::scratch("relinked").join("donutloopledger.aln");
+    let mut ledger = DonutloopLedger::create(&path, "subject").unwrap();
+    for (id, before, after) in [
+        ("e1", 0.20, 0.18),
+        ("e2", 0.18, 0.17),
+        ("e3", 0.17, 0.16),
+        ("e4", 0.16, 0.16),
+    ] {
+        ledger.append(common::entry(id, before, after)).unwrap();
+    }
+    let stamps: Vec<String> = ledger
+        .entries()
+        .iter()
+        .map(|e| e.hexstamp.clone())
+        .collect();
+    let text = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    let row = |id: &str| {
+        lines
+            .iter()
+            .position(|l| l.trim_start().starts_with(&format!("{id}, ")))
+            .unwrap()
+    };
+    let broken_link = |rows: Vec<&str>| {
+        std::fs::write(&path, rows.join("\n") + "\n").unwrap();
+        let err = DonutloopLedger::open(&path)
+            .unwrap()
+            .validate_chain()
+            .unwrap_err();
+        match err.kind {
+            ChainErrorKind::BrokenLink { expected, found } => (err.index, expected, found),
+            other => panic!("expected BrokenLink, got {other:?}"),
+        }
+    };
+
+    // e2 removed: e3, now at index 1, still points at e2.
+    let mut removed = lines.clone();
+    removed.remove(row("e2"));
+    assert_eq!(
+        broken_link(removed),
+        (1, stamps[0].clone(), stamps[1].clone())
+    );
+
+    // e3 and e4 swapped: e4, now at index 2, points at e3.
+    let mut reordered = lines.clone();
+    reordered.swap(row("e3"), row("e4"));
+    assert_eq!(
+        broken_link(reordered),
+        (2, stamps[1].clone(), stamps[2].clone())
+    );
+}
+
+#[test]
+fn resume_reads_only_rows_after_the_mark() {
+    let path = common::scratch("resume").join("donutloopledger.aln");
+    let mut ledger = DonutloopLedger::create(&path, "subject").unwrap();
+    ledger.append(common::entry("e1", 0.20, 0.18)).unwrap();
+    ledger.append(common::entry("e2", 0.18, 0.17)).unwrap();
+    let mark = ledger.mark().unwrap();
+    ledger.append(common::entry("e3", 0.17, 0.16)).unwrap();
+
+    let mut resumed = DonutloopLedger::resume(&path, &mark).unwrap();
+    assert_eq!(resumed.len(), 3);
+    assert_eq!(resumed.entries().len(), 2);
+    assert_eq!(resumed.head_hexstamp(), ledger.head_hexstamp());
+    resumed.validate_chain().unwrap();
+    assert!(resumed.entry("e1").unwrap().is_some());
+    let err = resumed
+        .append(common::entry("e1", 0.16, 0.15))
+        .unwrap_err()
+        .downcast::<ChainError>()
+        .unwrap();
+    assert_eq!(err.kind, ChainErrorKind::DuplicateEntryId);
+
+    // The marked head was rewritten: the mark no longer holds.
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, text.replacen("0.17, 0x", "0.16, 0x", 1)).unwrap();
+    assert!(DonutloopLedger::resume(&path, &mark).is_err());
+}
+
+#[test]
+fn resume_rejects_an_edited_prefix() {
+    let path = common::scratch("resume-prefix").join("donutloopledger.aln");
+    let mut ledger = DonutloopLedger::create(&path, "subject").unwrap();
+    ledger.append(common::entry("e1", 0.20, 0.18)).unwrap();
+    ledger.append(common::entry("e2", 0.18, 0.17)).unwrap();
+    let mark = ledger.mark().unwrap();
+    assert_eq!(mark.prefix_ids.iter().collect::<Vec<_>>(), ["e1"]);
+
+    // Rewrite e1 in place; the head row and its offset are untouched.
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, text.replacen("0.2, 0.18", "0.2, 0.19", 1)).unwrap();
+    assert_ne!(std::fs::read_to_string(&path).unwrap(), text);
+    let err = DonutloopLedger::resume(&path, &mark).unwrap_err();
+    assert!(err.to_string().contains("changed"), "{err}");
+    assert!(DonutloopLedger::open(&path)
+        .unwrap()
+        .validate_chain()
+        .is_err());
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use organiccpualn::evolvelog::{LogIssue, MergedEvolveLog, RotatingEvolveLog};
+use organiccpualn::evolvestream::EvolutionProposalRecord;
+use organiccpualn::hexstamp::Hexstamped;
+use time::{Date, Month};
+
+fn at(id: &str, timestamp_utc: &str) -> EvolutionProposalRecord {
+    let mut rec = common::record(id, common::SUBJECT);
+    rec.timestamp_utc = timestamp_utc.into();
+    rec.stamp();
+    rec
+}
+
+fn day(day: u8) -> Date {
+    Date::from_calendar_date(2026, Month::February, day).unwrap()
+}
+
+#[test]
+fn records_go_to_their_day_and_merge_in_time_order() {
+    let log = RotatingEvolveLog::new(common::scratch("rotation-order"), "evolve");
+    let late = log.append(&at("p2", "2026-02-02T09:00:00Z")).unwrap();
+    let early = log.append(&at("p1", "2026-02-01T23:59:59Z")).unwrap();
+    log.append(&at("p3", "2026-02-02T08:00:00Z")).unwrap();
+    assert_eq!(late, log.path_for(day(2)));
+    assert_eq!(early, log.path_for(day(1)));
+
+    let merged = log.read::<EvolutionProposalRecord>().unwrap();
+    assert!(merged.issues.is_empty(), "{:?}", merged.issues);
+    let ids: Vec<_> = merged.into_records().map(|r| r.proposalid).collect();
+    assert_eq!(ids, ["p1", "p3", "p2"]);
+
+    // Unstamped records never reach a log.
+    let mut unstamped = at("p4", "2026-02-02T10:00:00Z");
+    unstamped.hexstamp.clear();
+    assert!(log.append(&unstamped).is_err());
+}
+
+#[test]
+fn gaps_and_duplicates_are_reported() {
+    let dir = common::scratch("rotation-issues");
+    let log = RotatingEvolveLog::new(&dir, "evolve");
+    log.append(&at("p1", "2026-02-01T10:00:00Z")).unwrap();
+    log.append(&at("p2", "2026-02-04T10:00:00Z")).unwrap();
+    log.append(&at("p1", "2026-02-04T11:00:00Z")).unwrap();
+
+    let merged = log.read::<EvolutionProposalRecord>().unwrap();
+    let first = log.path_for(day(1));
+    let last = log.path_for(day(4));
+    assert_eq!(
+        merged.issues,
+        [
+            LogIssue::MissingDay { day: day(2) },
+            LogIssue::MissingDay { day: day(3) },
+            LogIssue::DuplicateProposal {
+                proposal_id: "p1".into(),
+                first,
+                first_line: 1,
+                path: last,
+                line: 2,
+            },
+        ]
+    );
+    assert_eq!(merged.records.len(), 3);
+}
+
+#[test]
+fn misfiled_and_undated_files_are_reported() {
+    let dir = common::scratch("rotation-misfiled");
+    let log = RotatingEvolveLog::new(&dir, "evolve");
+    let file = log.path_for(day(1));
+    let rec = at("p1", "2026-02-02T10:00:00Z");
+    std::fs::write(&file, serde_json::to_string(&rec).unwrap() + "\n").unwrap();
+    let undated = dir.join("evolve-latest.jsonl");
+    std::fs::write(&undated, "").unwrap();
+
+    let merged = MergedEvolveLog::<EvolutionProposalRecord>::read(&dir).unwrap();
+    assert_eq!(
+        merged.issues,
+        [
+            LogIssue::UndatedFile { path: undated },
+            LogIssue::Misfiled {
+                path: file,
+                line: 1
+            },
+        ]
+    );
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use std::fs::OpenOptions;
+use std::io::{BufReader, Write};
+
+use organiccpualn::evolvestream::{EvolutionProposalRecord, JsonlEvolutionLog, SchemaError};
+
+fn line(id: &str) -> String {
+    serde_json::to_string(&common::record(id, "subject")).unwrap()
+}
+
+#[test]
+fn bad_lines_are_reported_or_skipped() {
+    let text = format!("{}\n{{broken\n\n{}\n", line("p1"), line("p2"));
+    let log = JsonlEvolutionLog;
+
+    let strict: Vec<_> = log.records(text.as_bytes()).collect();
+    assert_eq!(strict.len(), 3);
+    assert_eq!(strict[1].as_ref().unwrap_err().line, 2);
+    assert_eq!(strict[2].as_ref().unwrap().0, 4);
+
+    let mut lenient = log.records(text.as_bytes()).lenient();
+    let ids: Vec<String> = lenient.by_ref().map(|r| r.unwrap().1.proposalid).collect();
+    assert_eq!(ids, ["p1", "p2"]);
+    assert_eq!(lenient.skipped(), 1);
+}
+
+#[test]
+fn follow_waits_for_complete_lines() {
+    let path = common::scratch("follow").join("proposals.evolve.jsonl");
+    std::fs::write(&path, format!("{}\n", line("p1"))).unwrap();
+    let mut follow = JsonlEvolutionLog.follow(&path).unwrap();
+    assert!(follow.poll().is_none());
+
+    let next = line("p2");
+    let (head, tail) = next.split_at(20);
+    let mut f = OpenOptions::new().append(true).open(&path).unwrap();
+    f.write_all(head.as_bytes()).unwrap();
+    assert!(follow.poll().is_none());
+    writeln!(f, "{tail}").unwrap();
+
+    let (n, rec) = follow.poll().unwrap().unwrap();
+    assert_eq!((n, rec.proposalid.as_str()), (2, "p2"));
+
+    let reread: Vec<_> = JsonlEvolutionLog
+        .records(BufReader::new(std::fs::File::open(&path).unwrap()))
+        .collect();
+    assert_eq!(reread.len(), 2);
+}
+
+#[test]
+fn edited_records_fail_hexstamp_check() {
+    let good = line("p1");
+    let edited = good.replace("\"roh_after\":0.1", "\"roh_after\":0.05");
+    assert_ne!(good, edited);
+
+    assert!(EvolutionProposalRecord::from_json(&good).is_ok());
+    let text = format!("{edited}\n");
+    let err = JsonlEvolutionLog
+        .records(text.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert!(err.message.contains("does not match"), "{}", err.message);
+}
+
+#[test]
+fn dropping_schema_version_does_not_skip_the_check() {
+    let edited = line("p1").replace("\"roh_after\":0.1", "\"roh_after\":0.05");
+    let mut value: serde_json::Value = serde_json::from_str(&edited).unwrap();
+    value.as_object_mut().unwrap().remove("schema_version");
+    let unversioned = value.to_string();
+    assert!(matches!(
+        EvolutionProposalRecord::from_legacy_json(&unversioned),
+        Err(SchemaError::UnversionedStamp { field: "hexstamp" })
+    ));
+
+    // Stripped of every stamp, it passes as v0 only to the legacy importer.
+    let rec = value.as_object_mut().unwrap();
+    for field in ["hexstamp", "payload_commitment", "payload_salt", "signatures"] {
+        rec.remove(field);
+    }
+    let stripped = value.to_string();
+    assert!(matches!(
+        EvolutionProposalRecord::from_json(&stripped),
+        Err(SchemaError::Unstamped { found: 0 })
+    ));
+    let err = JsonlEvolutionLog
+        .records(format!("{stripped}\n").as_bytes())
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert!(err.message.contains("no hexstamp"), "{}", err.message);
+
+    let v0 = EvolutionProposalRecord::from_legacy_json(&stripped).unwrap();
+    assert_eq!((v0.schema_version, v0.roh_after), (1, 0.05));
+    assert!(v0.hexstamp.is_empty());
+}
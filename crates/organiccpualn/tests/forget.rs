@@ -0,0 +1,137 @@
+mod common;
+
+use ed25519_dalek::SigningKey;
+use organiccpualn::evolvestream::EvolutionProposalRecord;
+use organiccpualn::forget::{self, ForgetLog, ForgetReceipt};
+use organiccpualn::hexstamp::Hexstamped;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+#[test]
+fn forgotten_records_keep_verifiable_stamps() {
+    let path = common::scratch("redact").join("proposals.evolve.jsonl");
+    let records = [
+        common::record("p1", "alice"),
+        common::record("p2", "bob"),
+        common::record("p3", "alice"),
+    ];
+    let text: String = records
+        .iter()
+        .map(|r| serde_json::to_string(r).unwrap() + "\n")
+        .collect();
+    std::fs::write(&path, text).unwrap();
+
+    let forgotten = forget::redact_evolve_log(&path, "alice", "2026-02-05T00:00:00Z").unwrap();
+    let ids: Vec<_> = forgotten.iter().map(|f| f.proposal_id.as_str()).collect();
+    assert_eq!(ids, ["p1", "p3"]);
+
+    let after: Vec<_> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|l| EvolutionProposalRecord::from_json(l).unwrap())
+        .collect();
+    for (rec, orig) in after.iter().zip(&records) {
+        rec.verify_hexstamp().unwrap();
+        assert_eq!(rec.hexstamp, orig.hexstamp);
+        assert_eq!(rec.payload_commitment, orig.payload_commitment);
+    }
+    assert!(after[0].is_forgotten() && after[0].module.is_empty());
+    assert!(!after[1].is_forgotten() && after[1].module == "hud");
+    assert!(after[0].payload_salt.is_empty() && !records[0].payload_salt.is_empty());
+
+    // Putting payload back into a forgotten record breaks it.
+    let mut restored = after[0].clone();
+    restored.module = "hud".into();
+    assert!(restored.verify_hexstamp().is_err());
+}
+
+#[test]
+fn forgetting_a_v1_record_restamps_it_and_keeps_the_old_stamp() {
+    let path = common::scratch("redact-v1").join("proposals.evolve.jsonl");
+    let mut v1 = common::record("p1", "alice");
+    v1.schema_version = 1;
+    v1.payload_commitment.clear();
+    v1.payload_salt.clear();
+    v1.stamp();
+    v1.verify_hexstamp().unwrap();
+    let v2 = common::record("p2", "alice");
+    let text = format!(
+        "{}\n{}\n",
+        serde_json::to_string(&v1).unwrap(),
+        serde_json::to_string(&v2).unwrap()
+    );
+    std::fs::write(&path, text).unwrap();
+
+    let forgotten = forget::redact_evolve_log(&path, "alice", "2026-02-05T00:00:00Z").unwrap();
+    assert_eq!(
+        forgotten[0].restamped_from.as_deref(),
+        Some(v1.hexstamp.as_str())
+    );
+    assert_eq!(forgotten[1].restamped_from, None);
+
+    // The record verifies under its new v2 stamp, which the receipt names.
+    let line = std::fs::read_to_string(&path).unwrap();
+    let after = EvolutionProposalRecord::from_json(line.lines().next().unwrap()).unwrap();
+    after.verify_hexstamp().unwrap();
+    assert_eq!(after.schema_version, 2);
+    assert_ne!(after.hexstamp, v1.hexstamp);
+    assert_eq!(after.hexstamp, forgotten[0].hexstamp);
+
+    // The v1 stamp stays bound to it only through the signed receipt.
+    let mut receipt = ForgetReceipt {
+        request_id: "forget-1".into(),
+        subject_id: "alice".into(),
+        requested_utc: "2026-02-04T00:00:00Z".into(),
+        completed_utc: "2026-02-05T00:00:00Z".into(),
+        records: forgotten,
+        donutloop_head: String::new(),
+        not_covered: Vec::new(),
+        public_key: String::new(),
+        signature: String::new(),
+    };
+    receipt.sign(&SigningKey::from_bytes(&[7; 32]));
+    receipt.verify_signature().unwrap();
+    let mut forged = receipt.clone();
+    forged.records[0].restamped_from = Some(v2.hexstamp.clone());
+    assert!(forged.verify_signature().is_err());
+}
+
+#[test]
+fn receipts_are_signed_and_sla_is_swept() {
+    let log = ForgetLog::new(common::scratch("log").join("forget.jsonl"));
+    let late = log.request("alice", "2026-02-01T00:00:00Z".into()).unwrap();
+    let open = log.request("alice", "2026-02-04T00:00:00Z".into()).unwrap();
+    assert_eq!(late.request_id, "forget-1");
+
+    let mut receipt = ForgetReceipt {
+        request_id: late.request_id.clone(),
+        subject_id: late.subject_id.clone(),
+        requested_utc: late.requested_utc.clone(),
+        completed_utc: "2026-02-03T00:00:00Z".into(),
+        records: Vec::new(),
+        donutloop_head: String::new(),
+        not_covered: Vec::new(),
+        public_key: String::new(),
+        signature: String::new(),
+    };
+    receipt.sign(&SigningKey::from_bytes(&[7; 32]));
+    receipt.verify_signature().unwrap();
+
+    let mut forged = receipt.clone();
+    forged.completed_utc = "2026-02-01T01:00:00Z".into();
+    assert!(forged.verify_signature().is_err());
+    assert!(log.complete(&forged).is_err());
+
+    log.complete(&receipt).unwrap();
+    assert!(log.complete(&receipt).is_err());
+
+    let now = OffsetDateTime::parse("2026-02-06T00:00:00Z", &Rfc3339).unwrap();
+    let overdue = log.sweep(now, 24).unwrap();
+    assert_eq!(overdue.len(), 2);
+    assert_eq!(
+        overdue[0].completed_utc.as_deref(),
+        Some("2026-02-03T00:00:00Z")
+    );
+    assert_eq!(overdue[1].request, open);
+    assert!(log.sweep(now, 72).unwrap().is_empty());
+}
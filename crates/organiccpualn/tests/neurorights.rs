@@ -0,0 +1,144 @@
+mod common;
+
+use organiccpualn::evolvestream::{EvolutionProposalRecord, Scope, TokenKind};
+use organiccpualn::neurorights::{NeurorightsClause as C, NeurorightsPolicyDocument};
+use organiccpualn::rohmodel::RohModelShard;
+
+fn policy() -> NeurorightsPolicyDocument {
+    NeurorightsPolicyDocument::load(common::repo(
+        "policies/bostrom-neurorights-v1.neurorights.json",
+    ))
+    .unwrap()
+}
+
+fn roh_model() -> RohModelShard {
+    RohModelShard::load(common::repo(
+        "qpudatashards/particles/bostrom-rohmodel-v1.rohmodel.aln",
+    ))
+    .unwrap()
+}
+
+/// The clause `rec` violates under the repo policy, if any.
+fn violated(rec: &EvolutionProposalRecord) -> Option<C> {
+    policy()
+        .enforce_for_proposal(rec, &roh_model())
+        .err()
+        .map(|v| v.clause)
+}
+
+/// A proposal declaring one allowed purpose and no data reads.
+fn plain() -> EvolutionProposalRecord {
+    let mut rec = common::record("p1", common::SUBJECT);
+    rec.purposes = vec!["personal_assistance".into()];
+    rec.data_access.clear();
+    rec
+}
+
+fn with_purpose(purpose: &str) -> EvolutionProposalRecord {
+    let mut rec = plain();
+    rec.purposes = vec![purpose.into()];
+    rec
+}
+
+fn with_read(data: &str) -> EvolutionProposalRecord {
+    let mut rec = plain();
+    rec.data_access = vec![data.into()];
+    rec
+}
+
+#[test]
+fn proposals_are_held_to_every_clause() {
+    assert_eq!(violated(&with_purpose("personal_assistance")), None);
+    assert_eq!(violated(&with_read("biostate_summaries")), None);
+
+    assert_eq!(violated(&plain()), None);
+    let mut rec = plain();
+    rec.purposes.clear();
+    assert_eq!(violated(&rec), Some(C::AllowedUses));
+    let mut rec = plain();
+    rec.roh_after = 0.35;
+    assert_eq!(violated(&rec), Some(C::RohCeiling));
+
+    let mut rec = plain();
+    rec.scope = Scope::LifeforceAlteration;
+    rec.tokenkind = TokenKind::Smart;
+    assert_eq!(violated(&rec), Some(C::DreamStateSensitive));
+    assert_eq!(
+        violated(&with_read("dream_metrics")),
+        Some(C::DreamStateSensitive)
+    );
+
+    assert_eq!(
+        violated(&with_purpose("credit")),
+        Some(C::ForbidDecisionUse)
+    );
+    assert_eq!(
+        violated(&with_purpose("commercial_ads")),
+        Some(C::Noncommercial)
+    );
+    assert_eq!(
+        violated(&with_purpose("trade_profile")),
+        Some(C::Soulnontradeable)
+    );
+    assert_eq!(violated(&with_purpose("marketing")), Some(C::AllowedUses));
+    assert_eq!(
+        violated(&with_read("neural_patterns")),
+        Some(C::MentalPrivacy)
+    );
+    assert_eq!(violated(&with_read("location")), Some(C::DataScope));
+}
+
+#[test]
+fn data_scope_binds_without_the_privacy_clauses() {
+    let mut policy = policy();
+    policy.mental_privacy = false;
+    policy.dream_state_sensitive = false;
+    policy.data_scope.may_read_biostate_summaries = false;
+    let roh_model = roh_model();
+    for data in ["neural_patterns", "dream_metrics", "biostate_summaries"] {
+        let err = policy
+            .enforce_for_proposal(&with_read(data), &roh_model)
+            .unwrap_err();
+        assert_eq!(err.clause, C::DataScope, "{data}");
+    }
+    policy.data_scope.may_read_dream_metrics = true;
+    policy
+        .enforce_for_proposal(&with_read("dream_metrics"), &roh_model)
+        .unwrap();
+}
+
+type Edit = fn(&mut NeurorightsPolicyDocument);
+
+#[test]
+fn contradictory_policies_are_rejected() {
+    policy().validate().unwrap();
+    let cases: [(Edit, C); 7] = [
+        (
+            |p| p.data_scope.may_read_neural_patterns = true,
+            C::MentalPrivacy,
+        ),
+        (
+            |p| p.data_scope.may_read_dream_metrics = true,
+            C::DreamStateSensitive,
+        ),
+        (|p| p.right_to_forget.forget_sla_hours = 0, C::RightToForget),
+        (|p| p.logging.audit_required = false, C::Logging),
+        (
+            |p| p.allowed_uses.push("credit".into()),
+            C::ForbidDecisionUse,
+        ),
+        (
+            |p| p.allowed_uses.push("commercial_ads".into()),
+            C::Noncommercial,
+        ),
+        (
+            |p| p.allowed_uses.push("sale_of_data".into()),
+            C::Soulnontradeable,
+        ),
+    ];
+    for (edit, clause) in cases {
+        let mut policy = policy();
+        edit(&mut policy);
+        assert_eq!(policy.validate().unwrap_err().clause, clause);
+    }
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use organiccpualn::nnetbind::{BindingError, NnetBindings};
+use organiccpualn::nnetcap::Capability;
+
+fn repo_bindings() -> NnetBindings {
+    NnetBindings::load(common::repo("policies/nnet-bindings-v1.nnet-bind.aln")).unwrap()
+}
+
+const ALL: [Capability; 8] = [
+    Capability::Suggest,
+    Capability::ProposeEvolve,
+    Capability::ReadMetrics,
+    Capability::EditDocs,
+    Capability::EditPolicies,
+    Capability::ReadDreamMetrics,
+    Capability::WriteConfig,
+    Capability::Actuate,
+];
+
+/// The capabilities `module` may call, in `ALL` order.
+fn allowed(bindings: &NnetBindings, module: &str) -> Vec<Capability> {
+    ALL.into_iter()
+        .filter(|c| bindings.check_call(module, *c).is_ok())
+        .collect()
+}
+
+#[test]
+fn readonly_refuses_every_mutating_capability() {
+    let mut bindings = repo_bindings();
+    let rank = bindings.for_module("organiccpu-qsearch").unwrap();
+    assert!(rank.readonly && !rank.suggest_only);
+    assert_eq!(
+        allowed(&bindings, "organiccpu-qsearch"),
+        [
+            Capability::Suggest,
+            Capability::ProposeEvolve,
+            Capability::ReadMetrics,
+            Capability::ReadDreamMetrics,
+        ]
+    );
+    assert_eq!(
+        bindings
+            .check_call("organiccpu-qsearch", Capability::EditDocs)
+            .unwrap_err(),
+        BindingError::Readonly {
+            module_id: "organiccpu-qsearch".into(),
+            capability: Capability::EditDocs,
+        }
+    );
+
+    bindings.bindings[1].readonly = false;
+    assert_eq!(allowed(&bindings, "organiccpu-qsearch"), ALL);
+}
+
+#[test]
+fn suggest_only_allows_suggest_and_reads() {
+    let mut bindings = repo_bindings();
+    bindings.bindings[0].readonly = false;
+    assert!(bindings.bindings[0].suggest_only);
+    assert_eq!(
+        allowed(&bindings, "neuropc-assistant-chat"),
+        [
+            Capability::Suggest,
+            Capability::ReadMetrics,
+            Capability::ReadDreamMetrics,
+        ]
+    );
+    for capability in [Capability::ProposeEvolve, Capability::EditDocs] {
+        assert_eq!(
+            bindings
+                .check_call("neuropc-assistant-chat", capability)
+                .unwrap_err(),
+            BindingError::SuggestOnly {
+                module_id: "neuropc-assistant-chat".into(),
+                capability,
+            }
+        );
+    }
+
+    assert_eq!(
+        bindings
+            .check_call("unbound", Capability::Suggest)
+            .unwrap_err(),
+        BindingError::UnknownModule {
+            module_id: "unbound".into()
+        }
+    );
+}
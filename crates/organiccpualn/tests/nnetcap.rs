@@ -0,0 +1,179 @@
+mod common;
+
+use organiccpualn::nnetcap::{
+    Capability, CapabilityDenial, CapabilityFlags, CapabilityManifest, CapabilityRegistry,
+};
+
+const MODEL: &str = "bostrom-chat-gov-v1";
+
+fn chat_gov() -> CapabilityManifest {
+    CapabilityManifest::load(common::repo("policies/bostrom-chat-gov-v1.nnet-cap.aln")).unwrap()
+}
+
+fn registry(caps: CapabilityManifest) -> CapabilityRegistry {
+    let mut registry = CapabilityRegistry::default();
+    registry.insert(caps).unwrap();
+    registry
+}
+
+#[test]
+fn repo_caps_grant_suggestions_and_proposals_only() {
+    let registry = registry(chat_gov());
+    let domain = "language_cowriter";
+    for capability in [
+        Capability::Suggest,
+        Capability::ProposeEvolve,
+        Capability::ReadMetrics,
+        Capability::EditDocs,
+    ] {
+        assert_eq!(registry.authorize(MODEL, capability, domain), Ok(()));
+    }
+    for capability in [
+        Capability::EditPolicies,
+        Capability::ReadDreamMetrics,
+        Capability::WriteConfig,
+        Capability::Actuate,
+    ] {
+        assert_eq!(
+            registry.authorize(MODEL, capability, domain),
+            Err(CapabilityDenial::NotGranted {
+                model_id: MODEL.into(),
+                capability,
+            })
+        );
+    }
+}
+
+#[test]
+fn unknown_models_and_domains_are_denied() {
+    let registry = registry(chat_gov());
+    assert_eq!(
+        registry.authorize(
+            "bostrom-rank-qpolicy-v1",
+            Capability::Suggest,
+            "language_cowriter"
+        ),
+        Err(CapabilityDenial::UnknownModel {
+            model_id: "bostrom-rank-qpolicy-v1".into(),
+        })
+    );
+    assert_eq!(
+        registry.authorize(MODEL, Capability::Suggest, "dream_observer"),
+        Err(CapabilityDenial::UnknownDomain {
+            model_id: MODEL.into(),
+            domain: "dream_observer".into(),
+        })
+    );
+}
+
+#[test]
+fn direct_writes_need_an_explicit_grant() {
+    let mut caps = chat_gov();
+    caps.capabilities.never_actuate = false;
+    caps.validate().unwrap();
+    assert_eq!(
+        caps.authorize(Capability::WriteConfig, "system_refactor"),
+        Err(CapabilityDenial::NotGranted {
+            model_id: MODEL.into(),
+            capability: Capability::WriteConfig,
+        })
+    );
+    caps.capabilities.may_write_config = true;
+    caps.validate().unwrap();
+    assert_eq!(
+        caps.authorize(Capability::WriteConfig, "system_refactor"),
+        Ok(())
+    );
+
+    caps.capabilities.never_actuate = true;
+    assert_eq!(
+        caps.authorize(Capability::WriteConfig, "system_refactor"),
+        Err(CapabilityDenial::NeverActuate {
+            model_id: MODEL.into(),
+            capability: Capability::WriteConfig,
+        })
+    );
+}
+
+#[test]
+fn suggest_only_allows_only_suggest_and_reads() {
+    let mut caps = chat_gov();
+    caps.capabilities = CapabilityFlags {
+        suggest_only: true,
+        may_propose_evolve: true,
+        never_actuate: false,
+        may_read_metrics: true,
+        may_edit_docs: true,
+        may_edit_policies: true,
+        may_read_dreammetrics: true,
+        may_write_config: true,
+        may_actuate: true,
+    };
+    // Every grant is overridden, leaving what a suggest_only binding allows.
+    let domain = "language_cowriter";
+    for capability in [
+        Capability::Suggest,
+        Capability::ProposeEvolve,
+        Capability::ReadMetrics,
+        Capability::EditDocs,
+        Capability::EditPolicies,
+        Capability::ReadDreamMetrics,
+        Capability::WriteConfig,
+        Capability::Actuate,
+    ] {
+        let expected = if capability.is_suggest_or_read() {
+            Ok(())
+        } else {
+            Err(CapabilityDenial::SuggestOnly {
+                model_id: MODEL.into(),
+                capability,
+            })
+        };
+        assert_eq!(caps.authorize(capability, domain), expected);
+    }
+}
+
+#[test]
+fn integration_depth_is_capped_per_domain() {
+    let caps = chat_gov();
+    assert_eq!(caps.check_integration_depth("system_refactor", 1), Ok(()));
+    assert_eq!(
+        caps.check_integration_depth("system_refactor", 2),
+        Err(CapabilityDenial::DepthExceeded {
+            model_id: MODEL.into(),
+            domain: "system_refactor".into(),
+            depth: 2,
+            max: 1,
+        })
+    );
+    assert_eq!(
+        caps.check_integration_depth("payments", 0),
+        Err(CapabilityDenial::UnknownDomain {
+            model_id: MODEL.into(),
+            domain: "payments".into(),
+        })
+    );
+}
+
+#[test]
+fn mandatory_guards_and_contradictions_are_rejected() {
+    let mut caps = chat_gov();
+    caps.guards.insert("roh_guard_required".into(), false);
+    assert!(caps.validate().is_err());
+
+    for (edit, flag) in [
+        (
+            (|c| c.suggest_only = true) as fn(&mut CapabilityFlags),
+            "suggest_only contradicts may_propose_evolve",
+        ),
+        (
+            |c| c.may_actuate = true,
+            "never_actuate contradicts may_actuate",
+        ),
+    ] {
+        let mut caps = chat_gov();
+        edit(&mut caps.capabilities);
+        let err = caps.validate().unwrap_err();
+        assert!(err.to_string().contains(flag), "{err}");
+    }
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use std::path::Path;
+
+use organiccpualn::nnetfs::{IndexIssue, NnetfsIndex};
+
+const ROH: &str = "roh-model-rohv1";
+
+const INDEX: &str = "\
+meta
+  version 1.0.0
+  repo    test
+  subject_id bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7
+
+models
+  # model_id, kind,   path,          roh_class, neurorights_class, nonfinancial, notes
+  m1,         ranker, models/m1.nnetx, low,     generic,           true,         -
+
+weights
+  # model_id, format, path,             quantized, roh_tag
+  m1,         int8,   weights/m1.nnetq, true,      roh-model-rohv1
+
+policies
+  # model_id, path
+  m1,         policies/m1.nnet-policy.aln
+
+caps
+  # model_id, path
+  m1,         policies/m1.nnet-cap.aln
+
+bindings
+  # bind_id, path
+  m1-bind,   policies/bind.nnet-bind.aln
+
+logs
+  # model_id, nnet_evolve,               nnet_loop
+  m1,         nnet/nnet-evolve-*.jsonl,  nnet/nnet-loop.aln
+
+proofs
+  # model_id, path
+";
+
+const FILES: [&str; 7] = [
+    "models/m1.nnetx",
+    "weights/m1.nnetq",
+    "policies/m1.nnet-policy.aln",
+    "policies/m1.nnet-cap.aln",
+    "policies/bind.nnet-bind.aln",
+    "nnet/nnet-loop.aln",
+    "nnet/nnet-evolve-20260204.jsonl",
+];
+
+/// An index in a fresh directory with every file it names, after `from`
+/// is replaced by `to` in its text.
+fn index(name: &str, from: &str, to: &str) -> NnetfsIndex {
+    let dir = common::scratch(name);
+    for file in FILES {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "").unwrap();
+    }
+    assert!(INDEX.contains(from), "index has no {from:?}");
+    let path = dir.join(".nnetfs-index.aln");
+    std::fs::write(&path, INDEX.replacen(from, to, 1)).unwrap();
+    NnetfsIndex::load(path).unwrap()
+}
+
+#[test]
+fn complete_index_has_no_issues() {
+    let index = index("nnetfs-ok", "", "");
+    assert_eq!(index.check_integrity(ROH), []);
+    let artifacts = index.artifacts("m1").unwrap();
+    assert_eq!(artifacts.roh_tag.as_deref(), Some(ROH));
+    assert_eq!(index.evolve_logs("m1").unwrap().len(), 1);
+}
+
+#[test]
+fn dangling_references_are_reported() {
+    let index = index(
+        "nnetfs-dangling",
+        "m1,         policies/m1.nnet-cap.aln",
+        "m2,         policies/m2.nnet-cap.aln",
+    );
+    let issues = index.check_integrity(ROH);
+    assert!(issues.contains(&IndexIssue::MissingEntry {
+        section: "caps",
+        model_id: "m1".into(),
+    }));
+    assert!(issues.contains(&IndexIssue::UnknownModel {
+        section: "caps",
+        model_id: "m2".into(),
+    }));
+    assert!(issues.contains(&IndexIssue::MissingFile {
+        section: "caps",
+        id: "m2".into(),
+        path: index.resolve("policies/m2.nnet-cap.aln"),
+    }));
+    assert_eq!(issues.len(), 3, "{issues:?}");
+}
+
+#[test]
+fn empty_log_globs_are_reported() {
+    let index = index("nnetfs-glob", "nnet/nnet-evolve-*", "nnet/other-*");
+    assert_eq!(
+        index.check_integrity(ROH),
+        [IndexIssue::EmptyGlob {
+            model_id: "m1".into(),
+            pattern: "nnet/other-*.jsonl".into(),
+        }]
+    );
+}
+
+#[test]
+fn weights_must_carry_the_active_roh_tag() {
+    let index = index(
+        "nnetfs-roh-tag",
+        "true,      roh-model-rohv1",
+        "true, roh-model-rohv0",
+    );
+    assert_eq!(
+        index.check_integrity(ROH),
+        [IndexIssue::RohTagMismatch {
+            model_id: "m1".into(),
+            roh_tag: "roh-model-rohv0".into(),
+            expected: ROH.into(),
+        }]
+    );
+}
+
+#[test]
+fn repo_index_reports_its_missing_rank_policy() {
+    let index = NnetfsIndex::load(common::repo(".nnetfs-index.aln")).unwrap();
+    let issues = index.check_integrity(ROH);
+    assert!(issues.contains(&IndexIssue::MissingFile {
+        section: "policies",
+        id: "bostrom-rank-qpolicy-v1".into(),
+        path: index.resolve(Path::new("policies/rank-qpolicy-v1.nnet-policy.aln")),
+    }));
+}
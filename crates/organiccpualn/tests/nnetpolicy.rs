@@ -0,0 +1,117 @@
+mod common;
+
+use organiccpualn::nnetfs::NnetfsIndex;
+use organiccpualn::nnetpolicy::{NnetPolicy, NnetPolicySet, NnetPolicyViolation};
+use organiccpualn::rohmodel::RohModelShard;
+
+const MODEL: &str = "bostrom-chat-gov-v1";
+
+fn chat_gov() -> NnetPolicy {
+    NnetPolicy::load(common::repo(
+        "qpudatashards/policies/bostrom-chat-gov-v1.nnet-policy.aln",
+    ))
+    .unwrap()
+}
+
+fn roh_model() -> RohModelShard {
+    RohModelShard::load(common::repo(
+        "qpudatashards/particles/bostrom-rohmodel-v1.rohmodel.aln",
+    ))
+    .unwrap()
+}
+
+fn set(policies: impl IntoIterator<Item = NnetPolicy>) -> NnetPolicySet {
+    let mut set = NnetPolicySet::default();
+    for policy in policies {
+        set.insert(policy).unwrap();
+    }
+    set
+}
+
+#[test]
+fn per_model_check_follows_domain_rules() {
+    let policies = set([chat_gov()]);
+    assert_eq!(
+        policies.check_proposal(MODEL, "language_cowriter", 0.20, 0.18),
+        Ok(())
+    );
+    assert_eq!(
+        policies.check_proposal(MODEL, "language_cowriter", 0.18, 0.20),
+        Err(NnetPolicyViolation::RohIncrease {
+            model_id: MODEL.into(),
+            domain: "language_cowriter".into(),
+            before: 0.18,
+            after: 0.20,
+        })
+    );
+    // `dream_observer` is listed with allowed=false.
+    assert_eq!(
+        policies.check_proposal(MODEL, "dream_observer", 0.20, 0.10),
+        Err(NnetPolicyViolation::DomainNotAllowed {
+            model_id: MODEL.into(),
+            domain: "dream_observer".into(),
+        })
+    );
+    assert_eq!(
+        policies.check_proposal(MODEL, "payments", 0.20, 0.10),
+        Err(NnetPolicyViolation::UnknownDomain {
+            model_id: MODEL.into(),
+            domain: "payments".into(),
+        })
+    );
+    assert_eq!(
+        policies.check_proposal("bostrom-rank-qpolicy-v1", "language_cowriter", 0.2, 0.1),
+        Err(NnetPolicyViolation::UnknownModel {
+            model_id: "bostrom-rank-qpolicy-v1".into(),
+        })
+    );
+}
+
+#[test]
+fn slices_must_fit_under_the_global_ceiling() {
+    let roh_model = roh_model();
+    assert_eq!(set([chat_gov()]).validate(&roh_model), Ok(()));
+
+    let mut greedy = chat_gov();
+    greedy.roh_slice.insert("other_axes".into(), 0.05);
+    assert!(matches!(
+        set([greedy]).validate(&roh_model),
+        Err(NnetPolicyViolation::SliceExceedsCeiling { .. })
+    ));
+
+    // Each model fits on its own; together they do not.
+    let mut rank = chat_gov();
+    rank.model_id = "bostrom-rank-qpolicy-v1".into();
+    rank.roh_slice = [("ranking".to_string(), 0.05)].into();
+    let err = set([chat_gov(), rank]).validate(&roh_model).unwrap_err();
+    let NnetPolicyViolation::AggregateExceedsCeiling { total, ceiling } = err else {
+        panic!("unexpected {err:?}");
+    };
+    assert!((total - 0.35).abs() < 1e-6);
+    assert_eq!(ceiling, 0.30);
+}
+
+#[test]
+fn a_missing_policy_fails_the_aggregate_check() {
+    let index = NnetfsIndex::load(common::repo(".nnetfs-index.aln")).unwrap();
+    let mut policies = NnetPolicySet::from_index(&index).unwrap();
+    assert!(policies.get(MODEL).is_some());
+    assert_eq!(
+        policies.missing().iter().collect::<Vec<_>>(),
+        ["bostrom-rank-qpolicy-v1"]
+    );
+    assert_eq!(
+        policies.validate(&roh_model()),
+        Err(NnetPolicyViolation::PolicyMissing {
+            model_id: "bostrom-rank-qpolicy-v1".into(),
+        })
+    );
+
+    // Supplying the policy closes the gap, and its slice then counts.
+    let mut rank = chat_gov();
+    rank.model_id = "bostrom-rank-qpolicy-v1".into();
+    rank.roh_slice.clear();
+    policies.insert(rank).unwrap();
+    assert!(policies.missing().is_empty());
+    assert_eq!(policies.validate(&roh_model()), Ok(()));
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use organiccpualn::donutloopledger::DonutloopLedger;
+use organiccpualn::nnetproof::{file_hash, NnetProof, ProofAnchor, ProofIssue};
+use organiccpualn::stake::StakeShard;
+
+struct Fixture {
+    root: PathBuf,
+    ledger: DonutloopLedger,
+    stake: StakeShard,
+    proof: NnetProof,
+}
+
+impl Fixture {
+    fn verify(&self) -> Vec<ProofIssue> {
+        self.proof
+            .verify(&self.root, &self.ledger, &self.stake)
+            .unwrap()
+    }
+
+    fn anchor(&mut self) -> &mut ProofAnchor {
+        &mut self.proof.anchors[0]
+    }
+}
+
+/// A weights file, a donutloop entry admitting it and a proof anchoring both.
+fn fixture(name: &str) -> Fixture {
+    let root = common::scratch(name);
+    let weights = Path::new("weights/chat-gov-v1.nnetw");
+    std::fs::create_dir_all(root.join("weights")).unwrap();
+    std::fs::write(root.join(weights), b"weights").unwrap();
+
+    let mut ledger =
+        DonutloopLedger::create(root.join("donutloopledger.aln"), common::SUBJECT).unwrap();
+    let entry = ledger
+        .append(common::entry("e1", 0.20, 0.18))
+        .unwrap()
+        .clone();
+    let stake = StakeShard::load(common::repo(
+        "qpudatashards/particles/bostrom-stake-v1.stake.aln",
+    ))
+    .unwrap();
+
+    let proof = NnetProof {
+        version: "1.0.0".into(),
+        subject_id: common::SUBJECT.into(),
+        model_id: "bostrom-chat-gov-v1".into(),
+        chain: "bostrom".into(),
+        network: "mainnet".into(),
+        asset_kind: "nnet-weights".into(),
+        nonfinancial: true,
+        soulnontradeable: true,
+        anchors: vec![ProofAnchor {
+            anchor_id: "a1".into(),
+            artifact_kind: "weights".into(),
+            local_path: weights.into(),
+            local_hash: file_hash(&root.join(weights)).unwrap(),
+            donut_entry_id: entry.entry_id.clone(),
+            donut_hexstamp: entry.hexstamp.clone(),
+            tx_hash: "0xabc".into(),
+            block_height: 1,
+            timestamp_utc: "2026-02-04T02:00:00Z".into(),
+            multisig: vec![common::SUBJECT.into(), common::ORGANIC_CPU.into()],
+        }],
+    };
+    Fixture {
+        root,
+        ledger,
+        stake,
+        proof,
+    }
+}
+
+#[test]
+fn a_matching_proof_has_no_issues() {
+    assert_eq!(fixture("proof-ok").verify(), []);
+    NnetProof::load(common::repo(
+        "proofs/nnet/bostrom-chat-gov-v1.nnet-proof.bchain.json",
+    ))
+    .unwrap();
+}
+
+#[test]
+fn artifacts_must_match_their_hash() {
+    let mut f = fixture("proof-hash");
+    let path = f.root.join(&f.proof.anchors[0].local_path);
+    std::fs::write(&path, b"retrained").unwrap();
+    assert_eq!(
+        f.verify(),
+        [ProofIssue::ArtifactHashMismatch {
+            anchor_id: "a1".into(),
+            expected: f.anchor().local_hash.clone(),
+            found: file_hash(&path).unwrap(),
+        }]
+    );
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        f.verify(),
+        [ProofIssue::MissingArtifact {
+            anchor_id: "a1".into(),
+            path,
+        }]
+    );
+}
+
+#[test]
+fn anchors_must_name_a_donutloop_entry_and_its_stamp() {
+    let mut f = fixture("proof-entry");
+    let stamp = f.anchor().donut_hexstamp.clone();
+    f.anchor().donut_hexstamp = format!("0x{}", "0".repeat(64));
+    assert_eq!(
+        f.verify(),
+        [ProofIssue::DonutHexstampMismatch {
+            anchor_id: "a1".into(),
+            expected: f.anchor().donut_hexstamp.clone(),
+            found: stamp,
+        }]
+    );
+
+    f.anchor().donut_entry_id = "e9".into();
+    assert_eq!(
+        f.verify(),
+        [ProofIssue::UnknownDonutEntry {
+            anchor_id: "a1".into(),
+            entry_id: "e9".into(),
+        }]
+    );
+}
+
+#[test]
+fn asset_flags_subject_and_signers_are_checked() {
+    let mut f = fixture("proof-flags");
+    f.proof.nonfinancial = false;
+    f.proof.soulnontradeable = false;
+    assert_eq!(
+        f.verify(),
+        [
+            ProofIssue::AssetNotProtected {
+                flag: "nonfinancial"
+            },
+            ProofIssue::AssetNotProtected {
+                flag: "soulnontradeable"
+            },
+        ]
+    );
+
+    let mut f = fixture("proof-signers");
+    f.anchor().multisig = vec![common::ORGANIC_CPU.into(), "bostrom1stranger".into()];
+    assert_eq!(
+        f.verify(),
+        [
+            ProofIssue::UnauthorizedSigner {
+                anchor_id: "a1".into(),
+                address: "bostrom1stranger".into(),
+            },
+            ProofIssue::MissingHostSignature {
+                anchor_id: "a1".into(),
+            },
+        ]
+    );
+
+    let mut f = fixture("proof-subject");
+    f.proof.subject_id = "bostrom1other".into();
+    let issues = f.verify();
+    assert_eq!(
+        issues[0],
+        ProofIssue::SubjectMismatch {
+            expected: common::SUBJECT.into(),
+            found: "bostrom1other".into(),
+        }
+    );
+}
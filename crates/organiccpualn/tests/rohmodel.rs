@@ -0,0 +1,81 @@
+mod common;
+
+use organiccpualn::aln::{AlnDocument, AlnError, AlnErrorKind};
+use organiccpualn::rohmodel::RohModelShard;
+
+const HEADER: &str = "\
+models
+  # model_id,       version, energy_load, thermal_load, cognitive_load, inflammation, eco_impact, roh_ceiling, active
+";
+
+const V0: &str = "  roh-model-rohv0,  0,  0.30, 0.20, 0.20, 0.15, 0.15, 0.30, ";
+const V1: &str = "  roh-model-rohv1,  1,  0.25, 0.20, 0.25, 0.15, 0.15, 0.30, ";
+
+/// A shard of `(row, active)` pairs.
+fn shard(rows: &[(&str, bool)]) -> Result<RohModelShard, AlnError> {
+    let mut text = HEADER.to_string();
+    for (row, active) in rows {
+        text.push_str(&format!("{row}{active}\n"));
+    }
+    RohModelShard::from_aln(&AlnDocument::parse("test.rohmodel.aln", &text).unwrap())
+}
+
+fn error_kind(rows: &[(&str, bool)]) -> AlnErrorKind {
+    shard(rows).unwrap_err().kind
+}
+
+#[test]
+fn exactly_one_row_is_active() {
+    let shard = shard(&[(V0, false), (V1, true)]).unwrap();
+    assert_eq!(shard.model.id, "roh-model-rohv1");
+    assert_eq!(shard.superseded.len(), 1);
+    assert_eq!(shard.superseded[0].id, "roh-model-rohv0");
+    shard.validate_invariants().unwrap();
+
+    let err = error_kind(&[(V0, true), (V1, true)]);
+    assert!(matches!(err, AlnErrorKind::InvalidValue { ref field, .. } if field == "active"));
+
+    let err = error_kind(&[(V0, false), (V1, false)]);
+    let AlnErrorKind::InvalidValue { reason, .. } = err else {
+        panic!("unexpected {err:?}");
+    };
+    assert_eq!(reason, "no RoH model row is active");
+
+    let err = error_kind(&[(V1, false), (V1, true)]);
+    assert!(matches!(err, AlnErrorKind::DuplicateKey(ref key) if key == "roh-model-rohv1 v1"));
+}
+
+#[test]
+fn every_row_keeps_the_ceiling_and_weights() {
+    let mut shard = shard(&[(V0, false), (V1, true)]).unwrap();
+    shard.superseded[0].weights.eco_impact = 0.25;
+    let err = shard.validate_invariants().unwrap_err();
+    assert!(format!("{err:#}").contains("sum to 1.0"), "{err:#}");
+
+    shard.superseded[0].weights.eco_impact = 0.15;
+    shard.model.roh_ceiling = 0.35;
+    let err = shard.validate_invariants().unwrap_err();
+    assert!(
+        format!("{err:#}").contains("ceiling must be 0.3"),
+        "{err:#}"
+    );
+
+    // The active row cannot also be listed as superseded.
+    shard.model.roh_ceiling = 0.30;
+    shard.superseded.push(shard.model.clone());
+    let err = shard.validate_invariants().unwrap_err();
+    assert!(
+        format!("{err:#}").contains("exactly one active row"),
+        "{err:#}"
+    );
+}
+
+#[test]
+fn repo_shard_loads() {
+    let shard = RohModelShard::load(common::repo(
+        "qpudatashards/particles/bostrom-rohmodel-v1.rohmodel.aln",
+    ))
+    .unwrap();
+    assert_eq!(shard.model.id, "roh-model-rohv1");
+    assert_eq!(shard.roh_ceiling(), 0.30);
+}
@@ -0,0 +1,178 @@
+mod common;
+
+use ed25519_dalek::SigningKey;
+use organiccpualn::evolvestream::{Scope, TokenKind};
+use organiccpualn::stake::{Role, StakeError, StakeShard};
+
+fn repo_shard() -> StakeShard {
+    StakeShard::load(common::repo(
+        "qpudatashards/particles/bostrom-stake-v1.stake.aln",
+    ))
+    .unwrap()
+}
+
+#[test]
+fn repo_shard_binds_keys_to_every_role() {
+    let stake = repo_shard();
+    for (address, role) in [
+        (common::SUBJECT, Role::Host),
+        (common::ORGANIC_CPU, Role::OrganicCpu),
+        (common::RESEARCH_AGENT, Role::ResearchAgent),
+    ] {
+        assert_eq!(stake.roles_for_address(common::SUBJECT, address), [role]);
+        assert!(
+            stake.signer_keys.iter().any(|k| k.address == address),
+            "no signer key bound to {address}"
+        );
+    }
+
+    // Signing with a key the shard does not bind proves no role.
+    let mut rec = common::record("p1", common::SUBJECT);
+    rec.sign(common::SUBJECT, &SigningKey::from_bytes(&[1; 32]));
+    assert_eq!(
+        stake.signer_roles(&rec, &rec.signatures[0]),
+        Err(StakeError::UnboundSignerKey {
+            address: common::SUBJECT.into()
+        })
+    );
+    assert!(stake.verified_roles(&rec).is_empty());
+}
+
+/// The repo shard with `from` replaced by `to`, loaded from a scratch copy.
+fn load_edited(name: &str, from: &str, to: &str) -> anyhow::Result<StakeShard> {
+    let path = common::repo("qpudatashards/particles/bostrom-stake-v1.stake.aln");
+    let text = std::fs::read_to_string(path).unwrap();
+    assert!(text.contains(from), "shard has no {from:?}");
+    let path = common::scratch(name).join("edited.stake.aln");
+    std::fs::write(&path, text.replacen(from, to, 1)).unwrap();
+    StakeShard::load(path)
+}
+
+fn stake_error(result: anyhow::Result<StakeShard>) -> StakeError {
+    result
+        .expect_err("shard should be rejected")
+        .downcast::<StakeError>()
+        .unwrap()
+}
+
+#[test]
+fn loader_requires_exactly_one_host_per_subject() {
+    let second_host = "  bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7, Host,       \
+                       bostrom1second, \"second host\"\n";
+    let organic = "  bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7, OrganicCPU,";
+    let err = stake_error(load_edited(
+        "two-hosts",
+        organic,
+        &format!("{second_host}{organic}"),
+    ));
+    assert_eq!(
+        err,
+        StakeError::HostCount {
+            subject_id: common::SUBJECT.into(),
+            count: 2
+        }
+    );
+
+    let err = stake_error(load_edited("no-host", " Host, ", " ResearchAgent, "));
+    assert_eq!(
+        err,
+        StakeError::HostCount {
+            subject_id: common::SUBJECT.into(),
+            count: 0
+        }
+    );
+
+    // Every subject named in the roles needs its own Host.
+    let mut stake = repo_shard();
+    stake.roles[1].subject_id = "bostrom1other".into();
+    assert_eq!(
+        stake.validate(),
+        Err(StakeError::HostCount {
+            subject_id: "bostrom1other".into(),
+            count: 0
+        })
+    );
+}
+
+#[test]
+fn loader_rejects_requirements_below_the_floor() {
+    let err = stake_error(load_edited(
+        "weak-arch",
+        "requiredroles Host,OrganicCPU,ResearchAgent",
+        "requiredroles Host,OrganicCPU",
+    ));
+    assert_eq!(
+        err,
+        StakeError::WeakRequirement {
+            scope: Scope::ArchChange,
+            missing: "ResearchAgent signer".into()
+        }
+    );
+
+    let err = stake_error(load_edited(
+        "weak-lifeforce",
+        "requiredroles Host,OrganicCPU\n",
+        "requiredroles Host\n",
+    ));
+    assert_eq!(
+        err,
+        StakeError::WeakRequirement {
+            scope: Scope::LifeforceAlteration,
+            missing: "OrganicCPU signer".into()
+        }
+    );
+
+    let mut stake = repo_shard();
+    stake
+        .requirements
+        .lifeforce
+        .token_kinds
+        .push(TokenKind::Smart);
+    assert_eq!(
+        stake.validate(),
+        Err(StakeError::WeakRequirement {
+            scope: Scope::LifeforceAlteration,
+            missing: "EVOLVE-only".into()
+        })
+    );
+
+    let mut stake = repo_shard();
+    stake.requirements.tuning.required_roles = vec![Role::OrganicCpu];
+    assert_eq!(
+        stake.validate(),
+        Err(StakeError::WeakRequirement {
+            scope: Scope::DayToDayTuning,
+            missing: "Host signer".into()
+        })
+    );
+}
+
+#[test]
+fn required_roles_must_be_held_for_the_subject() {
+    let mut stake = repo_shard();
+    stake.roles.retain(|r| r.role != Role::ResearchAgent);
+    stake
+        .signer_keys
+        .retain(|k| k.address != common::RESEARCH_AGENT);
+    let unheld = Err(StakeError::UnheldRole {
+        scope: Scope::ArchChange,
+        role: Role::ResearchAgent,
+        subject_id: common::SUBJECT.into(),
+    });
+    assert_eq!(stake.validate(), unheld);
+
+    // Holding it for another subject does not count.
+    let mut stake = repo_shard();
+    let research = stake
+        .roles
+        .iter_mut()
+        .find(|r| r.role == Role::ResearchAgent)
+        .unwrap();
+    research.subject_id = "bostrom1other".into();
+    stake.roles.push(organiccpualn::stake::StakeRole {
+        subject_id: "bostrom1other".into(),
+        role: Role::Host,
+        address: "bostrom1other".into(),
+    });
+    assert_eq!(stake.validate(), unheld);
+}
@@ -0,0 +1,156 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use organiccpualn::donutloopledger::LedgerMark;
+use organiccpualn::evolvelog::{LogMark, RotatingMark};
+use organiccpualn::hexstamp::{Canonical, Hexstamped};
+use organiccpualn::nnetevolve::NnetUpdateTracker;
+use serde::{Deserialize, Serialize};
+
+use crate::lifecycle::ProposalLifecycle;
+
+/// Checkpoint layout written by this version.
+pub const CHECKPOINT_VERSION: u32 = 3;
+
+/// Checkpoints kept on disk; older ones are pruned after each save.
+const KEEP: usize = 2;
+
+/// State the core derives from its ledger and logs, with the position in
+/// each it was derived up to. Startup restores it and replays only what
+/// was appended after.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Checkpoint {
+    pub version: u32,
+    pub created_utc: String,
+    pub donutloop: LedgerMark,
+    pub evolvestream: LogMark,
+    pub nnetlog: Option<RotatingMark>,
+    pub lifecycle: ProposalLifecycle,
+    pub nnet_tracker: NnetUpdateTracker,
+    pub hexstamp: String,
+}
+
+impl Hexstamped for Checkpoint {
+    /// Nested state is stamped as its JSON; the maps in it are ordered.
+    fn canonical(&self) -> Canonical {
+        Canonical::new("core-checkpoint", 1)
+            .u32("version", self.version)
+            .str("created_utc", &self.created_utc)
+            .str("donutloop", &json(&self.donutloop))
+            .str("evolvestream", &json(&self.evolvestream))
+            .str("nnetlog", &json(&self.nnetlog))
+            .str("lifecycle", &json(&self.lifecycle))
+            .str("nnet_tracker", &json(&self.nnet_tracker))
+    }
+
+    fn hexstamp(&self) -> &str {
+        &self.hexstamp
+    }
+
+    fn set_hexstamp(&mut self, hexstamp: String) {
+        self.hexstamp = hexstamp;
+    }
+}
+
+fn json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).expect("checkpoint state serializes")
+}
+
+/// `checkpoint-NNNNNNNNNN.json` files in a directory, numbered in save order.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointStore {
+    dir: PathBuf,
+}
+
+impl CheckpointStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Saved checkpoints, newest first.
+    pub fn list(&self) -> anyhow::Result<Vec<PathBuf>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut numbered = Vec::new();
+        for entry in fs::read_dir(&self.dir)
+            .with_context(|| format!("listing checkpoints in {}", self.dir.display()))?
+        {
+            let path = entry?.path();
+            if let Some(n) = sequence(&path) {
+                numbered.push((n, path));
+            }
+        }
+        numbered.sort();
+        Ok(numbered.into_iter().rev().map(|(_, p)| p).collect())
+    }
+
+    /// Read a checkpoint and check its version and hexstamp.
+    pub fn load(path: &Path) -> anyhow::Result<Checkpoint> {
+        let text =
+            fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        let checkpoint: Checkpoint =
+            serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            anyhow::bail!(
+                "{}: unsupported checkpoint version {}",
+                path.display(),
+                checkpoint.version
+            );
+        }
+        checkpoint
+            .verify_hexstamp()
+            .with_context(|| format!("checking {}", path.display()))?;
+        Ok(checkpoint)
+    }
+
+    /// Stamp and write `checkpoint` as the newest one, then prune old ones.
+    /// It is written to a temporary file and renamed into place.
+    pub fn save(&self, checkpoint: &mut Checkpoint) -> anyhow::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("creating {}", self.dir.display()))?;
+        let saved = self.list()?;
+        let next = saved.first().and_then(|p| sequence(p)).map_or(1, |n| n + 1);
+        let path = self.dir.join(format!("checkpoint-{next:010}.json"));
+
+        checkpoint.stamp();
+        let tmp = path.with_extension("json.tmp");
+        {
+            let mut f =
+                File::create(&tmp).with_context(|| format!("creating {}", tmp.display()))?;
+            f.write_all(serde_json::to_string(checkpoint)?.as_bytes())?;
+            f.sync_all()?;
+        }
+        fs::rename(&tmp, &path)?;
+        File::open(&self.dir)?.sync_all()?;
+
+        for old in saved.iter().skip(KEEP - 1) {
+            fs::remove_file(old).with_context(|| format!("removing {}", old.display()))?;
+        }
+        Ok(path)
+    }
+
+    /// Remove every saved checkpoint.
+    pub fn clear(&self) -> anyhow::Result<()> {
+        for path in self.list()? {
+            fs::remove_file(&path).with_context(|| format!("removing {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn sequence(path: &Path) -> Option<u64> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix("checkpoint-")?
+        .strip_suffix(".json")?
+        .parse()
+        .ok()
+}
@@ -0,0 +1,280 @@
+use std::fmt;
+
+use organiccpualn::neurorights::NeurorightsClause;
+use organiccpualn::stake::Role;
+use serde::{Deserialize, Serialize};
+
+use crate::guards::{GuardRun, Verdict};
+
+/// Why a proposal was rejected, as a stable machine-readable code.
+/// Serialized in snake_case; existing codes are never renamed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasonCode {
+    /// The record is not what was stamped.
+    HexstampMismatch,
+    /// The record's subject is not the one this core governs.
+    SubjectNotGoverned,
+    /// A signer role is not one the stake shard knows.
+    UnknownSignerRole,
+    /// The scope's multisig rule needs roles that did not sign.
+    MissingSigners,
+    /// A neurorights clause forbids the proposal; see `Finding::clause`.
+    NeurorightsViolation,
+    /// RoH after the change is above the global ceiling.
+    RohCeilingExceeded,
+    /// RoH after the change is above RoH before it, or above the ledger head.
+    RohIncreased,
+    /// The rollback target is not in the donutloop.
+    RollbackTargetMissing,
+    /// The rollback target was never applied or is already undone.
+    RollbackTargetNotApplied,
+    /// A rollback does not return RoH exactly to the state before its target.
+    RollbackRohMismatch,
+    /// The state a rollback returns to is above the global ceiling.
+    RollbackStateAboveCeiling,
+    /// An envelope loosens (G_new < G_old or D_new > D_old).
+    EnvelopeLoosened,
+    /// The current swarm state lies outside the viability kernel.
+    OutsideViabilityKernel,
+    /// CyberRank has no Tsafe action for the update.
+    NoTsafeAction,
+    /// The token kind is not allowed for the scope.
+    TokenNotAllowed,
+    /// An NnetUpdate is malformed (kind, delta or timestamp).
+    NnetInvalidUpdate,
+    /// An NnetUpdate's parameter delta exceeds the per-update bound.
+    NnetEffectSizeExceeded,
+    /// An NnetUpdate would exceed the model's daily parameter-delta budget.
+    NnetDailyBudgetExceeded,
+    /// An NnetUpdate does not continue the model's params hash chain.
+    NnetParamsHashMismatch,
+    /// No nnet policy is loaded for the NnetUpdate's model.
+    NnetPolicyMissing,
+    /// The proposal's donutloop entry already exists.
+    DuplicateEntry,
+    /// The donutloop refused the entry for another chain reason.
+    DonutloopRejected,
+    /// A guard failed to run; the pipeline fails closed.
+    GuardFailed,
+    /// A deployment-registered guard rejected; see `Finding::reason`.
+    Custom,
+    /// The proposing model lacks the declared capability for the domain.
+    CapabilityDenied,
+    /// A deferred proposal did not collect its signers in time.
+    DeferralExpired,
+    /// A signature is malformed or does not verify over the proposal.
+    BadSignature,
+    /// A signature's key is not bound to its address in the stake shard.
+    UnboundSignerKey,
+    /// A verified signer holds no role for the proposal's subject.
+    SignerHasNoRole,
+    /// An incoming record already carries a decision other than Proposed.
+    NotProposed,
+    /// An incoming record has already been forgotten.
+    AlreadyForgotten,
+}
+
+impl fmt::Display for ReasonCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The serde name, so logs and API payloads agree.
+        let name = serde_json::to_value(self).expect("reason codes serialize");
+        f.write_str(name.as_str().expect("unit variant"))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Bound {
+    AtMost,
+    AtLeast,
+    Equal,
+}
+
+/// A measured value and the threshold it was compared with, e.g.
+/// `roh_after` at most `roh_ceiling`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub quantity: String,
+    pub measured: f32,
+    pub bound: Bound,
+    pub threshold: f32,
+    /// What the threshold is, e.g. `roh_ceiling` or `g_old`.
+    pub threshold_of: String,
+}
+
+impl Measurement {
+    pub fn at_most(quantity: &str, measured: f32, threshold_of: &str, threshold: f32) -> Self {
+        Self::new(quantity, measured, Bound::AtMost, threshold_of, threshold)
+    }
+
+    pub fn at_least(quantity: &str, measured: f32, threshold_of: &str, threshold: f32) -> Self {
+        Self::new(quantity, measured, Bound::AtLeast, threshold_of, threshold)
+    }
+
+    pub fn equal(quantity: &str, measured: f32, threshold_of: &str, threshold: f32) -> Self {
+        Self::new(quantity, measured, Bound::Equal, threshold_of, threshold)
+    }
+
+    fn new(
+        quantity: &str,
+        measured: f32,
+        bound: Bound,
+        threshold_of: &str,
+        threshold: f32,
+    ) -> Self {
+        Self {
+            quantity: quantity.to_string(),
+            measured,
+            bound,
+            threshold,
+            threshold_of: threshold_of.to_string(),
+        }
+    }
+
+    /// Whether the measured value is within the bound, with the same
+    /// `f32::EPSILON` slack the guards use.
+    pub fn holds(&self) -> bool {
+        match self.bound {
+            Bound::AtMost => self.measured <= self.threshold + f32::EPSILON,
+            Bound::AtLeast => self.measured + f32::EPSILON >= self.threshold,
+            Bound::Equal => (self.measured - self.threshold).abs() <= f32::EPSILON,
+        }
+    }
+}
+
+/// A guard's finding against a proposal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub code: ReasonCode,
+    pub reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clause: Option<NeurorightsClause>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub measurements: Vec<Measurement>,
+}
+
+impl Finding {
+    pub fn new(code: ReasonCode, reason: impl Into<String>) -> Self {
+        Self {
+            code,
+            reason: reason.into(),
+            clause: None,
+            measurements: Vec::new(),
+        }
+    }
+
+    pub fn measured(mut self, measurement: Measurement) -> Self {
+        self.measurements.push(measurement);
+        self
+    }
+
+    pub fn clause(mut self, clause: NeurorightsClause) -> Self {
+        self.clause = Some(clause);
+        self
+    }
+}
+
+/// Why a proposal was rejected and which guard decided it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rejection {
+    pub guard: String,
+    #[serde(flatten)]
+    pub finding: Finding,
+}
+
+impl Rejection {
+    pub fn new(guard: &str, finding: Finding) -> Self {
+        Self {
+            guard: guard.to_string(),
+            finding,
+        }
+    }
+
+    pub fn code(&self) -> ReasonCode {
+        self.finding.code
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.finding.reason
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}]: {}", self.guard, self.code(), self.reason())
+    }
+}
+
+/// A proposal parked until the missing signer roles attach their signatures.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Deferral {
+    pub guard: String,
+    pub missing_roles: Vec<Role>,
+    /// When the proposal is rejected if still short of signers; set by the core.
+    pub expires_utc: String,
+}
+
+/// Final decision of the sovereignty core.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DecisionOutcome {
+    Allowed,
+    Rejected(Rejection),
+    Deferred(Deferral),
+}
+
+impl DecisionOutcome {
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, DecisionOutcome::Allowed)
+    }
+
+    pub fn rejection(&self) -> Option<&Rejection> {
+        match self {
+            DecisionOutcome::Rejected(rejection) => Some(rejection),
+            _ => None,
+        }
+    }
+
+    pub fn deferral(&self) -> Option<&Deferral> {
+        match self {
+            DecisionOutcome::Deferred(deferral) => Some(deferral),
+            _ => None,
+        }
+    }
+}
+
+/// A decision together with the verdict of every guard that ran on it, in order.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DecisionReport {
+    pub outcome: DecisionOutcome,
+    pub trace: Vec<GuardRun>,
+    /// Signatures `attach_signatures` dropped because they did not verify.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dropped_signatures: Vec<Finding>,
+}
+
+impl DecisionReport {
+    /// Rejected by a single check made outside the guard pipeline.
+    pub fn rejected(guard: &str, finding: Finding) -> Self {
+        Self {
+            trace: vec![GuardRun {
+                guard: guard.to_string(),
+                verdict: Verdict::Reject(finding.clone()),
+            }],
+            outcome: DecisionOutcome::Rejected(Rejection::new(guard, finding)),
+            dropped_signatures: Vec::new(),
+        }
+    }
+
+    pub fn is_allowed(&self) -> bool {
+        self.outcome.is_allowed()
+    }
+
+    pub fn rejection(&self) -> Option<&Rejection> {
+        self.outcome.rejection()
+    }
+
+    pub fn deferral(&self) -> Option<&Deferral> {
+        self.outcome.deferral()
+    }
+}
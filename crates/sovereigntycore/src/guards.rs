@@ -0,0 +1,541 @@
+use std::fmt;
+use std::sync::Arc;
+
+use cybernano_vector_cyberrank::{self, CandidateAction, RankVector, RankWeights};
+use cybernano_viability_kernel::{LifeforceState, SwarmState7D, ViabilityKernel};
+use organiccpualn::donutloopledger::DonutloopLedger;
+use organiccpualn::evolvestream::{Decision, EvolutionProposalRecord};
+use organiccpualn::neurorights::{NeurorightsClause, NeurorightsPolicyDocument};
+use organiccpualn::proposalsig::Signable;
+use organiccpualn::rohmodel::RohModelShard;
+use organiccpualn::stake::{Role, StakeError, StakeShard};
+use serde::{Deserialize, Serialize};
+
+use crate::decision::{DecisionOutcome, Deferral, Finding, Measurement, ReasonCode, Rejection};
+use crate::lifecycle::ProposalLifecycle;
+use crate::types::UpdateProposal;
+
+/// Final pipeline stage: log the decision to the evolve stream and append
+/// allowed proposals to the donutloop. Run by the core, always last.
+pub const LOG_STAGE: &str = "logtoevolveanddonutloop";
+
+/// Pipeline used when no manifest supplies one.
+pub const DEFAULT_ORDER: [&str; 7] = [
+    "signerguard",
+    "stakeguard",
+    "neurorightsguard",
+    "rohguard",
+    "envelopeguard",
+    "tokenguard",
+    LOG_STAGE,
+];
+
+/// Guards every pipeline must run; a manifest may reorder them but not drop them.
+pub const MANDATORY_GUARDS: [&str; 6] = [
+    "signerguard",
+    "stakeguard",
+    "neurorightsguard",
+    "rohguard",
+    "envelopeguard",
+    "tokenguard",
+];
+
+/// Core state a guard may consult.
+pub struct GuardContext<'a> {
+    pub roh_ceiling: f32,
+    pub roh_model: &'a RohModelShard,
+    pub stake: &'a StakeShard,
+    pub neurorights: &'a NeurorightsPolicyDocument,
+    pub viability_kernel: &'a ViabilityKernel,
+    pub rank_weights: &'a RankWeights,
+    pub donutloop: &'a DonutloopLedger,
+    pub lifecycle: &'a ProposalLifecycle,
+}
+
+/// What is being decided: always the Proposed evolve record, plus the
+/// envelopes and live state when it came in as an `UpdateProposal`.
+pub struct GuardInput<'a> {
+    pub record: &'a EvolutionProposalRecord,
+    pub update: Option<UpdateInput<'a>>,
+}
+
+pub struct UpdateInput<'a> {
+    pub proposal: &'a UpdateProposal,
+    pub swarm_state: &'a SwarmState7D,
+    pub lifeforce: &'a LifeforceState,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Verdict {
+    Pass,
+    // The input carries nothing this guard checks.
+    NotApplicable { why: String },
+    // Nothing is wrong yet, but these signer roles must still sign.
+    Defer { missing_roles: Vec<Role> },
+    Reject(Finding),
+}
+
+impl Verdict {
+    pub fn passed(&self) -> bool {
+        !matches!(self, Verdict::Reject(_))
+    }
+}
+
+/// One check in the pipeline, named as in the manifest's `guardpipeline order`.
+pub trait Guard: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn check(&self, ctx: &GuardContext<'_>, input: &GuardInput<'_>) -> anyhow::Result<Verdict>;
+}
+
+/// A guard that ran on one evaluation and what it said.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GuardRun {
+    pub guard: String,
+    pub verdict: Verdict,
+}
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum PipelineError {
+    #[error("unknown guard `{0}` in pipeline")]
+    UnknownGuard(String),
+    #[error("guard `{0}` appears twice in pipeline")]
+    DuplicateGuard(String),
+    #[error("mandatory guard `{0}` is missing from pipeline")]
+    MissingMandatory(&'static str),
+    #[error("pipeline must end with `{LOG_STAGE}`")]
+    LogStageNotLast,
+    #[error("a guard named `{0}` is already registered")]
+    NameTaken(String),
+}
+
+/// Guards a pipeline can be built from: the built-in ones plus any a
+/// deployment registers. Built-in names cannot be re-registered, so a
+/// mandatory guard cannot be swapped for a weaker one.
+#[derive(Clone)]
+pub struct GuardRegistry {
+    guards: Vec<Arc<dyn Guard>>,
+}
+
+impl GuardRegistry {
+    pub fn builtin() -> Self {
+        Self {
+            guards: vec![
+                Arc::new(SignerGuard),
+                Arc::new(StakeGuard),
+                Arc::new(NeurorightsGuard),
+                Arc::new(RohGuard),
+                Arc::new(EnvelopeGuard),
+                Arc::new(TokenGuard),
+            ],
+        }
+    }
+
+    pub fn register(&mut self, guard: Arc<dyn Guard>) -> Result<(), PipelineError> {
+        let name = guard.name();
+        if name == LOG_STAGE || self.get(name).is_some() {
+            return Err(PipelineError::NameTaken(name.to_string()));
+        }
+        self.guards.push(guard);
+        Ok(())
+    }
+
+    fn get(&self, name: &str) -> Option<&Arc<dyn Guard>> {
+        self.guards.iter().find(|g| g.name() == name)
+    }
+
+    /// Build a pipeline from a manifest order. Fails closed: unknown or
+    /// repeated names, a missing mandatory guard or a misplaced log stage
+    /// are all errors.
+    pub fn pipeline<S: AsRef<str>>(&self, order: &[S]) -> Result<GuardPipeline, PipelineError> {
+        let (last, guards) = order.split_last().ok_or(PipelineError::LogStageNotLast)?;
+        if last.as_ref() != LOG_STAGE {
+            return Err(PipelineError::LogStageNotLast);
+        }
+        let mut pipeline = Vec::new();
+        for name in guards.iter().map(AsRef::as_ref) {
+            if name == LOG_STAGE {
+                return Err(PipelineError::LogStageNotLast);
+            }
+            let guard = self
+                .get(name)
+                .ok_or_else(|| PipelineError::UnknownGuard(name.to_string()))?;
+            if pipeline.iter().any(|g: &Arc<dyn Guard>| g.name() == name) {
+                return Err(PipelineError::DuplicateGuard(name.to_string()));
+            }
+            pipeline.push(guard.clone());
+        }
+        if let Some(missing) = MANDATORY_GUARDS
+            .into_iter()
+            .find(|m| !pipeline.iter().any(|g| g.name() == *m))
+        {
+            return Err(PipelineError::MissingMandatory(missing));
+        }
+        Ok(GuardPipeline { guards: pipeline })
+    }
+}
+
+/// Guards in manifest order, ending in the log stage.
+#[derive(Clone)]
+pub struct GuardPipeline {
+    guards: Vec<Arc<dyn Guard>>,
+}
+
+impl GuardPipeline {
+    /// Pipeline of built-in guards in `order`.
+    pub fn from_order<S: AsRef<str>>(order: &[S]) -> Result<Self, PipelineError> {
+        GuardRegistry::builtin().pipeline(order)
+    }
+
+    /// Configured stages in run order, the log stage included.
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self.guards.iter().map(|g| g.name()).collect();
+        names.push(LOG_STAGE);
+        names
+    }
+
+    /// Run the guards in order until one rejects; every guard that ran is
+    /// pushed to `trace`. A guard that fails to run rejects with
+    /// `GuardFailed`. Guards after a deferral still run, so a proposal is
+    /// only parked when nothing but signatures stands in its way. Allowed
+    /// means the log stage may run.
+    pub fn run(
+        &self,
+        ctx: &GuardContext<'_>,
+        input: &GuardInput<'_>,
+        trace: &mut Vec<GuardRun>,
+    ) -> DecisionOutcome {
+        let mut deferral: Option<Deferral> = None;
+        for guard in &self.guards {
+            let verdict = guard.check(ctx, input).unwrap_or_else(|e| {
+                Verdict::Reject(Finding::new(ReasonCode::GuardFailed, format!("{e:#}")))
+            });
+            trace.push(GuardRun {
+                guard: guard.name().to_string(),
+                verdict: verdict.clone(),
+            });
+            match verdict {
+                Verdict::Reject(finding) => {
+                    return DecisionOutcome::Rejected(Rejection::new(guard.name(), finding));
+                }
+                Verdict::Defer { missing_roles } => {
+                    let deferral = deferral.get_or_insert_with(|| Deferral {
+                        guard: guard.name().to_string(),
+                        missing_roles: Vec::new(),
+                        expires_utc: String::new(),
+                    });
+                    for role in missing_roles {
+                        if !deferral.missing_roles.contains(&role) {
+                            deferral.missing_roles.push(role);
+                        }
+                    }
+                }
+                Verdict::Pass | Verdict::NotApplicable { .. } => {}
+            }
+        }
+        match deferral {
+            Some(deferral) => DecisionOutcome::Deferred(deferral),
+            None => DecisionOutcome::Allowed,
+        }
+    }
+}
+
+impl Default for GuardPipeline {
+    fn default() -> Self {
+        Self::from_order(&DEFAULT_ORDER).expect("default pipeline is valid")
+    }
+}
+
+impl fmt::Debug for GuardPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+fn reject(finding: Finding) -> anyhow::Result<Verdict> {
+    Ok(Verdict::Reject(finding))
+}
+
+/// `roh_after` within the global ceiling.
+fn roh_ceiling(ctx: &GuardContext<'_>, roh_after: f32) -> Measurement {
+    Measurement::at_most("roh_after", roh_after, "roh_ceiling", ctx.roh_ceiling)
+}
+
+/// Stake errors as findings; the caller's scope and roles are in the error.
+pub(crate) fn stake_finding(e: StakeError) -> Finding {
+    let code = match &e {
+        StakeError::Unknown(_) => ReasonCode::UnknownSignerRole,
+        StakeError::MissingSigners { .. } => ReasonCode::MissingSigners,
+        StakeError::TokenNotAllowed { .. } => ReasonCode::TokenNotAllowed,
+        StakeError::Signature(_) => ReasonCode::BadSignature,
+        StakeError::UnboundSignerKey { .. } => ReasonCode::UnboundSignerKey,
+        StakeError::SignerHasNoRole { .. } => ReasonCode::SignerHasNoRole,
+        _ => ReasonCode::GuardFailed,
+    };
+    Finding::new(code, e.to_string())
+}
+
+/// Every signature must verify with a key the stake shard binds to the
+/// signer's address, and that address must hold a role for the subject.
+pub struct SignerGuard;
+
+impl Guard for SignerGuard {
+    fn name(&self) -> &'static str {
+        "signerguard"
+    }
+
+    fn check(&self, ctx: &GuardContext<'_>, input: &GuardInput<'_>) -> anyhow::Result<Verdict> {
+        match &input.update {
+            Some(update) => check_signatures(ctx.stake, update.proposal),
+            None => check_signatures(ctx.stake, input.record),
+        }
+    }
+}
+
+fn check_signatures(stake: &StakeShard, signed: &impl Signable) -> anyhow::Result<Verdict> {
+    if signed.signatures().is_empty() {
+        return Ok(Verdict::NotApplicable {
+            why: "no signatures attached".into(),
+        });
+    }
+    for signature in signed.signatures() {
+        if let Err(e) = stake.signer_roles(signed, signature) {
+            return reject(stake_finding(e));
+        }
+    }
+    Ok(Verdict::Pass)
+}
+
+/// Roles proven by verified signatures must satisfy the stake shard's
+/// multisig rule for the scope; claimed `signer_roles` count for nothing.
+/// A proposal short of signers is deferred until they sign; an update
+/// proposal is rejected instead, as it cannot be re-run later.
+pub struct StakeGuard;
+
+impl Guard for StakeGuard {
+    fn name(&self) -> &'static str {
+        "stakeguard"
+    }
+
+    fn check(&self, ctx: &GuardContext<'_>, input: &GuardInput<'_>) -> anyhow::Result<Verdict> {
+        let rec = input.record;
+        let signers = match &input.update {
+            Some(update) => ctx.stake.verified_roles(update.proposal),
+            None => ctx.stake.verified_roles(rec),
+        };
+        match ctx.stake.check_signers_for_scope(rec.scope, &signers) {
+            Ok(()) => Ok(Verdict::Pass),
+            Err(StakeError::MissingSigners { missing, .. }) if input.update.is_none() => {
+                Ok(Verdict::Defer {
+                    missing_roles: missing,
+                })
+            }
+            Err(e) => reject(stake_finding(e)),
+        }
+    }
+}
+
+/// Every neurorights clause: ceiling, dream-state, decision uses, data scope.
+pub struct NeurorightsGuard;
+
+impl Guard for NeurorightsGuard {
+    fn name(&self) -> &'static str {
+        "neurorightsguard"
+    }
+
+    fn check(&self, ctx: &GuardContext<'_>, input: &GuardInput<'_>) -> anyhow::Result<Verdict> {
+        let rec = input.record;
+        let Err(violation) = ctx.neurorights.enforce_for_proposal(rec, ctx.roh_model) else {
+            return Ok(Verdict::Pass);
+        };
+        let mut finding = Finding::new(ReasonCode::NeurorightsViolation, violation.to_string())
+            .clause(violation.clause);
+        if violation.clause == NeurorightsClause::RohCeiling {
+            finding = finding.measured(roh_ceiling(ctx, rec.roh_after));
+        }
+        reject(finding)
+    }
+}
+
+/// RoH stays within the 0.30 ceiling and never rises, except for a
+/// rollback that returns exactly to a recorded state.
+pub struct RohGuard;
+
+impl Guard for RohGuard {
+    fn name(&self) -> &'static str {
+        "rohguard"
+    }
+
+    fn check(&self, ctx: &GuardContext<'_>, input: &GuardInput<'_>) -> anyhow::Result<Verdict> {
+        let rec = input.record;
+        let ceiling = roh_ceiling(ctx, rec.roh_after);
+        if !ceiling.holds() {
+            return reject(
+                Finding::new(
+                    ReasonCode::RohCeilingExceeded,
+                    "RoH_after exceeds global ceiling (0.30)",
+                )
+                .measured(ceiling),
+            );
+        }
+        if let Some(entry_id) = rec.rollback_target() {
+            return match check_rollback(ctx, rec, entry_id) {
+                Ok(()) => Ok(Verdict::Pass),
+                Err(finding) => reject(finding),
+            };
+        }
+        let monotone =
+            Measurement::at_most("roh_after", rec.roh_after, "roh_before", rec.roh_before);
+        if !monotone.holds() {
+            return reject(
+                Finding::new(
+                    ReasonCode::RohIncreased,
+                    "RoH_after > RoH_before (no relaxation allowed)",
+                )
+                .measured(monotone),
+            );
+        }
+        Ok(Verdict::Pass)
+    }
+}
+
+/// A rollback must undo an applied change and return RoH exactly to the
+/// state recorded before it, which must itself be within the ceiling.
+fn check_rollback(
+    ctx: &GuardContext<'_>,
+    rec: &EvolutionProposalRecord,
+    entry_id: &str,
+) -> Result<(), Finding> {
+    let entry = ctx
+        .donutloop
+        .entry(entry_id)
+        .map_err(|e| {
+            Finding::new(
+                ReasonCode::GuardFailed,
+                format!("reading rollback target {entry_id}: {e:#}"),
+            )
+        })?
+        .ok_or_else(|| {
+            Finding::new(
+                ReasonCode::RollbackTargetMissing,
+                format!("rollback target {entry_id} is not in the donutloop"),
+            )
+        })?;
+    if ctx.lifecycle.state(&entry.proposal_id) != Some(Decision::Applied) {
+        return Err(Finding::new(
+            ReasonCode::RollbackTargetNotApplied,
+            format!("rollback target {entry_id} was never applied or is already undone"),
+        ));
+    }
+    let restores = Measurement::equal("roh_after", rec.roh_after, "roh_before", entry.roh_before);
+    if !restores.holds() {
+        return Err(Finding::new(
+            ReasonCode::RollbackRohMismatch,
+            format!(
+                "rollback of {entry_id} must restore RoH {} (got {})",
+                entry.roh_before, rec.roh_after
+            ),
+        )
+        .measured(restores));
+    }
+    let ceiling = Measurement::at_most(
+        "roh_before",
+        entry.roh_before,
+        "roh_ceiling",
+        ctx.roh_ceiling,
+    );
+    if !ceiling.holds() {
+        return Err(Finding::new(
+            ReasonCode::RollbackStateAboveCeiling,
+            format!("state before {entry_id} exceeds global ceiling (0.30)"),
+        )
+        .measured(ceiling));
+    }
+    Ok(())
+}
+
+/// Envelopes only tighten (G_new ≥ G_old, D_new ≤ D_old), the current
+/// swarm state lies in the viability kernel and CyberRank selects the update
+/// as a Tsafe action.
+pub struct EnvelopeGuard;
+
+impl Guard for EnvelopeGuard {
+    fn name(&self) -> &'static str {
+        "envelopeguard"
+    }
+
+    fn check(&self, ctx: &GuardContext<'_>, input: &GuardInput<'_>) -> anyhow::Result<Verdict> {
+        let Some(update) = &input.update else {
+            return Ok(Verdict::NotApplicable {
+                why: "evolve records carry no envelopes".into(),
+            });
+        };
+        let env = &update.proposal.envelopes;
+        if !env.is_monotone() {
+            let finding = Finding::new(
+                ReasonCode::EnvelopeLoosened,
+                "Envelope monotonicity violated (no loosening allowed)",
+            );
+            return reject(
+                [
+                    Measurement::at_least("g_new", env.g_new, "g_old", env.g_old),
+                    Measurement::at_most("d_new", env.d_new, "d_old", env.d_old),
+                ]
+                .into_iter()
+                .filter(|m| !m.holds())
+                .fold(finding, Finding::measured),
+            );
+        }
+        if !ctx
+            .viability_kernel
+            .is_viable(update.swarm_state, update.lifeforce)
+        {
+            return reject(Finding::new(
+                ReasonCode::OutsideViabilityKernel,
+                "Current swarm_state outside viability kernel",
+            ));
+        }
+        let candidates = [update_candidate(update.proposal, ctx.roh_ceiling)];
+        if cybernano_vector_cyberrank::tsafe_select(&candidates, ctx.rank_weights).is_none() {
+            return reject(Finding::new(
+                ReasonCode::NoTsafeAction,
+                "Update is not a Tsafe action (irreversible or above the RoH ceiling)",
+            ));
+        }
+        Ok(Verdict::Pass)
+    }
+}
+
+/// The update as a CyberRank candidate: viable while it stays under the RoH
+/// ceiling and can be rolled back (CLAUSE rollbackanytime). Proposals only
+/// carry RoH headroom and reversibility to rank on.
+fn update_candidate(proposal: &UpdateProposal, roh_ceiling: f32) -> CandidateAction {
+    let reversible = !proposal.effect_bounds.irreversible;
+    CandidateAction {
+        id: proposal.proposal_id.clone(),
+        rank: RankVector {
+            safety: (1.0 - proposal.roh_after / roh_ceiling).clamp(0.0, 1.0),
+            legal: 0.0,
+            biomech: 0.0,
+            psych: 0.0,
+            rollback: if reversible { 1.0 } else { 0.0 },
+        },
+        is_viable: reversible && proposal.roh_after <= roh_ceiling,
+    }
+}
+
+/// The token must be one the stake shard accepts for the scope (SMART vs EVOLVE).
+pub struct TokenGuard;
+
+impl Guard for TokenGuard {
+    fn name(&self) -> &'static str {
+        "tokenguard"
+    }
+
+    fn check(&self, ctx: &GuardContext<'_>, input: &GuardInput<'_>) -> anyhow::Result<Verdict> {
+        match input.record.enforce_token_scopes(ctx.stake) {
+            Ok(()) => Ok(Verdict::Pass),
+            Err(e) => reject(stake_finding(e)),
+        }
+    }
+}
@@ -1,27 +1,57 @@
-use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
 
 use cybernano_viability_kernel::{LifeforceState, SwarmState7D, ViabilityKernel};
-use cybernano_vector_cyberrank::{self, CandidateAction, RankWeights};
+use anyhow::Context;
+use cybernano_vector_cyberrank::RankWeights;
+use ed25519_dalek::SigningKey;
 use organiccpualn::{
-    donutloopledger::{DonutloopEntry, DonutloopLedger},
-    evolvestream::{EvolutionLogWriter, EvolutionProposalRecord},
+    donutloopledger::{ChainError, ChainErrorKind, DonutloopEntry, DonutloopLedger},
+    evolvelog::{LogMark, RotatingEvolveLog},
+    evolvestream::{Decision, EvolutionLogWriter, EvolutionProposalRecord, JsonlEvolutionLog},
+    proposalsig::{ProposalSignature, Signable},
+    forget::{self, ForgetLog, ForgetReceipt, ForgetRequest, OverdueForget},
+    hexstamp::Hexstamped,
+    manifest::WorkspaceManifest,
     neurorights::NeurorightsPolicyDocument,
-    rohmodel::{RohInputs, RohModelShard},
+    nnetevolve::{NnetUpdateError, NnetUpdateRecord, NnetUpdateTracker},
+    nnetpolicy::NnetPolicySet,
+    rohmodel::RohModelShard,
     stake::StakeShard,
 };
 use serde::{Deserialize, Serialize};
 
-mod types;
+pub mod types;
 pub mod metrics;
+pub mod lifecycle;
+pub mod checkpoint;
+pub mod guards;
+pub mod decision;
+pub mod server;
 
-use crate::types::{NeuroRightsPolicy, Scope, TokenKind, UpdateProposal};
+pub use crate::decision::{DecisionOutcome, DecisionReport};
 
-/// Final decision of the sovereignty core.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum DecisionOutcome {
-    Allowed,
-    Rejected { reason: String },
-}
+use crate::checkpoint::{Checkpoint, CheckpointStore, CHECKPOINT_VERSION};
+use crate::decision::{Finding, Measurement, ReasonCode, Rejection};
+use crate::guards::{
+    stake_finding, GuardContext, GuardInput, GuardPipeline, GuardRun, UpdateInput, Verdict,
+    LOG_STAGE,
+};
+use crate::lifecycle::{LifecycleError, ProposalLifecycle};
+use crate::types::{NeuroRightsPolicy, UpdateProposal};
+
+/// Check made on an evolve record before its pipeline runs.
+const HEXSTAMP_CHECK: &str = "hexstampcheck";
+
+/// Check that an incoming evolve record is a fresh proposal.
+const INTAKE_CHECK: &str = "intakecheck";
+
+/// Checks made on NnetUpdates, which do not go through the guard pipeline.
+const NNET_GUARD: &str = "nnetupdateguard";
+
+/// Expiry of proposals deferred for missing signers.
+const DEFERRAL: &str = "deferral";
 
 /// High‑trust sovereignty kernel wired to RoH, neurorights, stake, Tsafe, and donutloop.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -52,10 +82,57 @@ pub struct SovereigntyCore<LW: EvolutionLogWriter> {
 
     // Evolution log writer for .evolve.jsonl.
     evolvelog: LW,
+
+    // The .evolve.jsonl file `evolvelog` appends to.
+    evolvestream: PathBuf,
+
+    // Day-rotated nnet-evolve-YYYYMMDD.jsonl logs, next to the evolve stream.
+    nnetlog: RotatingEvolveLog,
+
+    // Accepted NnetUpdates per model, replayed from the nnet-evolve logs.
+    nnet_tracker: NnetUpdateTracker,
+
+    // Per-model nnet policies; updates of models without one are rejected.
+    nnet_policies: NnetPolicySet,
+
+    // Lifecycle state of every proposal, replayed from the evolve stream.
+    lifecycle: ProposalLifecycle,
+
+    // Right-to-forget requests and signed receipts, next to the evolve stream.
+    forgetlog: ForgetLog,
+
+    // Derived-state checkpoints, in `checkpoints/` next to the evolve stream.
+    checkpoints: CheckpointStore,
+
+    // Records logged since the last checkpoint was saved.
+    since_checkpoint: usize,
+
+    // Checkpoint startup resumed from, and the newer ones it rejected.
+    resumed_from: Option<PathBuf>,
+    rejected_checkpoints: Vec<String>,
+
+    // Proposals startup logged as Allowed because their donutloop entry
+    // was appended but the decision never reached the evolve stream.
+    reconciled: Vec<String>,
+
+    // Guards in manifest order; built-ins only after deserializing.
+    #[serde(skip)]
+    pipeline: GuardPipeline,
+}
+
+/// Records logged between periodic checkpoints.
+const CHECKPOINT_EVERY: usize = 64;
+
+/// Derived state restored from a checkpoint and brought up to date.
+struct Restored {
+    path: PathBuf,
+    lifecycle: ProposalLifecycle,
+    nnet_tracker: NnetUpdateTracker,
 }
 
 impl<LW: EvolutionLogWriter> SovereigntyCore<LW> {
     /// Construct a new sovereignty core, validating all invariants up front.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         roh_model: RohModelShard,
         stake: StakeShard,
@@ -63,7 +140,35 @@ impl<LW: EvolutionLogWriter> SovereigntyCore<LW> {
         viability_kernel: ViabilityKernel,
         rank_weights: RankWeights,
         evolvelog: LW,
+        evolvestream: PathBuf,
+        donutloop: DonutloopLedger,
+    ) -> anyhow::Result<Self> {
+        Self::build(
+            roh_model,
+            stake,
+            neurorights_doc,
+            viability_kernel,
+            rank_weights,
+            evolvelog,
+            evolvestream,
+            donutloop,
+            None,
+        )
+    }
+
+    /// `new`, with lifecycles and nnet budgets taken from `restored` when
+    /// `donutloop` was resumed from the same checkpoint.
+    #[allow(clippy::too_many_arguments)]
+    fn build(
+        roh_model: RohModelShard,
+        stake: StakeShard,
+        neurorights_doc: NeurorightsPolicyDocument,
+        viability_kernel: ViabilityKernel,
+        rank_weights: RankWeights,
+        evolvelog: LW,
+        evolvestream: PathBuf,
         donutloop: DonutloopLedger,
+        restored: Option<Restored>,
     ) -> anyhow::Result<Self> {
         // 1. RoH invariants (ceiling 0.30, weights ≥ 0, sum 1.0, exactly one active row). [file:1]
         roh_model.validate_invariants()?;
@@ -84,12 +189,28 @@ impl<LW: EvolutionLogWriter> SovereigntyCore<LW> {
         let neurorights = NeuroRightsPolicy::from_document(&neurorights_doc)?;
 
         // 6. Donutloop chain invariants: append‑only, hash‑linked, consistent prev_hexstamp. [file:1]
+        //    A resumed ledger holds only the entries from its checkpoint on.
         donutloop.validate_chain()?;
 
         // 7. Viability kernel polytope invariants. [file:3]
         viability_kernel.validate()?;
 
-        Ok(Self {
+        // 8. Daily NnetUpdate budgets survive restarts: replay accepted updates.
+        // 9. Proposal lifecycles resume where the evolve stream left them.
+        let nnetlog = RotatingEvolveLog::nnet(log_dir(&evolvestream));
+        let (nnet_tracker, lifecycle, resumed_from) = match restored {
+            Some(r) => (r.nnet_tracker, r.lifecycle, Some(r.path)),
+            None => {
+                let history = nnetlog.read::<NnetUpdateRecord>()?;
+                let tracker =
+                    NnetUpdateTracker::replay(history.records.iter().map(|r| &r.record))?;
+                (tracker, replay_lifecycle(&evolvestream)?, None)
+            }
+        };
+        let forgetlog = ForgetLog::new(nnetlog.dir().join("forget.jsonl"));
+        let checkpoints = CheckpointStore::new(nnetlog.dir().join("checkpoints"));
+
+        let mut core = Self {
             roh_model,
             stake,
             neurorights_doc,
@@ -99,101 +220,714 @@ impl<LW: EvolutionLogWriter> SovereigntyCore<LW> {
             viability_kernel,
             rank_weights,
             evolvelog,
-        })
+            evolvestream,
+            nnetlog,
+            nnet_tracker,
+            nnet_policies: NnetPolicySet::default(),
+            lifecycle,
+            forgetlog,
+            checkpoints,
+            since_checkpoint: 0,
+            resumed_from,
+            rejected_checkpoints: Vec::new(),
+            reconciled: Vec::new(),
+            pipeline: GuardPipeline::default(),
+        };
+        // 10. A crash between the donutloop append and the Allowed record
+        //     leaves an entry whose proposal never concluded.
+        core.reconcile_ledger()?;
+        Ok(core)
+    }
+
+    /// Log Allowed for every proposal the donutloop holds an entry for but
+    /// whose lifecycle stopped short of a decision. The entry is only
+    /// appended once every guard passed, so Allowed is what was decided.
+    fn reconcile_ledger(&mut self) -> anyhow::Result<()> {
+        let mut unconcluded = Vec::new();
+        for entry in self.donutloop.entries() {
+            let rec = self.lifecycle.latest(&entry.proposal_id).with_context(|| {
+                format!(
+                    "donutloop entry `{}` has no evolve record for proposal `{}`",
+                    entry.entry_id, entry.proposal_id
+                )
+            })?;
+            if matches!(
+                rec.decision,
+                Decision::Proposed | Decision::Pending | Decision::Deferred
+            ) {
+                unconcluded.push(rec.clone());
+            }
+        }
+        for rec in unconcluded {
+            self.transition(&rec, Decision::Allowed)?;
+            self.reconciled.push(rec.proposalid);
+        }
+        Ok(())
+    }
+
+    /// Boot from `neuro-workspace.manifest.aln`: load every core shard the
+    /// manifest names, check its invariants against them, then validate as `new`.
+    /// The guard pipeline follows the manifest's `guardpipeline order`.
+    /// Derived state comes from the newest checkpoint that still matches the
+    /// donutloop chain and logs, plus the entries after it; without one the
+    /// full history is replayed and a checkpoint saved for the next start.
+    pub fn from_manifest(
+        path: impl AsRef<Path>,
+        viability_kernel: ViabilityKernel,
+        rank_weights: RankWeights,
+        evolvelog: LW,
+    ) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let manifest = WorkspaceManifest::load(path)
+            .with_context(|| format!("loading manifest {}", path.display()))?;
+        manifest.require_shard_files()?;
+
+        let pipeline = GuardPipeline::from_order(&manifest.guard_pipeline).with_context(|| {
+            format!("manifest `{}` has an invalid guard pipeline", manifest.manifest_id)
+        })?;
+
+        let shards = &manifest.coreshards;
+        let roh_model =
+            RohModelShard::load(&shards.rohmodel).context("loading coreshard `rohmodel`")?;
+        let stake = StakeShard::load(&shards.stake).context("loading coreshard `stake`")?;
+        let neurorights_doc = NeurorightsPolicyDocument::load(&shards.neurorights)
+            .context("loading coreshard `neurorights`")?;
+        create_evolvestream(&shards.evolvestream)?;
+        let checkpoints = CheckpointStore::new(log_dir(&shards.evolvestream).join("checkpoints"));
+        let mut rejected = Vec::new();
+        let mut restored = None;
+        for path in checkpoints.list()? {
+            match restore(&path, &shards.donutloop, &shards.evolvestream) {
+                Ok(r) => {
+                    restored = Some(r);
+                    break;
+                }
+                Err(e) => rejected.push(format!("{}: {e:#}", path.display())),
+            }
+        }
+        let (donutloop, restored) = match restored {
+            Some((ledger, r)) => (ledger, Some(r)),
+            None => {
+                if let Some(dir) = shards.donutloop.parent() {
+                    fs::create_dir_all(dir)
+                        .with_context(|| format!("creating {}", dir.display()))?;
+                }
+                let ledger =
+                    DonutloopLedger::open_or_create(&shards.donutloop, &manifest.subject_id)
+                        .context("loading coreshard `donutloop`")?;
+                (ledger, None)
+            }
+        };
+        manifest
+            .check_shards(&roh_model, &stake, &neurorights_doc, &donutloop)
+            .with_context(|| {
+                format!("manifest `{}` is inconsistent with its shards", manifest.manifest_id)
+            })?;
+
+        let mut core = Self::build(
+            roh_model,
+            stake,
+            neurorights_doc,
+            viability_kernel,
+            rank_weights,
+            evolvelog,
+            shards.evolvestream.clone(),
+            donutloop,
+            restored,
+        )?;
+        core.rejected_checkpoints = rejected;
+        core.pipeline = pipeline;
+        if core.resumed_from.is_none() {
+            core.try_checkpoint();
+        }
+        Ok(core)
     }
 
     /// Evaluate a low‑level evolution record (canonical evolve stream path). [file:1][file:3]
+    /// The proposal is logged as Proposed, runs the guard pipeline, then is
+    /// logged as Allowed, Rejected, or Deferred when only signers are missing
+    /// (see `attach_signatures`). The report carries every guard's verdict.
+    /// Claimed `signer_roles` are replaced by the roles its signatures prove.
     pub fn evaluate_evolution_record(
         &mut self,
-        proposal: EvolutionProposalRecord,
-    ) -> anyhow::Result<DecisionOutcome> {
-        // 1. RoH guard (monotone + ceiling).
-        if proposal.roh_after > self.roh_ceiling + f32::EPSILON {
-            return Ok(DecisionOutcome::Rejected {
-                reason: "RoH_after exceeds global ceiling (0.30)".into(),
-            });
+        mut proposal: EvolutionProposalRecord,
+    ) -> anyhow::Result<DecisionReport> {
+        // 0. The record must be exactly what was stamped.
+        if let Err(e) = proposal.verify_hexstamp() {
+            return Ok(DecisionReport::rejected(
+                HEXSTAMP_CHECK,
+                Finding::new(
+                    ReasonCode::HexstampMismatch,
+                    format!("proposal {}: {e}", proposal.proposalid),
+                ),
+            ));
         }
-        if proposal.roh_after > proposal.roh_before + f32::EPSILON {
-            return Ok(DecisionOutcome::Rejected {
-                reason: "RoH_after > RoH_before (no relaxation allowed)".into(),
-            });
+        // Decisions and tombstones are the core's to write, not the caller's.
+        let intake = if proposal.is_forgotten() {
+            Some((ReasonCode::AlreadyForgotten, "is already forgotten".to_string()))
+        } else if proposal.decision != Decision::Proposed {
+            let found = format!("arrives as {}, not Proposed", proposal.decision.as_str());
+            Some((ReasonCode::NotProposed, found))
+        } else {
+            None
+        };
+        if let Some((code, reason)) = intake {
+            return Ok(DecisionReport::rejected(
+                INTAKE_CHECK,
+                Finding::new(code, format!("proposal {} {reason}", proposal.proposalid)),
+            ));
         }
+        proposal.signer_roles = self.proven_roles(&proposal);
+        let proposed = self.transition(&proposal, Decision::Proposed)?;
+        let outcome = self.run_pipeline(&proposed, None);
+        self.conclude(&proposed, outcome)
+    }
 
-        // 2. Stake / multisig guard based on scope and signer roles. [file:1]
-        self.stake
-            .check_signers_for_scope(&proposal.scope, &proposal.signer_roles)?;
-
-        // 3. Neurorights guard (dreamstate, non‑commercial, forbidden decision uses, etc.). [file:4]
-        self.neurorights_doc
-            .enforce_for_proposal(&proposal, &self.roh_model)?;
+    /// Undo an applied change (CLAUSE rollbackanytime): build the inverse of
+    /// donutloop entry `entry_id` and evaluate it like any other proposal.
+    /// Envelope and RoH monotonicity do not bind a rollback that returns to
+    /// a recorded state; stake, neurorights and token guards still apply.
+    pub fn rollback(
+        &mut self,
+        entry_id: &str,
+        signatures: Vec<ProposalSignature>,
+    ) -> anyhow::Result<DecisionReport> {
+        let entry = self
+            .donutloop
+            .entry(entry_id)?
+            .with_context(|| format!("donutloop entry `{entry_id}` not found"))?;
+        let original = self
+            .lifecycle
+            .latest(&entry.proposal_id)
+            .with_context(|| format!("no evolve record for proposal `{}`", entry.proposal_id))?;
+        self.lifecycle.check(&original.proposalid, Decision::RolledBack)?;
 
-        // 4. Token guard (SMART vs EVOLVE scopes). [file:3]
-        proposal.enforce_token_scopes(&self.stake)?;
+        let mut rec = entry.rollback_proposal(original, self.donutloop_roh());
+        // Earlier rollback attempts of the same entry may have been rejected.
+        let base = rec.proposalid.clone();
+        let mut attempt = 1;
+        while self.lifecycle.state(&rec.proposalid).is_some() {
+            attempt += 1;
+            rec.proposalid = format!("{base}-{attempt}");
+        }
+        rec.signatures = signatures;
+        rec.timestamp_utc = now_utc();
+        salt_payload(&mut rec)?;
+        rec.stamp();
+        self.evaluate_evolution_record(rec)
+    }
 
-        // 5. Donutloop append with RoH monotone + hashlink check. [file:1]
-        let entry = DonutloopEntry::from_proposal(&proposal);
-        self.donutloop.append(entry)?;
+    /// Roles proven by `rec`'s signatures, as logged in `signer_roles`.
+    fn proven_roles(&self, rec: &impl Signable) -> Vec<String> {
+        self.stake
+            .verified_roles(rec)
+            .iter()
+            .map(|role| role.to_string())
+            .collect()
+    }
 
-        Ok(DecisionOutcome::Allowed)
+    /// RoH of the latest donutloop state.
+    fn donutloop_roh(&self) -> f32 {
+        self.donutloop.head().map_or(0.0, |e| e.roh_after)
     }
 
-    /// Evaluate a high‑level update proposal with Tsafe / CyberRank and neurorights guards. [file:3]
+    /// Evaluate a high‑level update proposal through the same guard pipeline;
+    /// its envelopes and live state feed the Tsafe / CyberRank checks. [file:3]
+    /// Its signatures must meet the scope's multisig rule up front: updates
+    /// are rejected rather than deferred.
     pub fn evaluate_update(
         &mut self,
         proposal: UpdateProposal,
         swarm_state: &SwarmState7D,
         lifeforce: &LifeforceState,
-    ) -> anyhow::Result<DecisionOutcome> {
-        // 1. RoH monotone + neurorights ceiling.
-        if proposal.roh_after > self.neurorights.roh_ceiling + f32::EPSILON {
-            return Ok(DecisionOutcome::Rejected {
-                reason: "RoH_after exceeds neurorights ceiling (0.30)".into(),
-            });
+    ) -> anyhow::Result<DecisionReport> {
+        // Logged to .evolve.jsonl as Proposed, then Allowed or Rejected. [file:3]
+        let mut rec = proposal.to_record(Decision::Proposed, now_utc());
+        rec.signer_roles = self.proven_roles(&proposal);
+        let proposed = self.transition(&rec, Decision::Proposed)?;
+        let update = UpdateInput {
+            proposal: &proposal,
+            swarm_state,
+            lifeforce,
+        };
+        let outcome = self.run_pipeline(&proposed, Some(update));
+        self.conclude(&proposed, outcome)
+    }
+
+    /// Run the guard pipeline on a Proposed or Pending record. If every guard
+    /// passes, the log stage appends it to the donutloop (RoH monotone +
+    /// hashlink check); `conclude` then logs the decision to the evolve stream,
+    /// or startup does if the process stops in between.
+    /// Only a failure to write the ledger is an error.
+    fn run_pipeline(
+        &mut self,
+        proposed: &EvolutionProposalRecord,
+        update: Option<UpdateInput<'_>>,
+    ) -> anyhow::Result<DecisionReport> {
+        let mut trace = Vec::new();
+        let ctx = GuardContext {
+            roh_ceiling: self.roh_ceiling,
+            roh_model: &self.roh_model,
+            stake: &self.stake,
+            neurorights: &self.neurorights_doc,
+            viability_kernel: &self.viability_kernel,
+            rank_weights: &self.rank_weights,
+            donutloop: &self.donutloop,
+            lifecycle: &self.lifecycle,
+        };
+        let is_update = update.is_some();
+        let input = GuardInput {
+            record: proposed,
+            update,
+        };
+        let mut outcome = self.pipeline.run(&ctx, &input, &mut trace);
+        if let (true, DecisionOutcome::Deferred(deferral)) = (is_update, &outcome) {
+            // Parked records are re-run without their envelopes and live state.
+            outcome = DecisionOutcome::Rejected(Rejection::new(
+                &deferral.guard,
+                Finding::new(
+                    ReasonCode::MissingSigners,
+                    format!(
+                        "update proposals cannot be deferred; missing signers {:?}",
+                        deferral.missing_roles
+                    ),
+                ),
+            ));
         }
-        if proposal.roh_after > proposal.roh_before + f32::EPSILON {
-            return Ok(DecisionOutcome::Rejected {
-                reason: "RoH_after > RoH_before (no relaxation allowed)".into(),
+        if outcome.is_allowed() {
+            let mut allowed = proposed.clone();
+            allowed.decision = Decision::Allowed;
+            let verdict = match self.donutloop.append(DonutloopEntry::from_proposal(&allowed)) {
+                Ok(_) => Verdict::Pass,
+                Err(e) => match e.downcast::<ChainError>() {
+                    Ok(broken) => Verdict::Reject(chain_finding(broken)),
+                    Err(e) => return Err(e),
+                },
+            };
+            if let Verdict::Reject(finding) = &verdict {
+                outcome = DecisionOutcome::Rejected(Rejection::new(LOG_STAGE, finding.clone()));
+            }
+            trace.push(GuardRun {
+                guard: LOG_STAGE.to_string(),
+                verdict,
             });
         }
+        Ok(DecisionReport {
+            outcome,
+            trace,
+            dropped_signatures: Vec::new(),
+        })
+    }
 
-        // 2. Envelope monotone: G_new ≥ G_old, D_new ≤ D_old. [file:3]
-        if !proposal.envelopes.is_monotone() {
-            return Ok(DecisionOutcome::Rejected {
-                reason: "Envelope monotonicity violated (no loosening allowed)".into(),
-            });
+    /// Replace the guard pipeline, e.g. with one built from a registry that
+    /// holds deployment-specific guards. Any pipeline runs every mandatory guard.
+    pub fn set_guard_pipeline(&mut self, pipeline: GuardPipeline) {
+        self.pipeline = pipeline;
+    }
+
+    /// Stages every evaluation runs, in order, until one rejects.
+    pub fn guard_pipeline(&self) -> Vec<&'static str> {
+        self.pipeline.names()
+    }
+
+    /// Load the nnet policies NnetUpdates are checked against; their slices
+    /// must fit under the RoH ceiling.
+    pub fn set_nnet_policies(&mut self, policies: NnetPolicySet) -> anyhow::Result<()> {
+        policies.validate(&self.roh_model)?;
+        self.nnet_policies = policies;
+        Ok(())
+    }
+
+    /// Evaluate a neural-network update: the model's nnet policy, signers
+    /// and multisig rule for its stake scope, RoH, token scope, per-update
+    /// effect size, daily parameter-delta budget (both capped by the policy)
+    /// and params hash chain. Every decision is logged to the day's
+    /// nnet-evolve file.
+    pub fn evaluate_nnet_update(
+        &mut self,
+        mut rec: NnetUpdateRecord,
+    ) -> anyhow::Result<DecisionOutcome> {
+        let now = time::OffsetDateTime::now_utc();
+        let outcome = self.check_nnet_update(&rec, now.date())?;
+
+        rec.decision = match outcome {
+            DecisionOutcome::Allowed => Decision::Allowed,
+            // NnetUpdates are never deferred; missing signers reject them.
+            DecisionOutcome::Rejected(_) | DecisionOutcome::Deferred(_) => Decision::Rejected,
+        };
+        rec.timestamp_utc = now_utc();
+        rec.stamp();
+        self.nnetlog.append(&rec)?;
+        if let DecisionOutcome::Allowed = outcome {
+            self.nnet_tracker.accept(&rec, now.date());
+        }
+        self.logged();
+        Ok(outcome)
+    }
+
+    fn check_nnet_update(
+        &self,
+        rec: &NnetUpdateRecord,
+        day: time::Date,
+    ) -> anyhow::Result<DecisionOutcome> {
+        let reject = |finding: Finding| {
+            Ok(DecisionOutcome::Rejected(Rejection::new(NNET_GUARD, finding)))
+        };
+        if let Err(e) = rec.verify_hexstamp() {
+            return reject(Finding::new(
+                ReasonCode::HexstampMismatch,
+                format!("update {}: {e}", rec.proposal_id),
+            ));
+        }
+        if rec.subject_id != self.stake.subject_id {
+            return reject(Finding::new(
+                ReasonCode::SubjectNotGoverned,
+                format!("subject {} is not governed here", rec.subject_id),
+            ));
+        }
+        let Some(policy) = self.nnet_policies.get(&rec.model_id) else {
+            return reject(Finding::new(
+                ReasonCode::NnetPolicyMissing,
+                format!("model `{}` has no nnet policy", rec.model_id),
+            ));
+        };
+        for signature in &rec.signatures {
+            if let Err(e) = self.stake.signer_roles(rec, signature) {
+                return reject(stake_finding(e));
+            }
+        }
+        let signers = self.stake.verified_roles(rec);
+        if let Err(e) = self
+            .stake
+            .check_signers_for_scope(rec.scope.stake_scope(), &signers)
+        {
+            return reject(stake_finding(e));
+        }
+        let ceiling =
+            Measurement::at_most("roh_after", rec.roh_after, "roh_ceiling", self.roh_ceiling);
+        if !ceiling.holds() {
+            return reject(
+                Finding::new(
+                    ReasonCode::RohCeilingExceeded,
+                    "RoH_after exceeds global ceiling (0.30)",
+                )
+                .measured(ceiling),
+            );
+        }
+        let monotone =
+            Measurement::at_most("roh_after", rec.roh_after, "roh_before", rec.roh_before);
+        if !monotone.holds() {
+            return reject(
+                Finding::new(
+                    ReasonCode::RohIncreased,
+                    "RoH_after > RoH_before (no relaxation allowed)",
+                )
+                .measured(monotone),
+            );
+        }
+        if let Err(e) = self
+            .stake
+            .check_token_for_scope(rec.scope.stake_scope(), rec.token_kind)
+        {
+            return reject(Finding::new(ReasonCode::TokenNotAllowed, e.to_string()));
+        }
+        if let Err(e) = self.nnet_tracker.check(rec, day, &policy.update_bounds) {
+            return reject(nnet_finding(e));
         }
+        Ok(DecisionOutcome::Allowed)
+    }
+
+    /// The last record logged for a proposal; signers sign its
+    /// `signing_canonical` before `attach_signatures`.
+    pub fn proposal(&self, proposal_id: &str) -> Option<&EvolutionProposalRecord> {
+        self.lifecycle.latest(proposal_id)
+    }
+
+    /// Lifecycle state of a proposal; executors use it to see whether an
+    /// allowed change has been applied.
+    pub fn proposal_state(&self, proposal_id: &str) -> Option<Decision> {
+        self.lifecycle.state(proposal_id)
+    }
 
-        // 3. Neurorights / token kind: dream‑sensitive lifeforce changes require EVOLVE. [file:3][file:4]
-        if self.neurorights.dream_state_sensitive
-            && matches!(proposal.scope, Scope::LifeforceAlteration)
-            && matches!(proposal.token_kind, TokenKind::Smart)
+    /// Executor report: an allowed proposal has been carried out. Applying
+    /// a rollback marks the proposal it undoes RolledBack.
+    /// Fails with `LifecycleError` unless the proposal is Allowed.
+    pub fn mark_applied(&mut self, proposal_id: &str) -> anyhow::Result<()> {
+        self.advance(proposal_id, Decision::Applied)?;
+        let target = match self
+            .lifecycle
+            .latest(proposal_id)
+            .and_then(|rec| rec.rollback_target())
         {
-            return Ok(DecisionOutcome::Rejected {
-                reason: "LifeforceAlteration requires EVOLVE token under dream_state_sensitive neurorights".into(),
-            });
+            Some(entry_id) => self.donutloop.entry(entry_id)?,
+            None => None,
+        };
+        if let Some(undone) = target {
+            self.advance(&undone.proposal_id, Decision::RolledBack)?;
         }
+        Ok(())
+    }
 
-        // 4. Viability kernel: current swarm state must be inside Tsafe region. [file:3]
-        if !self.viability_kernel.is_viable(swarm_state, lifeforce) {
-            return Ok(DecisionOutcome::Rejected {
-                reason: "Current swarm_state outside viability kernel".into(),
-            });
+    fn advance(&mut self, proposal_id: &str, to: Decision) -> anyhow::Result<()> {
+        let latest = self
+            .lifecycle
+            .latest(proposal_id)
+            .cloned()
+            .ok_or_else(|| LifecycleError::UnknownProposal {
+                proposal_id: proposal_id.to_string(),
+            })?;
+        self.transition(&latest, to)?;
+        Ok(())
+    }
+
+    /// Move `rec`'s proposal to `to`: check the step is legal, then log the
+    /// record restamped with the new state and time. Returns the logged record.
+    fn transition(
+        &mut self,
+        rec: &EvolutionProposalRecord,
+        to: Decision,
+    ) -> anyhow::Result<EvolutionProposalRecord> {
+        self.lifecycle.check(&rec.proposalid, to)?;
+        let mut next = rec.clone();
+        next.decision = to;
+        next.timestamp_utc = now_utc();
+        salt_payload(&mut next)?;
+        next.stamp();
+        self.log_record(&next)?;
+        self.lifecycle.record(next.clone());
+        self.logged();
+        Ok(next)
+    }
+
+    /// Count a logged record and save a checkpoint every `CHECKPOINT_EVERY`.
+    /// A failed save is retried on the next record; decisions never depend on it.
+    fn logged(&mut self) {
+        self.since_checkpoint += 1;
+        if self.since_checkpoint >= CHECKPOINT_EVERY {
+            self.try_checkpoint();
         }
+    }
 
-        // 5. CyberRank Tsafe action selection: require at least one safe candidate. [file:3]
-        let candidates: Vec<CandidateAction> = Vec::new();
-        let tsafe_choice =
-            cybernano_vector_cyberrank::tsafe_select(&candidates, &self.rank_weights);
-        if tsafe_choice.is_none() {
-            return Ok(DecisionOutcome::Rejected {
-                reason: "No Tsafe candidate action available for this update".into(),
-            });
+    /// Best effort: a failed save only means the next start replays more,
+    /// so the error is logged and counted rather than returned.
+    fn try_checkpoint(&mut self) {
+        if let Err(e) = self.checkpoint() {
+            tracing::warn!("checkpoint save failed: {e:#}");
+            metrics::CHECKPOINT_FAILURES.inc();
         }
+    }
 
-        // 6. Log to .evolve.jsonl via EvolutionLogWriter (typed, append‑only). [file:3]
-        self.evolvelog.append_from_proposal(&proposal)?;
+    /// Save the current derived state with the ledger and log positions it
+    /// was derived up to.
+    pub fn checkpoint(&mut self) -> anyhow::Result<PathBuf> {
+        let mut checkpoint = Checkpoint {
+            version: CHECKPOINT_VERSION,
+            created_utc: now_utc(),
+            donutloop: self.donutloop.mark()?,
+            evolvestream: LogMark::at_end(&self.evolvestream)?,
+            nnetlog: self.nnetlog.mark()?,
+            lifecycle: self.lifecycle.clone(),
+            nnet_tracker: self.nnet_tracker.clone(),
+            hexstamp: String::new(),
+        };
+        let path = self.checkpoints.save(&mut checkpoint)?;
+        self.since_checkpoint = 0;
+        Ok(path)
+    }
 
-        Ok(DecisionOutcome::Allowed)
+    /// Checkpoint this core was restored from at startup, if any.
+    pub fn resumed_from(&self) -> Option<&Path> {
+        self.resumed_from.as_deref()
+    }
+
+    /// Newer checkpoints startup skipped because they no longer matched the
+    /// ledger or logs, each with the reason.
+    pub fn rejected_checkpoints(&self) -> &[String] {
+        &self.rejected_checkpoints
+    }
+
+    /// Proposals startup concluded as Allowed from their donutloop entries.
+    pub fn reconciled(&self) -> &[String] {
+        &self.reconciled
+    }
+
+    /// Log a proposal's outcome and count it in `metrics`. If the pipeline
+    /// failed with an error the proposal is logged as Rejected and the error
+    /// returned. A deferral expires `deferral_ttl_hours` after the first one.
+    fn conclude(
+        &mut self,
+        proposed: &EvolutionProposalRecord,
+        report: anyhow::Result<DecisionReport>,
+    ) -> anyhow::Result<DecisionReport> {
+        let to = match &report {
+            Ok(report) if report.is_allowed() => Decision::Allowed,
+            Ok(report) if report.deferral().is_some() => Decision::Deferred,
+            _ => Decision::Rejected,
+        };
+        let logged = self.transition(proposed, to)?;
+        let envelope = report
+            .as_ref()
+            .ok()
+            .and_then(|r| r.rejection())
+            .is_some_and(|r| r.code() == ReasonCode::EnvelopeLoosened);
+        metrics::record_decision(&self.stake.subject_id, &logged, to, envelope);
+        let mut report = report?;
+        if let DecisionOutcome::Deferred(deferral) = &mut report.outcome {
+            deferral.expires_utc = format_utc(self.deferral_expiry(&logged)?);
+        }
+        Ok(report)
+    }
+
+    /// Attach `signatures` to a deferred proposal. It is logged as Pending
+    /// with the merged signatures and the roles they prove, and runs the guard
+    /// pipeline again: allowed once the quorum is met, deferred again while
+    /// roles are missing, or rejected. Signatures that do not verify are
+    /// dropped and listed in `dropped_signatures`. A proposal past its
+    /// deferral window is logged as Rejected instead.
+    pub fn attach_signatures(
+        &mut self,
+        proposal_id: &str,
+        signatures: Vec<ProposalSignature>,
+    ) -> anyhow::Result<DecisionReport> {
+        let mut rec = self
+            .lifecycle
+            .latest(proposal_id)
+            .cloned()
+            .ok_or_else(|| LifecycleError::UnknownProposal {
+                proposal_id: proposal_id.to_string(),
+            })?;
+        if rec.decision != Decision::Deferred {
+            return Err(LifecycleError::IllegalTransition {
+                proposal_id: proposal_id.to_string(),
+                from: rec.decision,
+                to: Decision::Pending,
+            }
+            .into());
+        }
+        let expires = self.deferral_expiry(&rec)?;
+        if expires <= time::OffsetDateTime::now_utc() {
+            self.transition(&rec, Decision::Rejected)?;
+            return Ok(DecisionReport::rejected(
+                DEFERRAL,
+                Finding::new(
+                    ReasonCode::DeferralExpired,
+                    format!("proposal {proposal_id} expired at {}", format_utc(expires)),
+                ),
+            ));
+        }
+        let mut dropped = Vec::new();
+        for signature in signatures {
+            if rec.signatures.contains(&signature) {
+                continue;
+            }
+            match self.stake.signer_roles(&rec, &signature) {
+                Ok(_) => rec.signatures.push(signature),
+                Err(e) => dropped.push(stake_finding(e)),
+            }
+        }
+        rec.signer_roles = self.proven_roles(&rec);
+        let pending = self.transition(&rec, Decision::Pending)?;
+        let report = self.run_pipeline(&pending, None);
+        let mut report = self.conclude(&pending, report)?;
+        report.dropped_signatures = dropped;
+        Ok(report)
+    }
+
+    /// Reject every deferred proposal whose window has passed, logging each
+    /// to the evolve stream. Returns the expired proposal ids.
+    pub fn expire_deferred(&mut self) -> anyhow::Result<Vec<String>> {
+        let now = time::OffsetDateTime::now_utc();
+        let mut expired = Vec::new();
+        for rec in self.lifecycle.in_state(Decision::Deferred) {
+            if self.deferral_expiry(rec)? <= now {
+                expired.push(rec.clone());
+            }
+        }
+        for rec in &expired {
+            self.transition(rec, Decision::Rejected)?;
+        }
+        Ok(expired.into_iter().map(|rec| rec.proposalid).collect())
+    }
+
+    /// When a deferred proposal expires: `deferral_ttl_hours` from the stake
+    /// shard after it was first deferred. Re-deferrals do not extend it.
+    fn deferral_expiry(
+        &self,
+        rec: &EvolutionProposalRecord,
+    ) -> anyhow::Result<time::OffsetDateTime> {
+        let deferred_utc = self
+            .lifecycle
+            .deferred_since(&rec.proposalid)
+            .unwrap_or(&rec.timestamp_utc);
+        let deferred = time::OffsetDateTime::parse(
+            deferred_utc,
+            &time::format_description::well_known::Rfc3339,
+        )
+        .with_context(|| format!("proposal {}: bad timestamp_utc", rec.proposalid))?;
+        Ok(deferred + time::Duration::hours(self.stake.deferral_ttl_hours.into()))
+    }
+
+    /// Open a right-to-forget request for `subject_id`; it must be carried
+    /// out with `forget` within the policy's `forget_sla_hours`.
+    pub fn request_forget(&self, subject_id: &str) -> anyhow::Result<ForgetRequest> {
+        if !self.neurorights_doc.right_to_forget.enabled {
+            anyhow::bail!(
+                "right_to_forget is not enabled for {}",
+                self.neurorights_doc.subject_id
+            );
+        }
+        self.forgetlog.request(subject_id, now_utc())
+    }
+
+    /// Carry out a forget request: tombstone the payload of the subject's
+    /// evolve records, keeping their commitments and hexstamps, and log a
+    /// receipt signed with `key` that also names the nnet logs left as is.
+    pub fn forget(&mut self, request_id: &str, key: &SigningKey) -> anyhow::Result<ForgetReceipt> {
+        let request = self.forgetlog.pending(request_id)?;
+        let completed_utc = now_utc();
+        let records = if self.evolvestream.exists() {
+            forget::redact_evolve_log(&self.evolvestream, &request.subject_id, &completed_utc)?
+        } else {
+            Vec::new()
+        };
+        // NnetUpdate records are stamped whole, with nothing to tombstone
+        // under; the receipt names the day files that hold the subject's.
+        let mut not_covered: Vec<PathBuf> = self
+            .nnetlog
+            .read::<NnetUpdateRecord>()?
+            .records
+            .into_iter()
+            .filter(|r| r.record.subject_id == request.subject_id)
+            .map(|r| r.path)
+            .collect();
+        not_covered.dedup();
+        self.lifecycle = replay_lifecycle(&self.evolvestream)?;
+        // Older checkpoints hold the payloads just redacted.
+        self.checkpoints.clear()?;
+        self.checkpoint()?;
+
+        let mut receipt = ForgetReceipt {
+            request_id: request.request_id,
+            subject_id: request.subject_id,
+            requested_utc: request.requested_utc,
+            completed_utc,
+            records,
+            donutloop_head: self.donutloop.head_hexstamp().to_string(),
+            not_covered,
+            public_key: String::new(),
+            signature: String::new(),
+        };
+        receipt.sign(key);
+        self.forgetlog.complete(&receipt)?;
+        Ok(receipt)
+    }
+
+    /// Forget requests past the policy's `forget_sla_hours`, open or completed late.
+    pub fn overdue_forgets(&self) -> anyhow::Result<Vec<OverdueForget>> {
+        self.forgetlog.sweep(
+            time::OffsetDateTime::now_utc(),
+            self.neurorights_doc.right_to_forget.forget_sla_hours,
+        )
     }
 
     /// Expose read‑only view of RoH ceiling for metrics and external guards. [file:3]
@@ -205,4 +939,141 @@ impl<LW: EvolutionLogWriter> SovereigntyCore<LW> {
     pub fn neurorights_policy(&self) -> &NeurorightsPolicyDocument {
         &self.neurorights_doc
     }
+
+    /// Latest donutloop entry: the state every new proposal is checked against.
+    pub fn ledger_head(&self) -> Option<&DonutloopEntry> {
+        self.donutloop.head()
+    }
+
+    /// Append one record to the evolve stream and fsync it.
+    fn log_record(&self, rec: &EvolutionProposalRecord) -> anyhow::Result<()> {
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&self.evolvestream)
+            .with_context(|| format!("opening evolve stream {}", self.evolvestream.display()))?;
+        self.evolvelog.append(&mut file, rec)?;
+        file.sync_data()?;
+        Ok(())
+    }
+}
+
+/// Create an empty evolve stream, and its directory, unless one exists.
+fn create_evolvestream(path: &Path) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("creating evolve stream {}", path.display()))?;
+    Ok(())
+}
+
+/// Salt `rec` before stamping it, so an OS without entropy fails the write
+/// instead of logging a record that cannot verify.
+fn salt_payload(rec: &mut EvolutionProposalRecord) -> anyhow::Result<()> {
+    rec.salt_payload()
+        .map_err(|e| anyhow::anyhow!("drawing a payload salt for `{}`: {e}", rec.proposalid))
+}
+
+/// A donutloop refusal as a finding; RoH rising above the ledger head is
+/// measured against it.
+fn chain_finding(e: ChainError) -> Finding {
+    let reason = e.to_string();
+    match e.kind {
+        ChainErrorKind::RohIncrease { before, after } => {
+            Finding::new(ReasonCode::RohIncreased, reason)
+                .measured(Measurement::at_most("roh_after", after, "roh_before", before))
+        }
+        ChainErrorKind::RohIncreaseAcross { previous, after } => {
+            Finding::new(ReasonCode::RohIncreased, reason).measured(Measurement::at_most(
+                "roh_after",
+                after,
+                "donutloop_head_roh",
+                previous,
+            ))
+        }
+        ChainErrorKind::DuplicateEntryId => Finding::new(ReasonCode::DuplicateEntry, reason),
+        ChainErrorKind::BrokenLink { .. } | ChainErrorKind::HexstampMismatch { .. } => {
+            Finding::new(ReasonCode::DonutloopRejected, reason)
+        }
+    }
+}
+
+fn nnet_finding(e: NnetUpdateError) -> Finding {
+    let reason = e.to_string();
+    match e {
+        NnetUpdateError::WrongKind(_)
+        | NnetUpdateError::BadTimestamp(_)
+        | NnetUpdateError::InvalidDelta { .. } => {
+            Finding::new(ReasonCode::NnetInvalidUpdate, reason)
+        }
+        NnetUpdateError::EffectSizeExceeded { delta, bound } => {
+            Finding::new(ReasonCode::NnetEffectSizeExceeded, reason).measured(
+                Measurement::at_most("param_delta_l2", delta, "max_effect_size_l2", bound),
+            )
+        }
+        NnetUpdateError::DailyBudgetExceeded { total, budget, .. } => {
+            Finding::new(ReasonCode::NnetDailyBudgetExceeded, reason).measured(
+                Measurement::at_most("daily_param_delta", total, "daily_budget", budget),
+            )
+        }
+        NnetUpdateError::ParamsHashMismatch { .. } => {
+            Finding::new(ReasonCode::NnetParamsHashMismatch, reason)
+        }
+    }
+}
+
+/// Directory of the evolve stream, where the nnet logs, forget log and
+/// checkpoints live too.
+fn log_dir(evolvestream: &Path) -> PathBuf {
+    evolvestream.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// Resume the ledger at checkpoint `path` and replay the evolve and nnet
+/// records logged after it. Fails if any position no longer matches.
+fn restore(
+    path: &Path,
+    donutloop: &Path,
+    evolvestream: &Path,
+) -> anyhow::Result<(DonutloopLedger, Restored)> {
+    let checkpoint = CheckpointStore::load(path)?;
+    let ledger = DonutloopLedger::resume(donutloop, &checkpoint.donutloop)?;
+    let mut lifecycle = checkpoint.lifecycle;
+    lifecycle.extend(checkpoint.evolvestream.records_after(evolvestream)?)?;
+    let nnetlog = RotatingEvolveLog::nnet(log_dir(evolvestream));
+    let mut nnet_tracker = checkpoint.nnet_tracker;
+    nnet_tracker.extend(&nnetlog.read_after::<NnetUpdateRecord>(checkpoint.nnetlog.as_ref())?)?;
+    Ok((
+        ledger,
+        Restored {
+            path: path.to_path_buf(),
+            lifecycle,
+            nnet_tracker,
+        },
+    ))
+}
+
+/// Replay proposal lifecycles from the evolve stream, if it exists yet.
+fn replay_lifecycle(evolvestream: &Path) -> anyhow::Result<ProposalLifecycle> {
+    if !evolvestream.exists() {
+        return Ok(ProposalLifecycle::default());
+    }
+    let file = File::open(evolvestream)
+        .with_context(|| format!("opening evolve stream {}", evolvestream.display()))?;
+    let records = JsonlEvolutionLog
+        .records(BufReader::new(file))
+        .map(|r| r.map(|(_, rec)| rec))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ProposalLifecycle::replay(records)?)
+}
+
+fn now_utc() -> String {
+    format_utc(time::OffsetDateTime::now_utc())
+}
+
+fn format_utc(t: time::OffsetDateTime) -> String {
+    t.format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
 }
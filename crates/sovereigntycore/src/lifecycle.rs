@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use organiccpualn::evolvestream::{Decision, EvolutionProposalRecord};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum LifecycleError {
+    #[error("proposal `{proposal_id}` is unknown")]
+    UnknownProposal { proposal_id: String },
+    #[error("proposal `{proposal_id}` cannot move from {from} to {to}")]
+    IllegalTransition {
+        proposal_id: String,
+        from: Decision,
+        to: Decision,
+    },
+    #[error("proposal `{proposal_id}` must enter as Proposed, not {to}")]
+    NotProposed { proposal_id: String, to: Decision },
+}
+
+/// Legal lifecycle steps:
+/// Proposed -> Pending (signatures) -> Allowed | Rejected | Deferred,
+/// Deferred -> Pending | Allowed | Rejected, Allowed -> Applied -> RolledBack.
+/// Rejected and RolledBack are final.
+pub fn can_transition(from: Decision, to: Decision) -> bool {
+    use Decision::*;
+    matches!(
+        (from, to),
+        (Proposed, Pending | Allowed | Rejected | Deferred)
+            | (Pending, Allowed | Rejected | Deferred)
+            | (Deferred, Pending | Allowed | Rejected)
+            | (Allowed, Applied)
+            | (Applied, RolledBack)
+    )
+}
+
+/// Current state of every proposal, with the last record logged for it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProposalLifecycle {
+    proposals: BTreeMap<String, EvolutionProposalRecord>,
+    /// When each proposal was first deferred; re-deferrals keep it.
+    deferred_utc: BTreeMap<String, String>,
+}
+
+impl ProposalLifecycle {
+    /// Rebuild from the evolve stream; every logged step must be legal.
+    pub fn replay(
+        records: impl IntoIterator<Item = EvolutionProposalRecord>,
+    ) -> Result<Self, LifecycleError> {
+        let mut lifecycle = Self::default();
+        lifecycle.extend(records)?;
+        Ok(lifecycle)
+    }
+
+    /// Replay records logged after the state this was restored from.
+    pub fn extend(
+        &mut self,
+        records: impl IntoIterator<Item = EvolutionProposalRecord>,
+    ) -> Result<(), LifecycleError> {
+        for rec in records {
+            self.check(&rec.proposalid, rec.decision)?;
+            self.record(rec);
+        }
+        Ok(())
+    }
+
+    pub fn state(&self, proposal_id: &str) -> Option<Decision> {
+        self.proposals.get(proposal_id).map(|r| r.decision)
+    }
+
+    /// The last record logged for `proposal_id`.
+    pub fn latest(&self, proposal_id: &str) -> Option<&EvolutionProposalRecord> {
+        self.proposals.get(proposal_id)
+    }
+
+    /// When `proposal_id` was first logged as Deferred, if ever.
+    pub fn deferred_since(&self, proposal_id: &str) -> Option<&str> {
+        self.deferred_utc.get(proposal_id).map(String::as_str)
+    }
+
+    /// Latest records of the proposals currently in `state`.
+    pub fn in_state(&self, state: Decision) -> impl Iterator<Item = &EvolutionProposalRecord> {
+        self.proposals.values().filter(move |r| r.decision == state)
+    }
+
+    /// Whether `proposal_id` may move to `to`; new proposals enter as Proposed.
+    pub fn check(&self, proposal_id: &str, to: Decision) -> Result<(), LifecycleError> {
+        match self.state(proposal_id) {
+            None if to == Decision::Proposed => Ok(()),
+            None => Err(LifecycleError::NotProposed {
+                proposal_id: proposal_id.to_string(),
+                to,
+            }),
+            Some(from) if can_transition(from, to) => Ok(()),
+            Some(from) => Err(LifecycleError::IllegalTransition {
+                proposal_id: proposal_id.to_string(),
+                from,
+                to,
+            }),
+        }
+    }
+
+    /// Record a logged step; call only after `check` passed.
+    pub fn record(&mut self, rec: EvolutionProposalRecord) {
+        if rec.decision == Decision::Deferred {
+            self.deferred_utc
+                .entry(rec.proposalid.clone())
+                .or_insert_with(|| rec.timestamp_utc.clone());
+        }
+        self.proposals.insert(rec.proposalid.clone(), rec);
+    }
+}
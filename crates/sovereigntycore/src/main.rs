@@ -0,0 +1,86 @@
+//! `sovereigntycore` service binary; see `sovereigntycore::server`.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::Parser;
+use cybernano_vector_cyberrank::RankWeights;
+use cybernano_viability_kernel::ViabilityKernel;
+use organiccpualn::evolvestream::JsonlEvolutionLog;
+use organiccpualn::nnetfs::NnetfsIndex;
+use organiccpualn::nnetpolicy::NnetPolicySet;
+use serde::de::DeserializeOwned;
+use sovereigntycore::server::{self, AppState, Core};
+use sovereigntycore::SovereigntyCore;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// Workspace manifest naming the core shards and guard pipeline.
+    #[arg(long)]
+    manifest: PathBuf,
+    /// Viability kernel (JSON) for the Tsafe check on updates.
+    #[arg(long, default_value = "/config/viability-kernel.json")]
+    viability_kernel: PathBuf,
+    /// CyberRank weights (JSON) for Tsafe action selection.
+    #[arg(long, default_value = "/config/rank-weights.json")]
+    rank_weights: PathBuf,
+    /// `.nnetfs-index.aln` naming each model's nnet policy. Without it every
+    /// NnetUpdate is rejected.
+    #[arg(long)]
+    nnetfs_index: Option<PathBuf>,
+    #[arg(long, default_value = "0.0.0.0:8080")]
+    listen: SocketAddr,
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_str(&text).with_context(|| format!("parsing {}", path.display()))
+}
+
+fn load_core(args: &Args) -> anyhow::Result<Core> {
+    let kernel: ViabilityKernel = read_json(&args.viability_kernel)?;
+    let weights: RankWeights = read_json(&args.rank_weights)?;
+    let mut core =
+        SovereigntyCore::from_manifest(&args.manifest, kernel, weights, JsonlEvolutionLog)?;
+    if let Some(path) = &args.nnetfs_index {
+        let index =
+            NnetfsIndex::load(path).with_context(|| format!("loading {}", path.display()))?;
+        core.set_nnet_policies(NnetPolicySet::from_index(&index)?)?;
+    }
+    match core.resumed_from() {
+        Some(path) => tracing::info!("resumed from checkpoint {}", path.display()),
+        None => tracing::info!("replayed full history"),
+    }
+    for skipped in core.rejected_checkpoints() {
+        tracing::warn!("skipped checkpoint {skipped}");
+    }
+    for proposal_id in core.reconciled() {
+        tracing::warn!("concluded {proposal_id} as Allowed from its donutloop entry");
+    }
+    Ok(core)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .init();
+    let args = Args::parse();
+
+    let state = AppState::default();
+    let listener = tokio::net::TcpListener::bind(args.listen).await?;
+    tracing::info!("starting sovereigntycore on {}", args.listen);
+    let app = server::router(state.clone());
+    let server = tokio::spawn(async move { axum::serve(listener, app).await });
+
+    // A core that fails to load exits the process; nothing is served half-loaded.
+    let core = tokio::task::spawn_blocking(move || load_core(&args)).await??;
+    tracing::info!("core ready, RoH ceiling {}", core.roh_ceiling());
+    state.set_core(core);
+
+    server.await??;
+    Ok(())
+}
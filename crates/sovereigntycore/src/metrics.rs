@@ -1,6 +1,8 @@
 use once_cell::sync::Lazy;
+use organiccpualn::evolvestream::{Decision, EvolutionProposalRecord};
 use prometheus::{
-    register_gauge_vec, register_int_counter_vec, GaugeVec, IntCounterVec,
+    register_gauge_vec, register_int_counter, register_int_counter_vec, GaugeVec, IntCounter,
+    IntCounterVec,
 };
 
 // RoH scalar per subject
@@ -29,3 +31,40 @@ pub static EVOLUTION_DECISIONS: Lazy<IntCounterVec> = Lazy::new(|| {
         &["subject_id", "decision"]
     ).expect("metric registered")
 });
+
+// Best-effort checkpoint saves that failed
+pub static CHECKPOINT_FAILURES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "sovereignty_checkpoint_failures_total",
+        "Count of best-effort checkpoint saves that failed"
+    ).expect("metric registered")
+});
+
+/// Label for decisions on subjects this core does not govern; their ids
+/// come from clients and would make label sets unbounded.
+pub const UNGOVERNED_SUBJECT: &str = "ungoverned";
+
+/// Count a logged decision on `rec`; envelope rejections also count as
+/// violations for the scope. Only `governed` is labelled by its id, and
+/// only its RoH is tracked.
+pub fn record_decision(
+    governed: &str,
+    rec: &EvolutionProposalRecord,
+    decision: Decision,
+    envelope_violation: bool,
+) {
+    let subject = if rec.subjectid == governed {
+        ROH_GAUGE.with_label_values(&[governed]).set(rec.roh_after.into());
+        governed
+    } else {
+        UNGOVERNED_SUBJECT
+    };
+    EVOLUTION_DECISIONS
+        .with_label_values(&[subject, &decision.to_string()])
+        .inc();
+    if envelope_violation {
+        ENVELOPE_VIOLATION_COUNTER
+            .with_label_values(&[subject, rec.scope.as_str()])
+            .inc();
+    }
+}
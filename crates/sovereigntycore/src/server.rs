@@ -0,0 +1,186 @@
+//! HTTP service in front of one `SovereigntyCore`. Every request takes the
+//! same lock, so evaluations and their ledger appends never interleave.
+
+use std::sync::{Arc, OnceLock};
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use cybernano_viability_kernel::{LifeforceState, SwarmState7D};
+use organiccpualn::donutloopledger::DonutloopEntry;
+use organiccpualn::evolvestream::{EvolutionProposalRecord, JsonlEvolutionLog};
+use organiccpualn::neurorights::NeurorightsPolicyDocument;
+use organiccpualn::evolvestream::SchemaError;
+use organiccpualn::hexstamp::HexstampError;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::lifecycle::LifecycleError;
+use crate::types::UpdateProposal;
+use crate::{DecisionReport, SovereigntyCore};
+
+pub type Core = SovereigntyCore<JsonlEvolutionLog>;
+
+/// The core, once loaded. Loading replays history past the newest
+/// checkpoint, so the server answers `/health` before it is ready.
+#[derive(Clone, Default)]
+pub struct AppState {
+    core: Arc<OnceLock<Arc<Mutex<Core>>>>,
+}
+
+impl AppState {
+    /// Install the loaded core; the service is ready from then on.
+    pub fn set_core(&self, core: Core) {
+        let _ = self.core.set(Arc::new(Mutex::new(core)));
+    }
+
+    async fn with_core<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut Core) -> anyhow::Result<T> + Send + 'static,
+    ) -> Result<Json<T>, ApiError> {
+        let core = Arc::clone(self.core.get().ok_or(ApiError::NotReady)?);
+        let mut core = core.lock_owned().await;
+        // Evaluations fsync the logs and ledger; keep that off the async workers.
+        let value = tokio::task::spawn_blocking(move || f(&mut core))
+            .await
+            .map_err(|e| ApiError::Failed(e.into()))?
+            .map_err(ApiError::Failed)?;
+        Ok(Json(value))
+    }
+}
+
+enum ApiError {
+    NotReady,
+    Failed(anyhow::Error),
+}
+
+/// Status and message for a failed request. Errors the client caused are
+/// reported by their own message; anything else only as an internal error,
+/// since its context names files on the server.
+fn classify(e: &anyhow::Error) -> (StatusCode, String) {
+    if let Some(e) = e.downcast_ref::<LifecycleError>() {
+        return (StatusCode::CONFLICT, e.to_string());
+    }
+    if let Some(e) = e.downcast_ref::<SchemaError>() {
+        return (StatusCode::BAD_REQUEST, e.to_string());
+    }
+    if let Some(e) = e.downcast_ref::<HexstampError>() {
+        return (StatusCode::BAD_REQUEST, e.to_string());
+    }
+    (StatusCode::INTERNAL_SERVER_ERROR, "internal error".into())
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            ApiError::NotReady => (StatusCode::SERVICE_UNAVAILABLE, "core is loading".into()),
+            ApiError::Failed(e) => {
+                let (status, error) = classify(&e);
+                if status.is_server_error() {
+                    tracing::error!("request failed: {e:#}");
+                } else {
+                    tracing::info!("request refused: {e:#}");
+                }
+                (status, error)
+            }
+        };
+        (status, Json(ErrorBody { error })).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+struct EvaluateUpdate {
+    proposal: UpdateProposal,
+    swarm_state: SwarmState7D,
+    lifeforce: LifeforceState,
+}
+
+#[derive(Serialize)]
+struct RohCeiling {
+    roh_ceiling: f32,
+}
+
+async fn evaluate_update(
+    State(state): State<AppState>,
+    Json(req): Json<EvaluateUpdate>,
+) -> Result<Json<DecisionReport>, ApiError> {
+    state
+        .with_core(move |core| {
+            core.evaluate_update(req.proposal, &req.swarm_state, &req.lifeforce)
+        })
+        .await
+}
+
+async fn evaluate_record(
+    State(state): State<AppState>,
+    Json(record): Json<EvolutionProposalRecord>,
+) -> Result<Json<DecisionReport>, ApiError> {
+    state
+        .with_core(move |core| core.evaluate_evolution_record(record))
+        .await
+}
+
+async fn ledger_head(
+    State(state): State<AppState>,
+) -> Result<Json<Option<DonutloopEntry>>, ApiError> {
+    state
+        .with_core(|core| Ok(core.ledger_head().cloned()))
+        .await
+}
+
+async fn policy(
+    State(state): State<AppState>,
+) -> Result<Json<NeurorightsPolicyDocument>, ApiError> {
+    state
+        .with_core(|core| Ok(core.neurorights_policy().clone()))
+        .await
+}
+
+async fn roh_ceiling(State(state): State<AppState>) -> Result<Json<RohCeiling>, ApiError> {
+    state
+        .with_core(|core| {
+            Ok(RohCeiling {
+                roh_ceiling: core.roh_ceiling(),
+            })
+        })
+        .await
+}
+
+async fn metrics() -> Result<impl IntoResponse, ApiError> {
+    let mut text = String::new();
+    prometheus::TextEncoder::new()
+        .encode_utf8(&prometheus::gather(), &mut text)
+        .map_err(|e| ApiError::Failed(e.into()))?;
+    Ok(([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], text))
+}
+
+async fn health() -> &'static str {
+    "ok"
+}
+
+async fn ready(State(state): State<AppState>) -> StatusCode {
+    match state.core.get() {
+        Some(_) => StatusCode::OK,
+        None => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/evaluate_update", post(evaluate_update))
+        .route("/evaluate_record", post(evaluate_record))
+        .route("/ledger/head", get(ledger_head))
+        .route("/policy", get(policy))
+        .route("/roh_ceiling", get(roh_ceiling))
+        .route("/metrics", get(metrics))
+        .route("/health", get(health))
+        .route("/ready", get(ready))
+        .with_state(state)
+}
@@ -1,5 +1,14 @@
+use std::fmt;
+
+use organiccpualn::evolvestream::{
+    self, Decision, EvolutionProposalRecord, EVOLVE_SCHEMA_VERSION,
+};
+use ed25519_dalek::SigningKey;
+use organiccpualn::hexstamp::{Canonical, Hexstamped};
+use organiccpualn::neurorights::NeurorightsPolicyDocument;
+use organiccpualn::proposalsig::{self, ProposalSignature, Signable};
+use organiccpualn::rohmodel::ROH_CEILING;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Scope {
@@ -34,6 +43,16 @@ impl EnvelopeBounds {
     }
 }
 
+impl fmt::Display for EnvelopeBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "envelopes g {}->{} d {}->{}",
+            self.g_old, self.g_new, self.d_old, self.d_new
+        )
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UpdateProposal {
     pub proposal_id: String,
@@ -45,6 +64,12 @@ pub struct UpdateProposal {
     pub roh_after: f32,
     pub envelopes: EnvelopeBounds,
     pub evidence_bundle_ref: String, // points into CRD / donutloop
+    // Declared uses, held to the neurorights allowed uses; none is rejected.
+    #[serde(default)]
+    pub purposes: Vec<String>,
+    // Signers' signatures over `signing_canonical`; see `sign`.
+    #[serde(default)]
+    pub signatures: Vec<ProposalSignature>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -55,3 +80,110 @@ pub struct NeuroRightsPolicy {
     pub forbid_decision_use: Vec<String>,
     pub roh_ceiling: f32, // must be 0.30
 }
+
+impl NeuroRightsPolicy {
+    /// Compile the fast Tsafe view of a validated policy document.
+    pub fn from_document(doc: &NeurorightsPolicyDocument) -> anyhow::Result<Self> {
+        doc.validate()?;
+        Ok(Self {
+            id: doc.model_id.clone().unwrap_or_else(|| doc.subject_id.clone()),
+            noncommercial_neural_data: doc.noncommercial,
+            dream_state_sensitive: doc.dream_state_sensitive,
+            forbid_decision_use: doc.forbid_decision_use.clone(),
+            roh_ceiling: ROH_CEILING,
+        })
+    }
+}
+
+impl UpdateProposal {
+    /// Evolve-stream record for this update, stamped, as logged once decided.
+    /// The evidence reference is logged as `module` and the envelopes as
+    /// `updatekind`; `signatures` are over the update's `signing_canonical`,
+    /// not the record's.
+    pub fn to_record(&self, decision: Decision, timestamp_utc: String) -> EvolutionProposalRecord {
+        let mut rec = EvolutionProposalRecord {
+            schema_version: EVOLVE_SCHEMA_VERSION,
+            proposalid: self.proposal_id.clone(),
+            subjectid: self.subject_id.clone(),
+            scope: self.record_scope(),
+            kind: "UpdateProposal".into(),
+            module: self.evidence_bundle_ref.clone(),
+            updatekind: self.envelopes.to_string(),
+            effectbounds: evolvestream::EffectBounds {
+                l2_delta_norm: self.effect_bounds.l2_delta_norm,
+                irreversible: self.effect_bounds.irreversible,
+            },
+            roh_before: self.roh_before,
+            roh_after: self.roh_after,
+            tsafe_mode: "viability-kernel".into(),
+            signer_roles: Vec::new(),
+            tokenkind: self.record_token_kind(),
+            decision,
+            hexstamp: String::new(),
+            timestamp_utc,
+            purposes: self.purposes.clone(),
+            data_access: Vec::new(),
+            payload_salt: String::new(),
+            payload_commitment: String::new(),
+            signatures: self.signatures.clone(),
+            forgotten_utc: None,
+        };
+        rec.stamp();
+        rec
+    }
+
+    /// Add `address`'s signature over `signing_canonical`; the stake guard
+    /// checks them against the scope's multisig rule.
+    pub fn sign(&mut self, address: &str, key: &SigningKey) {
+        let signature = proposalsig::sign(self, address, key);
+        self.signatures.push(signature);
+    }
+
+    fn record_scope(&self) -> evolvestream::Scope {
+        match self.scope {
+            Scope::DayToDayTuning => evolvestream::Scope::DayToDayTuning,
+            Scope::ArchChange => evolvestream::Scope::ArchChange,
+            Scope::LifeforceAlteration => evolvestream::Scope::LifeforceAlteration,
+        }
+    }
+
+    fn record_token_kind(&self) -> evolvestream::TokenKind {
+        match self.token_kind {
+            TokenKind::Smart => evolvestream::TokenKind::Smart,
+            TokenKind::Evolve => evolvestream::TokenKind::Evolve,
+        }
+    }
+}
+
+impl Signable for UpdateProposal {
+    /// Every field but the signatures, envelopes and evidence reference included.
+    fn signing_canonical(&self) -> Canonical {
+        Canonical::new("update-proposal-signing", 1)
+            .str("proposal_id", &self.proposal_id)
+            .str("subject_id", &self.subject_id)
+            .str("scope", self.record_scope().as_str())
+            .str("token_kind", self.record_token_kind().as_str())
+            .f32("effect_bounds.l2_delta_norm", self.effect_bounds.l2_delta_norm)
+            .bool("effect_bounds.irreversible", self.effect_bounds.irreversible)
+            .f32("roh_before", self.roh_before)
+            .f32("roh_after", self.roh_after)
+            .f32("envelopes.g_old", self.envelopes.g_old)
+            .f32("envelopes.g_new", self.envelopes.g_new)
+            .f32("envelopes.d_old", self.envelopes.d_old)
+            .f32("envelopes.d_new", self.envelopes.d_new)
+            .str("evidence_bundle_ref", &self.evidence_bundle_ref)
+            .list("purposes", &self.purposes)
+    }
+
+    fn proposal_id(&self) -> &str {
+        &self.proposal_id
+    }
+
+    fn subject_id(&self) -> &str {
+        &self.subject_id
+    }
+
+    fn signatures(&self) -> &[ProposalSignature] {
+        &self.signatures
+    }
+}
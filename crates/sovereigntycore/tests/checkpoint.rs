@@ -0,0 +1,76 @@
+mod common;
+
+use organiccpualn::evolvestream::Decision;
+
+#[test]
+fn restores_from_checkpoint_and_falls_back_when_the_ledger_changed() {
+    let dir = common::scratch("checkpoint");
+    let manifest = common::workspace(&dir);
+
+    // First start replays everything and checkpoints the empty ledger.
+    let mut core = common::from_manifest(&manifest);
+    assert!(core.resumed_from().is_none());
+    let empty = core.checkpoint().unwrap();
+    for (id, before, after) in [("t1", 0.20, 0.19), ("t2", 0.19, 0.18)] {
+        let report = core
+            .evaluate_evolution_record(common::tuning(id, before, after))
+            .unwrap();
+        assert!(report.is_allowed(), "{report:?}");
+    }
+    let latest = core.checkpoint().unwrap();
+    let report = core
+        .evaluate_evolution_record(common::tuning("t3", 0.18, 0.17))
+        .unwrap();
+    assert!(report.is_allowed());
+    let head = core.ledger_head().cloned();
+    drop(core);
+
+    let core = common::from_manifest(&manifest);
+    assert_eq!(core.resumed_from(), Some(latest.as_path()));
+    assert!(core.rejected_checkpoints().is_empty());
+    assert_eq!(core.ledger_head().cloned(), head);
+    assert_eq!(core.proposal_state("t3"), Some(Decision::Allowed));
+    drop(core);
+
+    // Bytes before the checkpointed head change, though every row and
+    // offset stays the same: the latest checkpoint is stale.
+    let ledger = dir.join("donutloopledger.aln");
+    let text = std::fs::read_to_string(&ledger).unwrap();
+    let edited = text.replacen("append-only", "append_only", 1);
+    assert_eq!(edited.len(), text.len());
+    std::fs::write(&ledger, edited).unwrap();
+
+    let core = common::from_manifest(&manifest);
+    assert_eq!(core.resumed_from(), Some(empty.as_path()));
+    let rejected = core.rejected_checkpoints();
+    assert_eq!(rejected.len(), 1);
+    assert!(rejected[0].contains("changed"), "{}", rejected[0]);
+    assert_eq!(core.ledger_head().cloned(), head);
+    drop(core);
+
+    // Without a usable checkpoint the full history is replayed.
+    std::fs::remove_file(&empty).unwrap();
+    let core = common::from_manifest(&manifest);
+    assert!(core.resumed_from().is_none());
+    assert_eq!(core.rejected_checkpoints().len(), 1);
+    assert_eq!(core.ledger_head().cloned(), head);
+    assert_eq!(core.proposal_state("t2"), Some(Decision::Allowed));
+}
+
+#[test]
+fn failed_startup_checkpoints_are_counted_not_fatal() {
+    let dir = common::scratch("checkpoint-fails");
+    let manifest = common::workspace(&dir);
+    // A directory where the save wants its temporary file.
+    let blocked = dir.join("checkpoints").join("checkpoint-0000000001.json.tmp");
+    std::fs::create_dir_all(&blocked).unwrap();
+
+    let failures = sovereigntycore::metrics::CHECKPOINT_FAILURES.get();
+    let mut core = common::from_manifest(&manifest);
+    assert!(core.resumed_from().is_none());
+    assert!(sovereigntycore::metrics::CHECKPOINT_FAILURES.get() > failures);
+    assert!(core.checkpoint().is_err());
+
+    std::fs::remove_dir(&blocked).unwrap();
+    core.checkpoint().unwrap();
+}
@@ -0,0 +1,343 @@
+//! Fixtures shared by the sovereigntycore integration tests.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use cybernano_vector_cyberrank::RankWeights;
+use cybernano_viability_kernel::{LifeforceState, SwarmState7D, ViabilityKernel};
+use ed25519_dalek::SigningKey;
+use organiccpualn::donutloopledger::DonutloopLedger;
+use organiccpualn::evolvestream::{
+    Decision, EffectBounds, EvolutionProposalRecord, JsonlEvolutionLog, Scope, TokenKind,
+    EVOLVE_SCHEMA_VERSION,
+};
+use organiccpualn::hexstamp::Hexstamped;
+use organiccpualn::neurorights::NeurorightsPolicyDocument;
+use organiccpualn::nnetevolve::{
+    NnetDelta, NnetEffectBounds, NnetScope, NnetUpdateRecord, TrainWindow,
+};
+use organiccpualn::nnetpolicy::{NnetPolicy, NnetPolicySet};
+use organiccpualn::proposalsig::ProposalSignature;
+use organiccpualn::rohmodel::RohModelShard;
+use organiccpualn::stake::{SignerKey, StakeShard};
+use sovereigntycore::types::{self, EnvelopeBounds, UpdateProposal};
+use sovereigntycore::SovereigntyCore;
+
+pub const SUBJECT: &str = "bostrom18sd2ujv24ual9c9pshtxys6j8knh6xaead9ye7";
+pub const ORGANIC_CPU: &str = "0x519fC0eB4111323Cac44b70e1aE31c30e405802D";
+pub const HOST_KEY: [u8; 32] = [1; 32];
+pub const ORGANIC_CPU_KEY: [u8; 32] = [2; 32];
+pub const RESEARCH_AGENT: &str = "0x8f72a93fb287a0f96b69c6f3f86e1e44134177ec";
+pub const RESEARCH_AGENT_KEY: [u8; 32] = [3; 32];
+
+/// Every signer of the repo's stake shard with its test key.
+const TEST_KEYS: [(&str, [u8; 32]); 3] = [
+    (SUBJECT, HOST_KEY),
+    (ORGANIC_CPU, ORGANIC_CPU_KEY),
+    (RESEARCH_AGENT, RESEARCH_AGENT_KEY),
+];
+
+pub type Core = SovereigntyCore<JsonlEvolutionLog>;
+
+pub fn repo(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../..")
+        .join(path)
+}
+
+/// A fresh directory holding an empty evolve stream and donutloop.
+pub fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("sovereigntycore-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("proposals.evolve.jsonl"), "").unwrap();
+    DonutloopLedger::create(dir.join("donutloopledger.aln"), SUBJECT).unwrap();
+    dir
+}
+
+/// The repo's stake shard with every signer's test key bound.
+pub fn stake() -> StakeShard {
+    let mut stake =
+        StakeShard::load(repo("qpudatashards/particles/bostrom-stake-v1.stake.aln")).unwrap();
+    for (address, key) in TEST_KEYS {
+        let key = SigningKey::from_bytes(&key).verifying_key();
+        stake.signer_keys.push(SignerKey::new(address, &key));
+    }
+    stake.validate().unwrap();
+    stake
+}
+
+pub fn kernel() -> ViabilityKernel {
+    ViabilityKernel {
+        mode_id: "test".into(),
+        a: vec![],
+        b: vec![],
+        min_integrity: 0.0,
+        min_chi: 0.0,
+    }
+}
+
+pub fn weights() -> RankWeights {
+    RankWeights {
+        safety: 1.0,
+        legal: 0.0,
+        biomech: 0.0,
+        psych: 0.0,
+        rollback: 0.0,
+    }
+}
+
+pub fn core_with(dir: &Path, stake: StakeShard) -> Core {
+    try_core_with(dir, stake).unwrap()
+}
+
+/// `core_with`, returning the startup error instead of panicking.
+pub fn try_core_with(dir: &Path, stake: StakeShard) -> anyhow::Result<Core> {
+    SovereigntyCore::new(
+        RohModelShard::load(repo(
+            "qpudatashards/particles/bostrom-rohmodel-v1.rohmodel.aln",
+        ))
+        .unwrap(),
+        stake,
+        NeurorightsPolicyDocument::load(repo("policies/bostrom-neurorights-v1.neurorights.json"))
+            .unwrap(),
+        kernel(),
+        weights(),
+        JsonlEvolutionLog,
+        dir.join("proposals.evolve.jsonl"),
+        DonutloopLedger::open(dir.join("donutloopledger.aln")).unwrap(),
+    )
+}
+
+/// Write a workspace manifest into `dir` naming copies of the repo's shards,
+/// with the test keys bound in the stake shard, and `dir`'s logs.
+pub fn workspace(dir: &Path) -> PathBuf {
+    let mut stake =
+        std::fs::read_to_string(repo("qpudatashards/particles/bostrom-stake-v1.stake.aln"))
+            .unwrap();
+    let shipped = StakeShard::load(repo("qpudatashards/particles/bostrom-stake-v1.stake.aln"))
+        .unwrap()
+        .signer_keys;
+    for (address, key) in TEST_KEYS {
+        let bound = shipped.iter().find(|k| k.address == address).unwrap();
+        let test_key = SignerKey::new(address, &SigningKey::from_bytes(&key).verifying_key());
+        stake = stake.replace(&bound.public_key, &test_key.public_key);
+    }
+    std::fs::write(dir.join("stake.aln"), stake).unwrap();
+    std::fs::copy(
+        repo("qpudatashards/particles/bostrom-rohmodel-v1.rohmodel.aln"),
+        dir.join("rohmodel.aln"),
+    )
+    .unwrap();
+    std::fs::copy(
+        repo("policies/bostrom-neurorights-v1.neurorights.json"),
+        dir.join("neurorights.json"),
+    )
+    .unwrap();
+    let manifest = format!(
+        "meta
+  manifestid test-workspace
+  subjectid {SUBJECT}
+  version 1.0.0
+paths
+  qpudataroot .
+  policiesroot .
+  logsroot .
+coreshards
+  rohmodel rohmodel.aln
+  stake stake.aln
+  neurorights neurorights.json
+  evolvestream proposals.evolve.jsonl
+  donutloop donutloopledger.aln
+invariants
+  rohceiling 0.30
+  rohmonotone true
+  envmonotone true
+  dreamnocomm true
+  nondiscriminate true
+stakerequirements
+  lifeforcescope
+    requiredroles Host,OrganicCPU
+    tokenkinds EVOLVE
+  archscope
+    requiredroles Host,OrganicCPU,ResearchAgent
+    tokenkinds EVOLVE
+  tuningscope
+    requiredroles Host
+    tokenkinds SMART,EVOLVE
+guardpipeline
+  order signerguard,stakeguard,neurorightsguard,rohguard,envelopeguard,tokenguard,logtoevolveanddonutloop
+"
+    );
+    let path = dir.join("neuro-workspace.manifest.aln");
+    std::fs::write(&path, manifest).unwrap();
+    path
+}
+
+/// A core booted from `manifest`, resuming from a checkpoint if one holds.
+pub fn from_manifest(manifest: &Path) -> Core {
+    SovereigntyCore::from_manifest(manifest, kernel(), weights(), JsonlEvolutionLog).unwrap()
+}
+
+/// A core over the repo's shards and `dir`'s logs, with the test keys bound.
+pub fn core(dir: &Path) -> Core {
+    core_with(dir, stake())
+}
+
+/// A proposal for `scope`, signed by the Host and stamped.
+pub fn proposal(
+    id: &str,
+    scope: Scope,
+    roh_before: f32,
+    roh_after: f32,
+) -> EvolutionProposalRecord {
+    let mut rec = EvolutionProposalRecord {
+        schema_version: EVOLVE_SCHEMA_VERSION,
+        proposalid: id.into(),
+        subjectid: SUBJECT.into(),
+        scope,
+        kind: "UpdateProposal".into(),
+        module: "hud".into(),
+        updatekind: "tuning".into(),
+        effectbounds: EffectBounds {
+            l2_delta_norm: 0.01,
+            irreversible: false,
+        },
+        roh_before,
+        roh_after,
+        tsafe_mode: "viability-kernel".into(),
+        signer_roles: vec!["Host".into()],
+        tokenkind: TokenKind::Evolve,
+        decision: Decision::Proposed,
+        hexstamp: String::new(),
+        timestamp_utc: "2026-02-04T02:00:00Z".into(),
+        purposes: vec!["personal_assistance".into()],
+        data_access: Vec::new(),
+        payload_salt: String::new(),
+        payload_commitment: String::new(),
+        signatures: Vec::new(),
+        forgotten_utc: None,
+    };
+    rec.sign(SUBJECT, &SigningKey::from_bytes(&HOST_KEY));
+    rec.stamp();
+    rec
+}
+
+/// A day-to-day tuning proposal; the Host's signature is its quorum.
+pub fn tuning(id: &str, roh_before: f32, roh_after: f32) -> EvolutionProposalRecord {
+    proposal(id, Scope::DayToDayTuning, roh_before, roh_after)
+}
+
+/// An unsigned update lowering RoH 0.10 -> 0.05 with unchanged envelopes.
+pub fn update(id: &str, scope: types::Scope) -> UpdateProposal {
+    UpdateProposal {
+        proposal_id: id.into(),
+        subject_id: SUBJECT.into(),
+        scope,
+        token_kind: types::TokenKind::Evolve,
+        effect_bounds: types::EffectBounds {
+            l2_delta_norm: 0.1,
+            irreversible: false,
+        },
+        roh_before: 0.10,
+        roh_after: 0.05,
+        envelopes: EnvelopeBounds {
+            g_old: 1.0,
+            g_new: 1.0,
+            d_old: 1.0,
+            d_new: 1.0,
+        },
+        evidence_bundle_ref: "evidence-1".into(),
+        purposes: vec!["personal_assistance".into()],
+        signatures: Vec::new(),
+    }
+}
+
+/// A resting swarm state with full lifeforce, inside the test kernel.
+pub fn live_state() -> (SwarmState7D, LifeforceState) {
+    let swarm = SwarmState7D {
+        intensity: 0.0,
+        duty_cycle: 0.0,
+        cumulative_load: 0.0,
+        implant_power: 0.0,
+        neuromod_amp: 0.0,
+        cognitive_load: 0.0,
+        legal_complexity: 0.0,
+    };
+    let lifeforce = LifeforceState {
+        cy: 1.0,
+        zen: 1.0,
+        chi: 1.0,
+        integrity: 1.0,
+    };
+    (swarm, lifeforce)
+}
+
+/// The OrganicCPU's signature over a proposal the core has logged.
+pub fn co_sign(core: &Core, id: &str) -> ProposalSignature {
+    co_sign_as(core, id, ORGANIC_CPU, ORGANIC_CPU_KEY)
+}
+
+/// `address`'s signature with `key` over a proposal the core has logged.
+pub fn co_sign_as(core: &Core, id: &str, address: &str, key: [u8; 32]) -> ProposalSignature {
+    let mut rec = core.proposal(id).unwrap().clone();
+    rec.sign(address, &SigningKey::from_bytes(&key));
+    rec.signatures.pop().unwrap()
+}
+
+/// The repo's chat-gov policy: daily budget 0.02, per-update bound 0.05.
+pub fn nnet_policies() -> NnetPolicySet {
+    let mut set = NnetPolicySet::default();
+    set.insert(
+        NnetPolicy::load(repo(
+            "qpudatashards/policies/bostrom-chat-gov-v1.nnet-policy.aln",
+        ))
+        .unwrap(),
+    )
+    .unwrap();
+    set
+}
+
+/// A chat-gov hyperparams update moving the params hash `before` -> `after`. It
+/// declares a generous daily budget so only the policy's can bind.
+pub fn nnet_update(id: &str, before: &str, after: &str, delta: f32) -> NnetUpdateRecord {
+    NnetUpdateRecord {
+        proposal_id: id.into(),
+        subject_id: SUBJECT.into(),
+        model_id: "bostrom-chat-gov-v1".into(),
+        kind: NnetUpdateRecord::KIND.into(),
+        scope: NnetScope::Hyperparams,
+        roh_before: 0.18,
+        roh_after: 0.17,
+        effect_bounds: NnetEffectBounds {
+            max_param_delta_per_day: 1.0,
+            max_effect_size_l2: 0.05,
+        },
+        nnet_delta: NnetDelta {
+            arch_hash_before: "arch".into(),
+            arch_hash_after: "arch".into(),
+            params_hash_before: before.into(),
+            params_hash_after: after.into(),
+            param_delta_l2: delta,
+            trainset_id: "dreamlog-2026Q1-redacted".into(),
+            train_window_utc: TrainWindow {
+                start: "2026-02-01T00:00:00Z".into(),
+                end: "2026-02-04T00:00:00Z".into(),
+            },
+        },
+        token_kind: TokenKind::Smart,
+        decision: Decision::Proposed,
+        hexstamp: String::new(),
+        timestamp_utc: "2026-02-04T02:00:00Z".into(),
+        signatures: Vec::new(),
+    }
+}
+
+/// Every record in `dir`'s evolve stream, in order.
+pub fn logged(dir: &Path) -> Vec<EvolutionProposalRecord> {
+    std::fs::read_to_string(dir.join("proposals.evolve.jsonl"))
+        .unwrap()
+        .lines()
+        .map(|l| EvolutionProposalRecord::from_json(l).unwrap())
+        .collect()
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use organiccpualn::evolvestream::{Decision, EvolutionProposalRecord, Scope};
+use organiccpualn::hexstamp::Hexstamped;
+use organiccpualn::stake::Role;
+use sovereigntycore::decision::{DecisionOutcome, ReasonCode};
+
+fn lifeforce(id: &str, roh_after: f32) -> EvolutionProposalRecord {
+    common::proposal(id, Scope::LifeforceAlteration, 0.20, roh_after)
+}
+
+#[test]
+fn deferred_proposal_is_allowed_once_quorum_signs() {
+    let dir = common::scratch("quorum");
+    let mut core = common::core(&dir);
+
+    let report = core
+        .evaluate_evolution_record(lifeforce("p1", 0.10))
+        .unwrap();
+    let deferral = report.deferral().unwrap();
+    assert_eq!(deferral.guard, "stakeguard");
+    assert_eq!(deferral.missing_roles, [Role::OrganicCpu]);
+    assert!(!deferral.expires_utc.is_empty());
+    assert_eq!(core.proposal_state("p1"), Some(Decision::Deferred));
+
+    // A proposal that would be rejected anyway is not parked.
+    let report = core
+        .evaluate_evolution_record(lifeforce("p2", 0.25))
+        .unwrap();
+    assert_eq!(report.rejection().unwrap().code(), ReasonCode::RohIncreased);
+
+    // A co-signature over another proposal is dropped; p1 stays parked.
+    let forged = common::co_sign(&core, "p2");
+    let report = core.attach_signatures("p1", vec![forged]).unwrap();
+    assert_eq!(report.deferral().unwrap().missing_roles, [Role::OrganicCpu]);
+    let dropped: Vec<_> = report.dropped_signatures.iter().map(|f| f.code).collect();
+    assert_eq!(dropped, [ReasonCode::BadSignature]);
+    assert_eq!(core.proposal_state("p1"), Some(Decision::Deferred));
+    let signature = common::co_sign(&core, "p1");
+    assert!(core
+        .attach_signatures("p1", vec![signature])
+        .unwrap()
+        .is_allowed());
+
+    let report = core
+        .evaluate_evolution_record(lifeforce("p3", 0.10))
+        .unwrap();
+    assert!(report.deferral().is_some());
+    let signature = common::co_sign(&core, "p3");
+    let report = core.attach_signatures("p3", vec![signature]).unwrap();
+    assert_eq!(report.outcome, DecisionOutcome::Allowed);
+    assert!(core.attach_signatures("p3", vec![]).is_err());
+
+    let steps: Vec<_> = common::logged(&dir)
+        .into_iter()
+        .filter(|r| r.proposalid == "p3")
+        .map(|r| (r.decision, r.signer_roles.len()))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            (Decision::Proposed, 1),
+            (Decision::Deferred, 1),
+            (Decision::Pending, 2),
+            (Decision::Allowed, 2),
+        ]
+    );
+}
+
+#[test]
+fn arch_changes_reach_quorum_with_the_research_agent() {
+    let mut core = common::core(&common::scratch("arch-quorum"));
+    let report = core
+        .evaluate_evolution_record(common::proposal("arch", Scope::ArchChange, 0.20, 0.10))
+        .unwrap();
+    assert_eq!(
+        report.deferral().unwrap().missing_roles,
+        [Role::OrganicCpu, Role::ResearchAgent]
+    );
+
+    let signatures = vec![
+        common::co_sign(&core, "arch"),
+        common::co_sign_as(
+            &core,
+            "arch",
+            common::RESEARCH_AGENT,
+            common::RESEARCH_AGENT_KEY,
+        ),
+    ];
+    let report = core.attach_signatures("arch", signatures).unwrap();
+    assert!(report.is_allowed(), "{report:?}");
+    assert_eq!(
+        core.proposal("arch").unwrap().signer_roles,
+        ["Host", "OrganicCPU", "ResearchAgent"]
+    );
+}
+
+#[test]
+fn deferred_proposals_expire() {
+    let dir = common::scratch("expiry");
+    let mut core = common::core(&dir);
+    for id in ["old", "stale", "new"] {
+        core.evaluate_evolution_record(lifeforce(id, 0.10)).unwrap();
+    }
+    assert!(core.expire_deferred().unwrap().is_empty());
+    drop(core);
+
+    // Backdate the deferrals of `old` and `stale` past the 72h window.
+    let text: String = common::logged(&dir)
+        .into_iter()
+        .map(|mut rec| {
+            if rec.proposalid != "new" && rec.decision == Decision::Deferred {
+                rec.timestamp_utc = "2026-01-01T00:00:00Z".into();
+                rec.stamp();
+            }
+            serde_json::to_string(&rec).unwrap() + "\n"
+        })
+        .collect();
+    std::fs::write(dir.join("proposals.evolve.jsonl"), text).unwrap();
+
+    let mut core = common::core(&dir);
+    let signature = common::co_sign(&core, "old");
+    let report = core.attach_signatures("old", vec![signature]).unwrap();
+    assert_eq!(
+        report.rejection().unwrap().code(),
+        ReasonCode::DeferralExpired
+    );
+    assert_eq!(core.proposal_state("old"), Some(Decision::Rejected));
+
+    assert_eq!(core.expire_deferred().unwrap(), ["stale"]);
+    assert_eq!(core.proposal_state("stale"), Some(Decision::Rejected));
+    assert_eq!(core.proposal_state("new"), Some(Decision::Deferred));
+}
+
+#[test]
+fn re_deferral_keeps_the_first_expiry() {
+    let dir = common::scratch("re-defer");
+    let mut core = common::core(&dir);
+    let report = core
+        .evaluate_evolution_record(lifeforce("p1", 0.10))
+        .unwrap();
+    let expires = report.deferral().unwrap().expires_utc.clone();
+
+    std::thread::sleep(std::time::Duration::from_millis(1100));
+    let report = core.attach_signatures("p1", vec![]).unwrap();
+    assert_eq!(report.deferral().unwrap().expires_utc, expires);
+    drop(core);
+
+    // Replay from the log derives the same window.
+    std::fs::remove_dir_all(dir.join("checkpoints")).ok();
+    let mut core = common::core(&dir);
+    let report = core.attach_signatures("p1", vec![]).unwrap();
+    assert_eq!(report.deferral().unwrap().expires_utc, expires);
+}
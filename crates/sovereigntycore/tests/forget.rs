@@ -0,0 +1,37 @@
+mod common;
+
+use ed25519_dalek::SigningKey;
+use organiccpualn::hexstamp::Hexstamped;
+
+#[test]
+fn receipt_names_the_nnet_logs_it_leaves_as_is() {
+    let dir = common::scratch("forget");
+    let mut core = common::core(&dir);
+    core.set_nnet_policies(common::nnet_policies()).unwrap();
+    core.evaluate_evolution_record(common::tuning("p1", 0.20, 0.18))
+        .unwrap();
+    let mut update = common::nnet_update("u1", "p0", "p1", 0.01);
+    update.sign(common::SUBJECT, &SigningKey::from_bytes(&common::HOST_KEY));
+    update.stamp();
+    assert!(core.evaluate_nnet_update(update).unwrap().is_allowed());
+
+    let request = core.request_forget(common::SUBJECT).unwrap();
+    let receipt = core
+        .forget(&request.request_id, &SigningKey::from_bytes(&[7; 32]))
+        .unwrap();
+    assert!(receipt.records.iter().any(|r| r.proposal_id == "p1"));
+    // The core logs updates under the day it evaluated them.
+    let [file] = receipt.not_covered.as_slice() else {
+        panic!("expected one nnet log, got {:?}", receipt.not_covered);
+    };
+    assert_eq!(file.parent(), Some(dir.as_path()));
+    assert!(file.to_str().unwrap().contains("nnet-evolve-"));
+    receipt.verify_signature().unwrap();
+
+    // Dropping the list breaks the signature.
+    let mut trimmed = receipt.clone();
+    trimmed.not_covered.clear();
+    assert!(trimmed.verify_signature().is_err());
+
+    assert!(common::logged(&dir).iter().all(|r| r.is_forgotten()));
+}
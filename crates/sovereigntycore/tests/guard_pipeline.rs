@@ -0,0 +1,316 @@
+mod common;
+
+use std::sync::Arc;
+
+use ed25519_dalek::SigningKey;
+use organiccpualn::evolvestream::Decision;
+use organiccpualn::hexstamp::Hexstamped;
+use organiccpualn::stake::Role;
+use sovereigntycore::decision::{Bound, DecisionOutcome, ReasonCode};
+use sovereigntycore::guards::{
+    Guard, GuardContext, GuardInput, GuardPipeline, GuardRegistry, PipelineError, Verdict,
+    DEFAULT_ORDER, LOG_STAGE,
+};
+use sovereigntycore::types::Scope;
+
+struct AuditGuard(&'static str);
+
+impl Guard for AuditGuard {
+    fn name(&self) -> &'static str {
+        self.0
+    }
+
+    fn check(&self, _: &GuardContext<'_>, _: &GuardInput<'_>) -> anyhow::Result<Verdict> {
+        Ok(Verdict::Pass)
+    }
+}
+
+#[test]
+fn manifest_order_is_kept_and_fails_closed() {
+    let order = [
+        "rohguard",
+        "signerguard",
+        "stakeguard",
+        "tokenguard",
+        "neurorightsguard",
+        "envelopeguard",
+        LOG_STAGE,
+    ];
+    let pipeline = GuardPipeline::from_order(&order).unwrap();
+    assert_eq!(pipeline.names(), order);
+    assert_eq!(GuardPipeline::default().names(), DEFAULT_ORDER);
+
+    let mut unknown = order.to_vec();
+    unknown.insert(0, "rohgaurd");
+    assert_eq!(
+        GuardPipeline::from_order(&unknown).unwrap_err(),
+        PipelineError::UnknownGuard("rohgaurd".into())
+    );
+
+    let dropped: Vec<_> = order.iter().filter(|g| **g != "stakeguard").collect();
+    assert_eq!(
+        GuardPipeline::from_order(&dropped).unwrap_err(),
+        PipelineError::MissingMandatory("stakeguard")
+    );
+
+    assert_eq!(
+        GuardPipeline::from_order(&order[..6]).unwrap_err(),
+        PipelineError::LogStageNotLast
+    );
+}
+
+#[test]
+fn deployments_add_guards_but_cannot_replace_builtins() {
+    let mut registry = GuardRegistry::builtin();
+    assert_eq!(
+        registry.register(Arc::new(AuditGuard("rohguard"))),
+        Err(PipelineError::NameTaken("rohguard".into()))
+    );
+    registry
+        .register(Arc::new(AuditGuard("auditguard")))
+        .unwrap();
+
+    let mut order = DEFAULT_ORDER.to_vec();
+    order.insert(0, "auditguard");
+    assert_eq!(registry.pipeline(&order).unwrap().names(), order);
+    assert!(GuardPipeline::from_order(&order).is_err());
+}
+
+struct BrokenGuard;
+
+impl Guard for BrokenGuard {
+    fn name(&self) -> &'static str {
+        "brokenguard"
+    }
+
+    fn check(&self, _: &GuardContext<'_>, _: &GuardInput<'_>) -> anyhow::Result<Verdict> {
+        anyhow::bail!("audit store unreachable")
+    }
+}
+
+fn core(name: &str) -> common::Core {
+    common::core(&common::scratch(name))
+}
+
+#[test]
+fn rejections_carry_code_guard_measurements_and_trace() {
+    let mut core = core("explain");
+    let report = core
+        .evaluate_evolution_record(common::tuning("up", 0.10, 0.12))
+        .unwrap();
+    let rejection = report.rejection().unwrap();
+    assert_eq!(rejection.guard, "rohguard");
+    assert_eq!(rejection.code(), ReasonCode::RohIncreased);
+    let m = &rejection.finding.measurements[0];
+    assert_eq!(
+        (m.quantity.as_str(), m.threshold_of.as_str()),
+        ("roh_after", "roh_before")
+    );
+    assert_eq!(
+        (m.measured, m.bound, m.threshold),
+        (0.12, Bound::AtMost, 0.10)
+    );
+    assert!(!m.holds());
+    let ran: Vec<_> = report.trace.iter().map(|r| r.guard.as_str()).collect();
+    assert_eq!(
+        ran,
+        ["signerguard", "stakeguard", "neurorightsguard", "rohguard"]
+    );
+    assert!(report.trace[2].verdict.passed() && !report.trace[3].verdict.passed());
+
+    // Signer errors are rejections too, not `Err`.
+    let mut forged = common::tuning("forged", 0.10, 0.05);
+    forged.signatures.clear();
+    forged.sign(common::SUBJECT, &SigningKey::from_bytes(&[9; 32]));
+    forged.stamp();
+    let report = core.evaluate_evolution_record(forged).unwrap();
+    assert_eq!(
+        report.rejection().unwrap().code(),
+        ReasonCode::UnboundSignerKey
+    );
+
+    let allowed = core
+        .evaluate_evolution_record(common::tuning("down", 0.10, 0.05))
+        .unwrap();
+    assert_eq!(allowed.outcome, DecisionOutcome::Allowed);
+    assert_eq!(allowed.trace.last().unwrap().guard, LOG_STAGE);
+
+    let json = serde_json::to_value(report.outcome).unwrap();
+    assert_eq!(json["Rejected"]["code"], "unbound_signer_key");
+    assert_eq!(json["Rejected"]["guard"], "signerguard");
+}
+
+#[test]
+fn only_verified_signatures_count_as_signers() {
+    let mut core = core("signers");
+
+    // A signature no longer covers the proposal once it is changed.
+    let mut tampered = common::tuning("tampered", 0.10, 0.05);
+    tampered.roh_after = 0.01;
+    tampered.stamp();
+    let report = core.evaluate_evolution_record(tampered).unwrap();
+    let rejection = report.rejection().unwrap();
+    assert_eq!(rejection.guard, "signerguard");
+    assert_eq!(rejection.code(), ReasonCode::BadSignature);
+
+    // Claimed roles without signatures prove nothing.
+    let mut claimed = common::tuning("claimed", 0.10, 0.05);
+    claimed.signatures.clear();
+    claimed.stamp();
+    let report = core.evaluate_evolution_record(claimed).unwrap();
+    assert_eq!(report.deferral().unwrap().missing_roles, [Role::Host]);
+    assert!(core.proposal("claimed").unwrap().signer_roles.is_empty());
+
+    let report = core
+        .evaluate_evolution_record(common::tuning("signed", 0.10, 0.05))
+        .unwrap();
+    assert!(report.is_allowed());
+    assert_eq!(core.proposal("signed").unwrap().signer_roles, ["Host"]);
+}
+
+#[test]
+fn update_proposals_need_their_scope_signers() {
+    let mut core = core("updates");
+    let (swarm, lifeforce) = common::live_state();
+    let host = SigningKey::from_bytes(&common::HOST_KEY);
+
+    for (id, scope) in [
+        ("unsigned-tuning", Scope::DayToDayTuning),
+        ("unsigned-lifeforce", Scope::LifeforceAlteration),
+    ] {
+        let report = core
+            .evaluate_update(common::update(id, scope), &swarm, &lifeforce)
+            .unwrap();
+        let rejection = report.rejection().unwrap();
+        assert_eq!(rejection.guard, "stakeguard");
+        assert_eq!(rejection.code(), ReasonCode::MissingSigners);
+        assert_eq!(core.proposal_state(id), Some(Decision::Rejected));
+    }
+
+    // The Host alone is not the lifeforce quorum, and updates are not parked.
+    let mut lifeforce_update = common::update("host-lifeforce", Scope::LifeforceAlteration);
+    lifeforce_update.sign(common::SUBJECT, &host);
+    let report = core
+        .evaluate_update(lifeforce_update, &swarm, &lifeforce)
+        .unwrap();
+    assert_eq!(
+        report.rejection().unwrap().code(),
+        ReasonCode::MissingSigners
+    );
+
+    // A quorum passes the stake guard; the record logs the proven roles.
+    let mut tuning = common::update("host-tuning", Scope::DayToDayTuning);
+    tuning.sign(common::SUBJECT, &host);
+    let report = core.evaluate_update(tuning, &swarm, &lifeforce).unwrap();
+    let stake = report
+        .trace
+        .iter()
+        .find(|r| r.guard == "stakeguard")
+        .unwrap();
+    assert_eq!(stake.verdict, Verdict::Pass);
+    assert_eq!(core.proposal("host-tuning").unwrap().signer_roles, ["Host"]);
+}
+
+#[test]
+fn a_signed_update_is_allowed_and_logged_to_the_donutloop() {
+    let mut core = core("update-allowed");
+    let (swarm, lifeforce) = common::live_state();
+    let host = SigningKey::from_bytes(&common::HOST_KEY);
+
+    let mut tuning = common::update("tuning", Scope::DayToDayTuning);
+    tuning.sign(common::SUBJECT, &host);
+    let report = core.evaluate_update(tuning, &swarm, &lifeforce).unwrap();
+    assert!(report.is_allowed(), "{report:?}");
+    let stages: Vec<_> = report.trace.iter().map(|r| r.guard.as_str()).collect();
+    assert_eq!(stages, DEFAULT_ORDER);
+    assert!(report.trace.iter().all(|r| r.verdict == Verdict::Pass));
+    assert_eq!(core.proposal_state("tuning"), Some(Decision::Allowed));
+    let head = core.ledger_head().unwrap();
+    assert_eq!(
+        (head.proposal_id.as_str(), head.roh_after),
+        ("tuning", 0.05)
+    );
+
+    // An irreversible update is not a Tsafe action.
+    let mut irreversible = common::update("irreversible", Scope::DayToDayTuning);
+    irreversible.effect_bounds.irreversible = true;
+    irreversible.roh_before = 0.05;
+    irreversible.sign(common::SUBJECT, &host);
+    let report = core
+        .evaluate_update(irreversible, &swarm, &lifeforce)
+        .unwrap();
+    let rejection = report.rejection().unwrap();
+    assert_eq!(rejection.guard, "envelopeguard");
+    assert_eq!(rejection.code(), ReasonCode::NoTsafeAction);
+    assert_eq!(core.ledger_head().unwrap().proposal_id, "tuning");
+
+    // Neither is an update that declares no purpose.
+    let mut unpurposed = common::update("unpurposed", Scope::DayToDayTuning);
+    unpurposed.purposes.clear();
+    unpurposed.roh_before = 0.05;
+    unpurposed.roh_after = 0.04;
+    unpurposed.sign(common::SUBJECT, &host);
+    let report = core
+        .evaluate_update(unpurposed, &swarm, &lifeforce)
+        .unwrap();
+    let rejection = report.rejection().unwrap();
+    assert_eq!(rejection.guard, "neurorightsguard");
+    assert_eq!(rejection.code(), ReasonCode::NeurorightsViolation);
+    assert_eq!(core.ledger_head().unwrap().proposal_id, "tuning");
+}
+
+#[test]
+fn update_signatures_cover_envelopes_and_evidence() {
+    let mut core = core("update-signing");
+    let (swarm, lifeforce) = common::live_state();
+    let host = SigningKey::from_bytes(&common::HOST_KEY);
+
+    let mut swapped_envelopes = common::update("envelopes", Scope::DayToDayTuning);
+    swapped_envelopes.sign(common::SUBJECT, &host);
+    swapped_envelopes.envelopes.d_new = 0.5;
+    let mut swapped_evidence = common::update("evidence", Scope::DayToDayTuning);
+    swapped_evidence.sign(common::SUBJECT, &host);
+    swapped_evidence.evidence_bundle_ref = "evidence-2".into();
+    let mut swapped_purposes = common::update("purposes", Scope::DayToDayTuning);
+    swapped_purposes.sign(common::SUBJECT, &host);
+    swapped_purposes.purposes = vec!["research_under_consent".into()];
+    for swapped in [swapped_envelopes, swapped_evidence, swapped_purposes] {
+        let id = swapped.proposal_id.clone();
+        let report = core.evaluate_update(swapped, &swarm, &lifeforce).unwrap();
+        let rejection = report.rejection().unwrap();
+        assert_eq!(rejection.guard, "signerguard");
+        assert_eq!(rejection.code(), ReasonCode::BadSignature);
+        assert_eq!(core.proposal_state(&id), Some(Decision::Rejected));
+    }
+
+    // The logged record keeps the signed envelopes and evidence reference.
+    let mut signed = common::update("signed", Scope::DayToDayTuning);
+    signed.envelopes.d_new = 0.5;
+    signed.sign(common::SUBJECT, &host);
+    assert!(core
+        .evaluate_update(signed, &swarm, &lifeforce)
+        .unwrap()
+        .is_allowed());
+    let logged = core.proposal("signed").unwrap();
+    assert_eq!(logged.module, "evidence-1");
+    assert_eq!(logged.updatekind, "envelopes g 1->1 d 1->0.5");
+}
+
+#[test]
+fn a_guard_that_fails_to_run_rejects() {
+    let mut registry = GuardRegistry::builtin();
+    registry.register(Arc::new(BrokenGuard)).unwrap();
+    let mut order = DEFAULT_ORDER.to_vec();
+    order.insert(0, "brokenguard");
+
+    let mut core = core("broken");
+    core.set_guard_pipeline(registry.pipeline(&order).unwrap());
+    let report = core
+        .evaluate_evolution_record(common::tuning("p1", 0.10, 0.05))
+        .unwrap();
+    let rejection = report.rejection().unwrap();
+    assert_eq!(rejection.guard, "brokenguard");
+    assert_eq!(rejection.code(), ReasonCode::GuardFailed);
+    assert_eq!(rejection.reason(), "audit store unreachable");
+    assert_eq!(core.proposal_state("p1"), Some(Decision::Rejected));
+}
@@ -0,0 +1,226 @@
+mod common;
+
+use organiccpualn::evolvestream::{Decision, EvolutionProposalRecord};
+use organiccpualn::hexstamp::Hexstamped;
+use sovereigntycore::decision::ReasonCode;
+use sovereigntycore::lifecycle::{can_transition, LifecycleError, ProposalLifecycle};
+
+use Decision::*;
+
+const ALL: [Decision; 7] = [
+    Proposed, Pending, Allowed, Rejected, Deferred, Applied, RolledBack,
+];
+
+/// `id` as logged in `decision` at `timestamp_utc`.
+fn step(id: &str, decision: Decision, timestamp_utc: &str) -> EvolutionProposalRecord {
+    let mut rec = common::tuning(id, 0.20, 0.10);
+    rec.decision = decision;
+    rec.timestamp_utc = timestamp_utc.into();
+    rec
+}
+
+#[test]
+fn only_documented_steps_are_legal() {
+    let legal = [
+        (Proposed, Pending),
+        (Proposed, Allowed),
+        (Proposed, Rejected),
+        (Proposed, Deferred),
+        (Pending, Allowed),
+        (Pending, Rejected),
+        (Pending, Deferred),
+        (Deferred, Pending),
+        (Deferred, Allowed),
+        (Deferred, Rejected),
+        (Allowed, Applied),
+        (Applied, RolledBack),
+    ];
+    for from in ALL {
+        for to in ALL {
+            assert_eq!(
+                can_transition(from, to),
+                legal.contains(&(from, to)),
+                "{from} -> {to}"
+            );
+        }
+    }
+    // Rejected and RolledBack are final.
+    assert!(ALL.iter().all(|&to| !can_transition(Rejected, to)));
+    assert!(ALL.iter().all(|&to| !can_transition(RolledBack, to)));
+}
+
+#[test]
+fn replay_follows_each_proposal() {
+    let lifecycle = ProposalLifecycle::replay([
+        step("p1", Proposed, "2026-02-04T00:00:00Z"),
+        step("p2", Proposed, "2026-02-04T00:00:01Z"),
+        step("p1", Deferred, "2026-02-04T00:00:02Z"),
+        step("p2", Allowed, "2026-02-04T00:00:03Z"),
+        step("p1", Pending, "2026-02-04T00:00:04Z"),
+        step("p1", Deferred, "2026-02-04T00:00:05Z"),
+        step("p2", Applied, "2026-02-04T00:00:06Z"),
+    ])
+    .unwrap();
+    assert_eq!(lifecycle.state("p1"), Some(Deferred));
+    assert_eq!(lifecycle.state("p2"), Some(Applied));
+    assert_eq!(lifecycle.state("p3"), None);
+    assert_eq!(
+        lifecycle.latest("p2").unwrap().timestamp_utc,
+        "2026-02-04T00:00:06Z"
+    );
+    assert_eq!(lifecycle.deferred_since("p1"), Some("2026-02-04T00:00:02Z"));
+    let deferred: Vec<_> = lifecycle
+        .in_state(Deferred)
+        .map(|r| r.proposalid.as_str())
+        .collect();
+    assert_eq!(deferred, ["p1"]);
+}
+
+#[test]
+fn replay_rejects_illegal_steps() {
+    let err = ProposalLifecycle::replay([
+        step("p1", Proposed, "2026-02-04T00:00:00Z"),
+        step("p1", Rejected, "2026-02-04T00:00:01Z"),
+        step("p1", Applied, "2026-02-04T00:00:02Z"),
+    ])
+    .unwrap_err();
+    assert_eq!(
+        err,
+        LifecycleError::IllegalTransition {
+            proposal_id: "p1".into(),
+            from: Rejected,
+            to: Applied,
+        }
+    );
+
+    let err = ProposalLifecycle::replay([step("p1", Allowed, "2026-02-04T00:00:00Z")]).unwrap_err();
+    assert_eq!(
+        err,
+        LifecycleError::NotProposed {
+            proposal_id: "p1".into(),
+            to: Allowed,
+        }
+    );
+}
+
+#[test]
+fn applying_a_rejected_proposal_fails() {
+    let mut core = common::core(&common::scratch("apply-rejected"));
+    let report = core
+        .evaluate_evolution_record(common::tuning("p1", 0.10, 0.20))
+        .unwrap();
+    assert!(report.rejection().is_some());
+
+    let err = core.mark_applied("p1").unwrap_err();
+    assert_eq!(
+        err.downcast::<LifecycleError>().unwrap(),
+        LifecycleError::IllegalTransition {
+            proposal_id: "p1".into(),
+            from: Rejected,
+            to: Applied,
+        }
+    );
+    assert_eq!(core.proposal_state("p1"), Some(Rejected));
+
+    let err = core.mark_applied("p9").unwrap_err();
+    assert_eq!(
+        err.downcast::<LifecycleError>().unwrap(),
+        LifecycleError::UnknownProposal {
+            proposal_id: "p9".into(),
+        }
+    );
+}
+
+#[test]
+fn replay_rejects_a_logged_record_stripped_of_its_stamp() {
+    let dir = common::scratch("stripped-stamp");
+    let mut core = common::core(&dir);
+    let report = core
+        .evaluate_evolution_record(common::tuning("p1", 0.10, 0.20))
+        .unwrap();
+    assert!(report.rejection().is_some());
+    drop(core);
+
+    // Rewrite the logged rejection as an allowed decrease, minus its stamps.
+    let path = dir.join("proposals.evolve.jsonl");
+    let text = std::fs::read_to_string(&path).unwrap();
+    let (head, last) = text.trim_end().rsplit_once('\n').unwrap();
+    let mut rec: serde_json::Value = serde_json::from_str(last).unwrap();
+    let fields = rec.as_object_mut().unwrap();
+    assert_eq!(fields["schema_version"], 2);
+    for field in [
+        "schema_version",
+        "hexstamp",
+        "payload_commitment",
+        "payload_salt",
+        "signatures",
+    ] {
+        fields.remove(field);
+    }
+    fields.insert("decision".into(), "Allowed".into());
+    fields.insert("roh_after".into(), 0.05.into());
+    std::fs::write(&path, format!("{head}\n{rec}\n")).unwrap();
+
+    let err = common::try_core_with(&dir, common::stake()).err().unwrap();
+    assert!(format!("{err:#}").contains("no hexstamp"), "{err:#}");
+}
+
+#[test]
+fn startup_concludes_a_proposal_whose_ledger_entry_was_written() {
+    let dir = common::scratch("crash-after-append");
+    let mut core = common::core(&dir);
+    assert!(core
+        .evaluate_evolution_record(common::tuning("p1", 0.20, 0.10))
+        .unwrap()
+        .is_allowed());
+    drop(core);
+
+    // Crash after the donutloop append, before the Allowed record.
+    let path = dir.join("proposals.evolve.jsonl");
+    let text = std::fs::read_to_string(&path).unwrap();
+    let (proposed, _) = text.trim_end().rsplit_once('\n').unwrap();
+    std::fs::write(&path, format!("{proposed}\n")).unwrap();
+    assert_eq!(common::logged(&dir).last().unwrap().decision, Proposed);
+
+    let mut core = common::core(&dir);
+    assert_eq!(core.reconciled(), ["p1"]);
+    assert_eq!(core.proposal_state("p1"), Some(Allowed));
+    let steps: Vec<_> = common::logged(&dir).iter().map(|r| r.decision).collect();
+    assert_eq!(steps, [Proposed, Allowed]);
+    core.mark_applied("p1").unwrap();
+
+    // Already concluded: the next start has nothing to do.
+    let core = common::core(&dir);
+    assert!(core.reconciled().is_empty());
+    assert_eq!(core.proposal_state("p1"), Some(Applied));
+}
+
+#[test]
+fn incoming_records_must_be_fresh_proposals() {
+    let mut core = common::core(&common::scratch("intake"));
+
+    let allowed = step("p1", Allowed, "2026-02-04T00:00:00Z");
+    let mut forgotten = common::tuning("p2", 0.20, 0.10);
+    forgotten.forget("2026-02-04T00:00:00Z".into()).unwrap();
+    for (rec, code) in [
+        (allowed, ReasonCode::NotProposed),
+        (forgotten, ReasonCode::AlreadyForgotten),
+    ] {
+        let mut rec = rec;
+        rec.stamp();
+        rec.verify_hexstamp().unwrap();
+        let id = rec.proposalid.clone();
+        let report = core.evaluate_evolution_record(rec).unwrap();
+        let rejection = report.rejection().unwrap();
+        assert_eq!(
+            (rejection.guard.as_str(), rejection.code()),
+            ("intakecheck", code)
+        );
+        // Nothing was logged, so the id is still free.
+        assert_eq!(core.proposal_state(&id), None);
+    }
+    assert!(core
+        .evaluate_evolution_record(common::tuning("p1", 0.20, 0.10))
+        .unwrap()
+        .is_allowed());
+}
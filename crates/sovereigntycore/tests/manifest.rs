@@ -0,0 +1,98 @@
+mod common;
+
+use std::path::Path;
+
+use sovereigntycore::SovereigntyCore;
+
+fn boot(manifest: &Path) -> anyhow::Result<common::Core> {
+    SovereigntyCore::from_manifest(
+        manifest,
+        common::kernel(),
+        common::weights(),
+        organiccpualn::evolvestream::JsonlEvolutionLog,
+    )
+}
+
+/// Replace `from` with `to` in `dir`'s file `name`.
+fn edit(dir: &Path, name: &str, from: &str, to: &str) {
+    let path = dir.join(name);
+    let text = std::fs::read_to_string(&path).unwrap();
+    assert!(text.contains(from), "{name} has no {from:?}");
+    std::fs::write(&path, text.replacen(from, to, 1)).unwrap();
+}
+
+#[test]
+fn first_start_creates_the_logs() {
+    let dir = common::scratch("manifest-fresh");
+    let manifest = common::workspace(&dir);
+    std::fs::remove_file(dir.join("proposals.evolve.jsonl")).unwrap();
+    std::fs::remove_file(dir.join("donutloopledger.aln")).unwrap();
+
+    let mut core = boot(&manifest).unwrap();
+    assert!(dir.join("proposals.evolve.jsonl").is_file());
+    assert!(core.ledger_head().is_none());
+    let report = core
+        .evaluate_evolution_record(common::tuning("t1", 0.20, 0.19))
+        .unwrap();
+    assert!(report.is_allowed(), "{report:?}");
+    drop(core);
+
+    let core = boot(&manifest).unwrap();
+    assert_eq!(core.ledger_head().unwrap().proposal_id, "t1");
+}
+
+#[test]
+fn a_missing_shard_is_named() {
+    for shard in ["rohmodel", "stake", "neurorights"] {
+        let dir = common::scratch(&format!("manifest-no-{shard}"));
+        let manifest = common::workspace(&dir);
+        let file = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .find(|p| p.file_stem().is_some_and(|s| s == shard))
+            .unwrap();
+        std::fs::remove_file(&file).unwrap();
+        let err = format!("{:#}", boot(&manifest).err().unwrap());
+        assert!(
+            err.contains(&format!("coreshard `{shard}` not found")),
+            "{err}"
+        );
+    }
+}
+
+#[test]
+fn shards_must_agree_with_the_manifest() {
+    let cases = [
+        (
+            "neuro-workspace.manifest.aln",
+            "rohceiling 0.30",
+            "rohceiling 0.25",
+        ),
+        (
+            "neuro-workspace.manifest.aln",
+            "envmonotone true",
+            "envmonotone false",
+        ),
+        ("neurorights.json", "\"credit\",", ""),
+        (
+            "neurorights.json",
+            "\"subject_id\": \"bostrom",
+            "\"subject_id\": \"other",
+        ),
+        (
+            "neuro-workspace.manifest.aln",
+            "requiredroles Host\n",
+            "requiredroles Host,OrganicCPU\n",
+        ),
+    ];
+    for (n, (name, from, to)) in cases.into_iter().enumerate() {
+        let dir = common::scratch(&format!("manifest-inconsistent-{n}"));
+        let manifest = common::workspace(&dir);
+        edit(&dir, name, from, to);
+        let err = format!("{:#}", boot(&manifest).err().unwrap());
+        assert!(
+            err.contains("is inconsistent with its shards"),
+            "{to}: {err}"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use ed25519_dalek::SigningKey;
+use organiccpualn::evolvestream::TokenKind;
+use organiccpualn::hexstamp::Hexstamped;
+use organiccpualn::nnetevolve::{NnetScope, NnetUpdateRecord};
+use sovereigntycore::decision::{DecisionOutcome, ReasonCode};
+
+fn core(name: &str) -> common::Core {
+    let mut core = common::core(&common::scratch(name));
+    core.set_nnet_policies(common::nnet_policies()).unwrap();
+    core
+}
+
+/// `rec` signed with `keys` as (address, key) pairs, then stamped.
+fn signed(mut rec: NnetUpdateRecord, keys: &[(&str, [u8; 32])]) -> NnetUpdateRecord {
+    for (address, key) in keys {
+        rec.sign(address, &SigningKey::from_bytes(key));
+    }
+    rec.stamp();
+    rec
+}
+
+fn host(rec: NnetUpdateRecord) -> NnetUpdateRecord {
+    signed(rec, &[(common::SUBJECT, common::HOST_KEY)])
+}
+
+fn code(outcome: DecisionOutcome) -> ReasonCode {
+    outcome.rejection().expect("rejected").code()
+}
+
+#[test]
+fn updates_need_a_policy_and_their_scope_signers() {
+    let mut core = common::core(&common::scratch("nnet-signers"));
+    let rec = host(common::nnet_update("u1", "p0", "p1", 0.01));
+    let outcome = core.evaluate_nnet_update(rec).unwrap();
+    assert_eq!(code(outcome), ReasonCode::NnetPolicyMissing);
+    core.set_nnet_policies(common::nnet_policies()).unwrap();
+
+    let unsigned = signed(common::nnet_update("u2", "p0", "p1", 0.01), &[]);
+    let outcome = core.evaluate_nnet_update(unsigned).unwrap();
+    assert_eq!(code(outcome), ReasonCode::MissingSigners);
+
+    let forged = signed(
+        common::nnet_update("u3", "p0", "p1", 0.01),
+        &[(common::SUBJECT, [9; 32])],
+    );
+    let outcome = core.evaluate_nnet_update(forged).unwrap();
+    assert_eq!(code(outcome), ReasonCode::UnboundSignerKey);
+
+    // Architecture changes need the ResearchAgent on top of Host and OrganicCPU.
+    let mut arch = common::nnet_update("u4", "p0", "p1", 0.01);
+    arch.scope = NnetScope::Architecture;
+    arch.token_kind = TokenKind::Evolve;
+    let arch = signed(
+        arch,
+        &[
+            (common::SUBJECT, common::HOST_KEY),
+            (common::ORGANIC_CPU, common::ORGANIC_CPU_KEY),
+        ],
+    );
+    let outcome = core.evaluate_nnet_update(arch).unwrap();
+    assert_eq!(code(outcome), ReasonCode::MissingSigners);
+
+    let outcome = core
+        .evaluate_nnet_update(host(common::nnet_update("u5", "p0", "p1", 0.01)))
+        .unwrap();
+    assert_eq!(outcome, DecisionOutcome::Allowed);
+}
+
+#[test]
+fn daily_budget_is_capped_by_the_policy() {
+    let mut core = core("nnet-budget");
+    let outcome = core
+        .evaluate_nnet_update(host(common::nnet_update("u1", "p0", "p1", 0.015)))
+        .unwrap();
+    assert_eq!(outcome, DecisionOutcome::Allowed);
+
+    // The record declares 1.0 a day; the policy allows 0.02.
+    let outcome = core
+        .evaluate_nnet_update(host(common::nnet_update("u2", "p1", "p2", 0.015)))
+        .unwrap();
+    let rejection = outcome.rejection().unwrap();
+    assert_eq!(rejection.code(), ReasonCode::NnetDailyBudgetExceeded);
+    let measured = &rejection.finding.measurements[0];
+    assert_eq!(measured.threshold, 0.02);
+    assert!((measured.measured - 0.03).abs() < 1e-6);
+
+    let outcome = core
+        .evaluate_nnet_update(host(common::nnet_update("u3", "p1", "p2", 0.005)))
+        .unwrap();
+    assert_eq!(outcome, DecisionOutcome::Allowed);
+}
+
+#[test]
+fn params_hashes_must_chain() {
+    let mut core = core("nnet-params");
+    core.evaluate_nnet_update(host(common::nnet_update("u1", "p0", "p1", 0.001)))
+        .unwrap();
+
+    let outcome = core
+        .evaluate_nnet_update(host(common::nnet_update("u2", "p0", "p2", 0.001)))
+        .unwrap();
+    assert_eq!(code(outcome), ReasonCode::NnetParamsHashMismatch);
+
+    let outcome = core
+        .evaluate_nnet_update(host(common::nnet_update("u3", "p1", "p2", 0.001)))
+        .unwrap();
+    assert_eq!(outcome, DecisionOutcome::Allowed);
+}
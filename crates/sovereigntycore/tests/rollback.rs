@@ -0,0 +1,97 @@
+mod common;
+
+use ed25519_dalek::SigningKey;
+use organiccpualn::donutloopledger::DonutloopEntry;
+use organiccpualn::evolvestream::{Decision, EvolutionProposalRecord};
+use organiccpualn::hexstamp::Hexstamped;
+use sovereigntycore::decision::ReasonCode;
+
+/// Core with `p1` (RoH 0.20 -> 0.10) allowed, and its donutloop entry.
+fn allowed_p1(name: &str) -> (common::Core, DonutloopEntry) {
+    let mut core = common::core(&common::scratch(name));
+    let report = core
+        .evaluate_evolution_record(common::tuning("p1", 0.20, 0.10))
+        .unwrap();
+    assert!(report.is_allowed());
+    let entry = core.ledger_head().unwrap().clone();
+    assert_eq!(entry.proposal_id, "p1");
+    (core, entry)
+}
+
+/// The Host-signed record `Core::rollback` builds for `entry`.
+fn inverse(core: &common::Core, entry: &DonutloopEntry) -> EvolutionProposalRecord {
+    let original = core.proposal(&entry.proposal_id).unwrap();
+    let head_roh = core.ledger_head().unwrap().roh_after;
+    let mut rec = entry.rollback_proposal(original, head_roh);
+    rec.sign(common::SUBJECT, &SigningKey::from_bytes(&common::HOST_KEY));
+    rec
+}
+
+fn stamped(mut rec: EvolutionProposalRecord, id: &str) -> EvolutionProposalRecord {
+    rec.proposalid = id.into();
+    rec.signatures.clear();
+    rec.sign(common::SUBJECT, &SigningKey::from_bytes(&common::HOST_KEY));
+    rec.timestamp_utc = "2026-02-04T03:00:00Z".into();
+    rec.stamp();
+    rec
+}
+
+#[test]
+fn rollback_to_a_recorded_state_is_allowed_and_marks_the_original() {
+    let (mut core, entry) = allowed_p1("rollback-allowed");
+    core.mark_applied("p1").unwrap();
+
+    // RoH rises back to 0.20, the state recorded before p1, inside the ceiling.
+    let signatures = inverse(&core, &entry).signatures;
+    let report = core.rollback(&entry.entry_id, signatures).unwrap();
+    assert!(report.is_allowed(), "{report:?}");
+    let undo = core.ledger_head().unwrap().clone();
+    assert_eq!(undo.rollback_of(), Some(entry.entry_id.as_str()));
+    assert_eq!(undo.roh_after, 0.20);
+
+    assert_eq!(core.proposal_state("p1"), Some(Decision::Applied));
+    core.mark_applied(&undo.proposal_id).unwrap();
+    assert_eq!(core.proposal_state("p1"), Some(Decision::RolledBack));
+    assert_eq!(
+        core.proposal_state(&undo.proposal_id),
+        Some(Decision::Applied)
+    );
+
+    // An undone change cannot be rolled back again.
+    let signatures = inverse(&core, &entry).signatures;
+    assert!(core.rollback(&entry.entry_id, signatures).is_err());
+}
+
+#[test]
+fn rollback_must_restore_the_recorded_roh() {
+    let (mut core, entry) = allowed_p1("rollback-roh");
+    core.mark_applied("p1").unwrap();
+
+    let mut rec = inverse(&core, &entry);
+    rec.roh_after = 0.15;
+    let report = core
+        .evaluate_evolution_record(stamped(rec, "rollback-wrong-roh"))
+        .unwrap();
+    assert_eq!(
+        report.rejection().unwrap().code(),
+        ReasonCode::RollbackRohMismatch
+    );
+    assert_eq!(core.proposal_state("p1"), Some(Decision::Applied));
+}
+
+#[test]
+fn only_applied_changes_can_be_rolled_back() {
+    let (mut core, entry) = allowed_p1("rollback-unapplied");
+
+    let signatures = inverse(&core, &entry).signatures;
+    assert!(core.rollback(&entry.entry_id, signatures).is_err());
+
+    // Submitted directly, the guard refuses it.
+    let rec = stamped(inverse(&core, &entry), "rollback-early");
+    let report = core.evaluate_evolution_record(rec).unwrap();
+    assert_eq!(
+        report.rejection().unwrap().code(),
+        ReasonCode::RollbackTargetNotApplied
+    );
+    assert_eq!(core.proposal_state("p1"), Some(Decision::Allowed));
+}
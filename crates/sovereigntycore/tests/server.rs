@@ -0,0 +1,142 @@
+mod common;
+
+use axum::body::{self, Body};
+use axum::http::{Request, StatusCode};
+use ed25519_dalek::SigningKey;
+use organiccpualn::hexstamp::Hexstamped;
+use serde_json::{json, Value};
+use sovereigntycore::server::{router, AppState};
+use sovereigntycore::types::Scope;
+use sovereigntycore::DecisionReport;
+use tower::ServiceExt;
+
+async fn call(state: &AppState, method: &str, uri: &str, body: Body) -> (StatusCode, String) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(body)
+        .unwrap();
+    let response = router(state.clone()).oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+async fn get(state: &AppState, uri: &str) -> (StatusCode, Value) {
+    let (status, body) = call(state, "GET", uri, Body::empty()).await;
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+async fn post(state: &AppState, uri: &str, body: String) -> (StatusCode, String) {
+    call(state, "POST", uri, Body::from(body)).await
+}
+
+// The default current-thread runtime: evaluations must not need a multi-thread one.
+#[tokio::test]
+async fn ready_once_the_core_is_loaded() {
+    let state = AppState::default();
+    let (status, body) = call(&state, "GET", "/health", Body::empty()).await;
+    assert_eq!((status, body.as_str()), (StatusCode::OK, "ok"));
+    let (status, _) = call(&state, "GET", "/ready", Body::empty()).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        get(&state, "/roh_ceiling").await,
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            json!({ "error": "core is loading" })
+        )
+    );
+
+    state.set_core(common::core(&common::scratch("server")));
+    let (status, _) = call(&state, "GET", "/ready", Body::empty()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        get(&state, "/roh_ceiling").await,
+        (StatusCode::OK, json!({ "roh_ceiling": 0.3 }))
+    );
+    assert_eq!(
+        get(&state, "/ledger/head").await,
+        (StatusCode::OK, Value::Null)
+    );
+    let (status, policy) = get(&state, "/policy").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(policy["subject_id"], common::SUBJECT);
+}
+
+#[tokio::test]
+async fn evaluations_report_decisions_and_client_errors() {
+    let dir = common::scratch("server-evaluate");
+    let state = AppState::default();
+    state.set_core(common::core(&dir));
+
+    let record = serde_json::to_string(&common::tuning("http-1", 0.10, 0.05)).unwrap();
+    let (status, body) = post(&state, "/evaluate_record", record.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: DecisionReport = serde_json::from_str(&body).unwrap();
+    assert!(report.is_allowed(), "{body}");
+    let (status, head) = get(&state, "/ledger/head").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(head["proposal_id"], "http-1");
+    assert_eq!(head["roh_after"], 0.05);
+
+    // A replayed proposal is the client's error, reported without server paths.
+    let (status, body) = post(&state, "/evaluate_record", record).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        serde_json::from_str::<Value>(&body).unwrap(),
+        json!({ "error": "proposal `http-1` cannot move from Allowed to Proposed" })
+    );
+    assert!(!body.contains(dir.to_str().unwrap()), "{body}");
+
+    let (status, _) = post(&state, "/evaluate_record", "{}".into()).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (swarm_state, lifeforce) = common::live_state();
+    let mut proposal = common::update("http-2", Scope::DayToDayTuning);
+    proposal.roh_before = 0.05;
+    proposal.roh_after = 0.04;
+    proposal.sign(common::SUBJECT, &SigningKey::from_bytes(&common::HOST_KEY));
+    let request = json!({
+        "proposal": proposal,
+        "swarm_state": swarm_state,
+        "lifeforce": lifeforce,
+    });
+    let (status, body) = post(&state, "/evaluate_update", request.to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    let report: DecisionReport = serde_json::from_str(&body).unwrap();
+    assert!(report.is_allowed(), "{body}");
+    let (_, head) = get(&state, "/ledger/head").await;
+    assert_eq!(head["proposal_id"], "http-2");
+}
+
+#[tokio::test]
+async fn metrics_label_only_the_governed_subject() {
+    let state = AppState::default();
+    state.set_core(common::core(&common::scratch("server-metrics")));
+
+    let mut foreign = common::tuning("http-foreign", 0.10, 0.05);
+    foreign.subjectid = "bostrom1clientchosen".into();
+    foreign.stamp();
+    let (status, body) = post(
+        &state,
+        "/evaluate_record",
+        serde_json::to_string(&foreign).unwrap(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let report: DecisionReport = serde_json::from_str(&body).unwrap();
+    assert!(report.rejection().is_some(), "{body}");
+
+    let (status, metrics) = call(&state, "GET", "/metrics", Body::empty()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(
+        metrics.contains(
+            r#"sovereignty_evolution_decisions_total{decision="Rejected",subject_id="ungoverned"}"#
+        ),
+        "{metrics}"
+    );
+    assert!(!metrics.contains("bostrom1clientchosen"), "{metrics}");
+}